@@ -34,7 +34,13 @@ pub async fn main(
     let generator = app.get::<Service<dyn SecureGenerator>>()?.clone();
 
     Ok(schema
-        .execute(request.into_inner().data(db).data(generator))
+        .execute(
+            request
+                .into_inner()
+                .data(db)
+                .data(generator)
+                .data(app.into_inner()),
+        )
         .await
         .into())
 }
@@ -1,4 +1,4 @@
-use async_graphql::{ComplexObject, Context, InputObject, Object, SimpleObject};
+use async_graphql::{ComplexObject, Context, InputObject, MaybeUndefined, Object, SimpleObject};
 use uuid::Uuid;
 
 use accesso_app::Service;
@@ -14,6 +14,9 @@ pub struct Application {
     redirect_uri: Vec<String>,
     title: String,
     allowed_registrations: bool,
+    post_logout_redirect_uri: Vec<String>,
+    backchannel_logout_uri: Option<String>,
+    require_dpop: bool,
     #[graphql(skip)]
     secret_key: String,
 }
@@ -26,6 +29,9 @@ impl From<accesso_core::models::Application> for Application {
             redirect_uri: app.redirect_uri,
             title: app.title,
             allowed_registrations: app.allowed_registrations,
+            post_logout_redirect_uri: app.post_logout_redirect_uri,
+            backchannel_logout_uri: app.backchannel_logout_uri,
+            require_dpop: app.require_dpop,
             secret_key: app.secret_key,
         }
     }
@@ -40,6 +46,9 @@ impl Into<accesso_core::models::Application> for Application {
             title: self.title,
             secret_key: self.secret_key,
             allowed_registrations: self.allowed_registrations,
+            post_logout_redirect_uri: self.post_logout_redirect_uri,
+            backchannel_logout_uri: self.backchannel_logout_uri,
+            require_dpop: self.require_dpop,
         }
     }
 }
@@ -68,6 +77,9 @@ pub struct ApplicationSecret {
     redirect_uri: Vec<String>,
     title: String,
     allowed_registrations: bool,
+    post_logout_redirect_uri: Vec<String>,
+    backchannel_logout_uri: Option<String>,
+    require_dpop: bool,
     /// Allowed to read only after application is created
     secret_key: String,
 }
@@ -80,6 +92,9 @@ impl From<accesso_core::models::Application> for ApplicationSecret {
             redirect_uri: app.redirect_uri,
             title: app.title,
             allowed_registrations: app.allowed_registrations,
+            post_logout_redirect_uri: app.post_logout_redirect_uri,
+            backchannel_logout_uri: app.backchannel_logout_uri,
+            require_dpop: app.require_dpop,
             secret_key: app.secret_key,
         }
     }
@@ -117,6 +132,10 @@ struct ApplicationCreate {
     redirect_uri: Vec<String>,
     is_dev: Option<bool>,
     allowed_registrations: Option<bool>,
+    post_logout_redirect_uri: Option<Vec<String>>,
+    backchannel_logout_uri: Option<String>,
+    /// Issue only access tokens bound to DPoP key
+    require_dpop: Option<bool>,
 }
 
 #[derive(InputObject)]
@@ -126,6 +145,10 @@ struct ApplicationEdit {
     redirect_uri: Option<Vec<String>>,
     is_dev: Option<bool>,
     allowed_registrations: Option<bool>,
+    post_logout_redirect_uri: Option<Vec<String>>,
+    /// Pass `null` to disable back-channel logout
+    backchannel_logout_uri: MaybeUndefined<String>,
+    require_dpop: Option<bool>,
 }
 
 #[Object]
@@ -137,15 +160,18 @@ impl MutationApplication {
     ) -> async_graphql::Result<ApplicationSecret> {
         let db = context.data::<Service<dyn Repository>>()?;
         let generator = context.data::<Service<dyn SecureGenerator>>()?;
-        let app = db
-            .application_create(accesso_core::contracts::ApplicationForm {
-                title: form.title,
-                redirect_uri: form.redirect_uri,
-                is_dev: form.is_dev.unwrap_or_default(),
-                allowed_registrations: form.allowed_registrations.unwrap_or_default(),
-                secret_key: generator.generate_token_long(),
-            })
-            .await?;
+        let form = accesso_core::contracts::ApplicationForm {
+            title: form.title,
+            redirect_uri: form.redirect_uri,
+            is_dev: form.is_dev.unwrap_or_default(),
+            allowed_registrations: form.allowed_registrations.unwrap_or_default(),
+            post_logout_redirect_uri: form.post_logout_redirect_uri.unwrap_or_default(),
+            backchannel_logout_uri: form.backchannel_logout_uri,
+            require_dpop: form.require_dpop.unwrap_or_default(),
+            secret_key: generator.generate_token_long(),
+        };
+        validate_redirect_uri(&form)?;
+        let app = db.application_create(form).await?;
 
         Ok(app.into())
     }
@@ -163,18 +189,27 @@ impl MutationApplication {
             let is_dev = form.is_dev.unwrap_or(app.is_dev);
             let redirect_uri = form.redirect_uri.unwrap_or(app.redirect_uri);
             let title = form.title.unwrap_or(app.title);
-            let app = db
-                .application_edit(
-                    form.id,
-                    accesso_core::contracts::ApplicationForm {
-                        title,
-                        redirect_uri,
-                        is_dev,
-                        allowed_registrations,
-                        secret_key: app.secret_key,
-                    },
-                )
-                .await?;
+            let post_logout_redirect_uri = form
+                .post_logout_redirect_uri
+                .unwrap_or(app.post_logout_redirect_uri);
+            let backchannel_logout_uri = match form.backchannel_logout_uri {
+                MaybeUndefined::Undefined => app.backchannel_logout_uri,
+                MaybeUndefined::Null => None,
+                MaybeUndefined::Value(uri) => Some(uri),
+            };
+            let require_dpop = form.require_dpop.unwrap_or(app.require_dpop);
+            let edit = accesso_core::contracts::ApplicationForm {
+                title,
+                redirect_uri,
+                is_dev,
+                allowed_registrations,
+                post_logout_redirect_uri,
+                backchannel_logout_uri,
+                require_dpop,
+                secret_key: app.secret_key,
+            };
+            validate_redirect_uri(&edit)?;
+            let app = db.application_edit(form.id, edit).await?;
             if let Some(app) = app {
                 Ok(Some(app.into()))
             } else {
@@ -209,3 +244,10 @@ impl MutationApplication {
         }
     }
 }
+
+fn validate_redirect_uri(
+    form: &accesso_core::contracts::ApplicationForm,
+) -> async_graphql::Result<()> {
+    form.validate_redirect_uri()
+        .map_err(|(uri, error)| async_graphql::Error::new(format!("{}: {}", error, uri)))
+}
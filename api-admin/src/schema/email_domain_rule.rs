@@ -0,0 +1,106 @@
+use async_graphql::*;
+
+use accesso_app::Service;
+use accesso_core::contracts::Repository;
+use accesso_core::models;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum EmailDomainRuleKind {
+    Allow,
+    Deny,
+}
+
+impl From<models::EmailDomainRuleKind> for EmailDomainRuleKind {
+    fn from(kind: models::EmailDomainRuleKind) -> Self {
+        match kind {
+            models::EmailDomainRuleKind::Allow => Self::Allow,
+            models::EmailDomainRuleKind::Deny => Self::Deny,
+        }
+    }
+}
+
+impl Into<models::EmailDomainRuleKind> for EmailDomainRuleKind {
+    fn into(self) -> models::EmailDomainRuleKind {
+        match self {
+            Self::Allow => models::EmailDomainRuleKind::Allow,
+            Self::Deny => models::EmailDomainRuleKind::Deny,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct EmailDomainRule {
+    id: uuid::Uuid,
+    domain: String,
+    kind: EmailDomainRuleKind,
+    /// Rule is global if application is not set
+    application_id: Option<uuid::Uuid>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<models::EmailDomainRule> for EmailDomainRule {
+    fn from(rule: models::EmailDomainRule) -> Self {
+        Self {
+            id: rule.id,
+            domain: rule.domain,
+            kind: rule.kind.into(),
+            application_id: rule.application_id,
+            created_at: rule.created_at,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct QueryEmailDomainRule;
+
+#[Object]
+impl QueryEmailDomainRule {
+    async fn email_domain_rules(
+        &self,
+        context: &Context<'_>,
+    ) -> async_graphql::Result<Vec<EmailDomainRule>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        Ok(db
+            .email_domain_rules_list()
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct MutationEmailDomainRule;
+
+#[Object]
+impl MutationEmailDomainRule {
+    /// Rule matches the domain and its subdomains.
+    /// Returns `null` if the same rule already exists
+    pub async fn email_domain_rule_create(
+        &self,
+        context: &Context<'_>,
+        #[graphql(validator(min_length = 3))] domain: String,
+        kind: EmailDomainRuleKind,
+        application_id: Option<uuid::Uuid>,
+    ) -> async_graphql::Result<Option<EmailDomainRule>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+
+        if let Some(application_id) = application_id {
+            if db.application_find_by_id(application_id).await?.is_none() {
+                return Err("Application not found".into());
+            }
+        }
+
+        let rule = models::EmailDomainRule::new(&domain, kind.into(), application_id);
+        Ok(db.email_domain_rule_create(rule).await?.map(Into::into))
+    }
+
+    pub async fn email_domain_rule_delete(
+        &self,
+        context: &Context<'_>,
+        id: uuid::Uuid,
+    ) -> async_graphql::Result<Option<EmailDomainRule>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        Ok(db.email_domain_rule_delete(id).await?.map(Into::into))
+    }
+}
@@ -0,0 +1,44 @@
+use async_graphql::*;
+
+use accesso_core::models;
+
+/// Reported by the email provider, emails are sent only to deliverable addresses
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum EmailStatus {
+    Deliverable,
+    Bounced,
+    Complained,
+}
+
+impl From<models::EmailStatus> for EmailStatus {
+    fn from(status: models::EmailStatus) -> Self {
+        match status {
+            models::EmailStatus::Deliverable => Self::Deliverable,
+            models::EmailStatus::Bounced => Self::Bounced,
+            models::EmailStatus::Complained => Self::Complained,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct EmailEvent {
+    provider: String,
+    provider_event_id: String,
+    email: String,
+    status: EmailStatus,
+    reason: Option<String>,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<models::EmailEvent> for EmailEvent {
+    fn from(event: models::EmailEvent) -> Self {
+        Self {
+            provider: event.provider,
+            provider_event_id: event.provider_event_id,
+            email: event.email,
+            status: event.status.into(),
+            reason: event.reason,
+            occurred_at: event.occurred_at,
+        }
+    }
+}
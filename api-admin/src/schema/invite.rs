@@ -0,0 +1,102 @@
+use async_graphql::*;
+
+use accesso_app::Service;
+use accesso_core::contracts::{Repository, SecureGenerator};
+
+const INVITE_WORDS: u8 = 5;
+const MAX_INVITE_INSERT_ATTEMPTS: u8 = 10;
+
+#[derive(SimpleObject, Clone)]
+pub struct Invite {
+    invite: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    application_id: Option<uuid::Uuid>,
+    user_id: Option<uuid::Uuid>,
+    registered_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<accesso_core::models::ApplicationInvite> for Invite {
+    fn from(invite: accesso_core::models::ApplicationInvite) -> Self {
+        Self {
+            invite: invite.invite,
+            created_at: invite.created_at,
+            application_id: invite.application_id,
+            user_id: invite.user_id,
+            registered_at: invite.registered_at,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct QueryInvite;
+
+#[Object]
+impl QueryInvite {
+    /// Invites of the application, or all invites if application is not set
+    async fn invites(
+        &self,
+        context: &Context<'_>,
+        application_id: Option<uuid::Uuid>,
+    ) -> async_graphql::Result<Vec<Invite>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        Ok(db
+            .invites_list(application_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct MutationInvite;
+
+#[Object]
+impl MutationInvite {
+    /// Generates invites to register, optionally tied to the application
+    pub async fn invites_generate(
+        &self,
+        context: &Context<'_>,
+        #[graphql(validator(minimum = 1, maximum = 100))] count: u8,
+        application_id: Option<uuid::Uuid>,
+    ) -> async_graphql::Result<Vec<Invite>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        let generator = context.data::<Service<dyn SecureGenerator>>()?;
+
+        if let Some(application_id) = application_id {
+            if db.application_find_by_id(application_id).await?.is_none() {
+                return Err("Application not found".into());
+            }
+        }
+
+        let mut invites = Vec::with_capacity(count as usize);
+
+        for _ in 0..count {
+            let mut attempts = 0u8;
+
+            let invite = loop {
+                attempts += 1;
+                let invite = generator.secure_words(INVITE_WORDS);
+
+                match db.invite_create(invite, application_id).await? {
+                    Some(invite) => break invite,
+                    None if attempts < MAX_INVITE_INSERT_ATTEMPTS => continue,
+                    None => return Err("Could not generate unique invite".into()),
+                }
+            };
+
+            invites.push(invite.into());
+        }
+
+        Ok(invites)
+    }
+
+    pub async fn invite_delete(
+        &self,
+        context: &Context<'_>,
+        invite: String,
+    ) -> async_graphql::Result<Option<Invite>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        Ok(db.invite_delete(invite).await?.map(Into::into))
+    }
+}
@@ -2,8 +2,12 @@ use async_graphql::{EmptySubscription, MergedObject, Object, Schema, SchemaBuild
 
 mod access_token;
 mod application;
+mod email_domain_rule;
+mod email_event;
+mod invite;
 mod register_request;
 mod user;
+mod user_import;
 mod user_registration;
 
 #[derive(MergedObject, Default)]
@@ -11,6 +15,8 @@ pub struct Query(
     CommonQuery,
     access_token::QueryAccessToken,
     application::QueryApplication,
+    email_domain_rule::QueryEmailDomainRule,
+    invite::QueryInvite,
     register_request::QueryRequesterRequest,
     user::QueryUser,
 );
@@ -29,8 +35,11 @@ impl CommonQuery {
 pub struct Mutation(
     access_token::MutationAccessToken,
     application::MutationApplication,
+    email_domain_rule::MutationEmailDomainRule,
+    invite::MutationInvite,
     register_request::MutationRegisterRequest,
     user::MutationUser,
+    user_import::MutationUserImport,
 );
 
 pub type AdminSchema = Schema<Query, Mutation, EmptySubscription>;
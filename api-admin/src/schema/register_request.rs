@@ -2,11 +2,17 @@ use accesso_app::Service;
 use accesso_core::contracts::{Repository, SecureGenerator};
 use async_graphql::{Context, Object, SimpleObject};
 
+use super::email_event::EmailStatus;
+
 #[derive(SimpleObject)]
 pub struct RegisterRequest {
     email: String,
     code: String,
     expires_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    last_sent_at: chrono::DateTime<chrono::Utc>,
+    locale: String,
+    email_status: EmailStatus,
 }
 
 impl From<accesso_core::models::RegisterRequest> for RegisterRequest {
@@ -15,6 +21,10 @@ impl From<accesso_core::models::RegisterRequest> for RegisterRequest {
             email: register_request.email,
             code: register_request.code,
             expires_at: register_request.expires_at,
+            created_at: register_request.created_at,
+            last_sent_at: register_request.last_sent_at,
+            locale: register_request.locale,
+            email_status: register_request.email_status.into(),
         }
     }
 }
@@ -77,8 +87,12 @@ impl MutationRegisterRequest {
         let db = context.data::<Service<dyn Repository>>()?;
         let generator = context.data::<Service<dyn SecureGenerator>>()?;
         let code = generator.confirmation_code();
-        let request = accesso_core::models::RegisterRequest::new(email, code);
-        let result = db.register_request_save(request).await?;
+        let request = accesso_core::models::RegisterRequest::new(
+            email,
+            code,
+            accesso_core::services::DEFAULT_LOCALE.to_owned(),
+        );
+        let result = db.register_request_save(request, None).await?;
         Ok(result.into())
     }
 
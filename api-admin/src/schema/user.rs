@@ -1,8 +1,14 @@
+use std::sync::Arc;
+
 use async_graphql::*;
 
+use super::email_event::{EmailEvent, EmailStatus};
 use super::user_registration::UserRegistration;
-use accesso_app::Service;
+use accesso_app::{App, Service};
+use accesso_core::app::email_domain::EmailDomainPolicy;
 use accesso_core::contracts::{Repository, SecureGenerator, UserEditForm};
+use accesso_core::models::LoginAttemptKind;
+use accesso_core::services::locale::LOCALES;
 
 #[derive(SimpleObject, Clone)]
 #[graphql(complex)]
@@ -12,6 +18,9 @@ pub struct User {
     canonical_email: String,
     first_name: String,
     last_name: String,
+    locale: String,
+    email_status: EmailStatus,
+    email_verified: bool,
 }
 
 impl From<accesso_core::models::User> for User {
@@ -22,6 +31,9 @@ impl From<accesso_core::models::User> for User {
             canonical_email: user.canonical_email,
             first_name: user.first_name,
             last_name: user.last_name,
+            locale: user.locale,
+            email_status: user.email_status.into(),
+            email_verified: user.email_verified,
         }
     }
 }
@@ -41,6 +53,31 @@ impl User {
         let db = context.data::<Service<dyn Repository>>()?;
         Ok(db.user_access_tokens_count(self.id).await?)
     }
+
+    /// Login is locked out after too many failed attempts
+    async fn locked_until(
+        &self,
+        context: &Context<'_>,
+    ) -> async_graphql::Result<Option<chrono::DateTime<chrono::Utc>>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        let attempts = db
+            .login_attempts_get(LoginAttemptKind::Account, self.canonical_email.clone())
+            .await?;
+        Ok(attempts
+            .and_then(|attempts| attempts.locked_until)
+            .filter(|locked_until| *locked_until > chrono::Utc::now()))
+    }
+
+    /// Bounces and complaints reported for the email, latest first
+    async fn email_events(&self, context: &Context<'_>) -> async_graphql::Result<Vec<EmailEvent>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+        Ok(db
+            .email_events_list(self.email.clone())
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
 }
 
 #[derive(Default)]
@@ -93,6 +130,8 @@ pub struct UserEdit {
     email: Option<String>,
     first_name: Option<String>,
     last_name: Option<String>,
+    /// One of the supported locales: `en`, `ru`
+    locale: Option<String>,
 }
 
 #[derive(Default)]
@@ -112,6 +151,18 @@ impl MutationUser {
         user: UserEdit,
     ) -> async_graphql::Result<Option<User>> {
         let db = context.data::<Service<dyn Repository>>()?;
+
+        if let Some(email) = &user.email {
+            let app = context.data::<Arc<App>>()?;
+            app.email_domain_check(email, None).await?;
+        }
+
+        if let Some(locale) = &user.locale {
+            if !LOCALES.contains(&locale.as_str()) {
+                return Err("Locale is not supported".into());
+            }
+        }
+
         Ok(Some(
             db.user_edit_by_id(
                 user.id,
@@ -119,6 +170,7 @@ impl MutationUser {
                     first_name: user.first_name,
                     last_name: user.last_name,
                     email: user.email,
+                    locale: user.locale,
                 },
             )
             .await?
@@ -126,6 +178,40 @@ impl MutationUser {
         ))
     }
 
+    /// Removes lockout and failed login attempts of the user
+    pub async fn user_unlock(
+        &self,
+        context: &Context<'_>,
+        user_id: uuid::Uuid,
+    ) -> async_graphql::Result<Option<User>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+
+        if let Some(user) = db.user_get_by_id(user_id).await? {
+            db.login_attempts_clear(LoginAttemptKind::Account, user.canonical_email.clone())
+                .await?;
+            Ok(Some(user.into()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Sends emails to the user again, for example after the mailbox is fixed.
+    /// Reported events are kept
+    pub async fn user_email_status_reset(
+        &self,
+        context: &Context<'_>,
+        user_id: uuid::Uuid,
+    ) -> async_graphql::Result<Option<User>> {
+        let db = context.data::<Service<dyn Repository>>()?;
+
+        if let Some(user) = db.user_get_by_id(user_id).await? {
+            db.email_status_reset(user.email.clone()).await?;
+            Ok(db.user_get_by_id(user_id).await?.map(Into::into))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub async fn user_password_reset(
         &self,
         context: &Context<'_>,
@@ -134,7 +220,7 @@ impl MutationUser {
         let db = context.data::<Service<dyn Repository>>()?;
         let generator = context.data::<Service<dyn SecureGenerator>>()?;
         let password = generator.generate_token();
-        let password_hash = generator.password_hash(password.clone()).0;
+        let password_hash = generator.password_hash(password.clone()).await?;
         let user = db.user_password_reset(user_id, password_hash).await?;
 
         if let Some(user) = user {
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use async_graphql::*;
+
+use super::user::User;
+use accesso_app::App;
+use accesso_core::app::user_import::{UserImport, UserImportError, UserImportRow};
+
+#[derive(InputObject)]
+pub struct UserImportInput {
+    email: String,
+    /// bcrypt hash or PHC string of argon2, scrypt or PBKDF2 hash
+    password_hash: String,
+    first_name: String,
+    last_name: String,
+    /// `en` when not set
+    locale: Option<String>,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum UserImportErrorCode {
+    InvalidForm,
+    UnsupportedPasswordHash,
+    EmailAlreadyRegistered,
+    Unexpected,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct UserImportRowError {
+    /// Index of the row in the input, starting from zero
+    row: u64,
+    email: String,
+    code: UserImportErrorCode,
+    message: String,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct UsersImportReport {
+    created: Vec<User>,
+    errors: Vec<UserImportRowError>,
+}
+
+#[derive(Default)]
+pub struct MutationUserImport;
+
+#[Object]
+impl MutationUserImport {
+    /// Creates users with password hashes exported from another application
+    pub async fn users_import(
+        &self,
+        context: &Context<'_>,
+        users: Vec<UserImportInput>,
+    ) -> async_graphql::Result<UsersImportReport> {
+        let app = context.data::<Arc<App>>()?;
+        let rows = users
+            .into_iter()
+            .map(|user| UserImportRow {
+                email: user.email,
+                password_hash: user.password_hash,
+                first_name: user.first_name,
+                last_name: user.last_name,
+                locale: user.locale,
+            })
+            .collect();
+
+        let report = app.users_import(rows).await;
+
+        Ok(UsersImportReport {
+            created: report.created.into_iter().map(Into::into).collect(),
+            errors: report
+                .failed
+                .into_iter()
+                .map(|failed| {
+                    let code = match failed.error {
+                        UserImportError::Unexpected(ref error) => {
+                            tracing::error!(%error, row = failed.row, "Failed to import user");
+                            UserImportErrorCode::Unexpected
+                        }
+                        UserImportError::InvalidForm(_) => UserImportErrorCode::InvalidForm,
+                        UserImportError::UnsupportedPasswordHash => {
+                            UserImportErrorCode::UnsupportedPasswordHash
+                        }
+                        UserImportError::EmailAlreadyRegistered => {
+                            UserImportErrorCode::EmailAlreadyRegistered
+                        }
+                    };
+                    UserImportRowError {
+                        row: failed.row as u64,
+                        email: failed.email,
+                        code,
+                        message: failed.error.to_string(),
+                    }
+                })
+                .collect(),
+        })
+    }
+}
@@ -41,6 +41,17 @@ pub mod api {
             self
         }
 
+        pub fn bind_oauth_end_session<F, T, R, Res>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            Res: Responder + 'static,
+            R: Future<Output = Result<Res, super::paths::oauth_end_session::Error>> + 'static,
+        {
+            self.api = self.api.bind("/oauth/end-session", Method::POST, handler);
+            self
+        }
+
         pub fn bind_oauth_token<F, T, R>(mut self, handler: F) -> Self
         where
             F: Handler<T, R>,
@@ -71,6 +82,21 @@ pub mod api {
             self
         }
 
+        pub fn bind_register_resend<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::register_resend::Response,
+                        super::paths::register_resend::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/register/resend", Method::POST, handler);
+            self
+        }
+
         pub fn bind_register_confirmation<F, T, R>(mut self, handler: F) -> Self
         where
             F: Handler<T, R>,
@@ -110,6 +136,51 @@ pub mod api {
             self
         }
 
+        pub fn bind_session_list<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::session_list::Response,
+                        super::paths::session_list::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/session/list", Method::POST, handler);
+            self
+        }
+
+        pub fn bind_session_revoke<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::session_revoke::Response,
+                        super::paths::session_revoke::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/session/revoke", Method::POST, handler);
+            self
+        }
+
+        pub fn bind_session_unlock<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::session_unlock::Response,
+                        super::paths::session_unlock::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/session/unlock", Method::POST, handler);
+            self
+        }
+
         pub fn bind_session_get<F, T, R>(mut self, handler: F) -> Self
         where
             F: Handler<T, R>,
@@ -169,6 +240,51 @@ pub mod api {
             self.api = self.api.bind("/applications.list", Method::POST, handler);
             self
         }
+
+        pub fn bind_access_token_create<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::access_token_create::Response,
+                        super::paths::access_token_create::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/access-token.create", Method::POST, handler);
+            self
+        }
+
+        pub fn bind_access_tokens_list<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::access_tokens_list::Response,
+                        super::paths::access_tokens_list::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/access-tokens.list", Method::POST, handler);
+            self
+        }
+
+        pub fn bind_access_token_revoke<F, T, R>(mut self, handler: F) -> Self
+        where
+            F: Handler<T, R>,
+            T: FromRequest + 'static,
+            R: Future<
+                    Output = Result<
+                        super::paths::access_token_revoke::Response,
+                        super::paths::access_token_revoke::Error,
+                    >,
+                > + 'static,
+        {
+            self.api = self.api.bind("/access-token.revoke", Method::POST, handler);
+            self
+        }
     }
 }
 
@@ -242,9 +358,13 @@ pub mod components {
 
         #[derive(Debug, Serialize, thiserror::Error)]
         pub enum RegisterFailedError {
-            #[serde(rename = "email_already_registered")]
-            #[error("Email already registered: {0}")]
-            EmailAlreadyRegistered(#[serde(skip)] String),
+            #[serde(rename = "invite_invalid")]
+            #[error("Invite not found or already used")]
+            InviteInvalid,
+
+            #[serde(rename = "email_domain_not_allowed")]
+            #[error("Email domain is not allowed")]
+            EmailDomainNotAllowed,
 
             #[serde(rename = "invalid_form")]
             #[error(transparent)]
@@ -252,10 +372,39 @@ pub mod components {
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
-        #[error(transparent)]
+        #[error("{error}")]
         pub struct RegisterFailed {
-            #[from]
             pub error: RegisterFailedError,
+
+            #[doc = "Localized messages of invalid fields, set with invalid_form error"]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub messages: Option<std::collections::BTreeMap<String, Vec<String>>>,
+        }
+
+        impl From<RegisterFailedError> for RegisterFailed {
+            fn from(error: RegisterFailedError) -> Self {
+                Self {
+                    error,
+                    messages: None,
+                }
+            }
+        }
+
+        #[doc = "Code was sent recently"]
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(rename_all = "camelCase")]
+        #[error("Too many requests, retry after {retry_after} seconds")]
+        pub struct RegisterResendTooManyRequests {
+            pub error: RegisterResendTooManyRequestsError,
+
+            #[doc = "Seconds until the code can be sent again"]
+            pub retry_after: i64,
+        }
+
+        #[derive(Debug, Serialize)]
+        #[serde(rename_all = "snake_case")]
+        pub enum RegisterResendTooManyRequestsError {
+            TooManyRequests,
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
@@ -268,6 +417,10 @@ pub mod components {
             #[error(transparent)]
             EmailAlreadyActivated(#[serde(skip)] eyre::Report),
 
+            #[serde(rename = "invite_invalid")]
+            #[error("Invite not found or already used")]
+            InviteInvalid,
+
             #[serde(rename = "invalid_form")]
             #[error(transparent)]
             InvalidForm(
@@ -278,10 +431,22 @@ pub mod components {
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
-        #[error(transparent)]
+        #[error("{error}")]
         pub struct RegisterConfirmationFailed {
-            #[from]
             pub error: RegisterConfirmationFailedError,
+
+            #[doc = "Localized messages of invalid fields, set with invalid_form error"]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub messages: Option<std::collections::BTreeMap<String, Vec<String>>>,
+        }
+
+        impl From<RegisterConfirmationFailedError> for RegisterConfirmationFailed {
+            fn from(error: RegisterConfirmationFailedError) -> Self {
+                Self {
+                    error,
+                    messages: None,
+                }
+            }
         }
 
         #[derive(Debug, Serialize)]
@@ -298,10 +463,22 @@ pub mod components {
 
         #[doc = "Login failed"]
         #[derive(Debug, Serialize, thiserror::Error)]
-        #[error(transparent)]
+        #[error("{error}")]
         pub struct SessionCreateFailed {
-            #[from]
             pub error: SessionCreateFailedError,
+
+            #[doc = "Localized messages of invalid fields, set with invalid_form error"]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub messages: Option<std::collections::BTreeMap<String, Vec<String>>>,
+        }
+
+        impl From<SessionCreateFailedError> for SessionCreateFailed {
+            fn from(error: SessionCreateFailedError) -> Self {
+                Self {
+                    error,
+                    messages: None,
+                }
+            }
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
@@ -323,6 +500,23 @@ pub mod components {
             InvalidPayload,
         }
 
+        #[doc = "Too many failed attempts, login is delayed or locked out"]
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(rename_all = "camelCase")]
+        #[error("Too many attempts, retry after {retry_after} seconds")]
+        pub struct SessionCreateTooManyAttempts {
+            pub error: SessionCreateTooManyAttemptsError,
+
+            #[doc = "Seconds until next attempt is allowed"]
+            pub retry_after: i64,
+        }
+
+        #[derive(Debug, Serialize)]
+        #[serde(rename_all = "snake_case")]
+        pub enum SessionCreateTooManyAttemptsError {
+            TooManyAttempts,
+        }
+
         #[doc = "failed to delete session"]
         #[derive(Debug, Serialize, thiserror::Error)]
         #[error(transparent)]
@@ -360,10 +554,22 @@ pub mod components {
 
         #[doc = "failed to edit account"]
         #[derive(Debug, Serialize, thiserror::Error)]
-        #[error(transparent)]
+        #[error("{error}")]
         pub struct AccountEditFailure {
-            #[from]
             pub error: AccountEditFailureError,
+
+            #[doc = "Localized messages of invalid fields, set with invalid_form error"]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub messages: Option<std::collections::BTreeMap<String, Vec<String>>>,
+        }
+
+        impl From<AccountEditFailureError> for AccountEditFailure {
+            fn from(error: AccountEditFailureError) -> Self {
+                Self {
+                    error,
+                    messages: None,
+                }
+            }
         }
 
         #[derive(Debug, Serialize)]
@@ -372,6 +578,39 @@ pub mod components {
             pub user: super::schemas::SessionUser,
         }
 
+        #[derive(Debug, Serialize)]
+        pub struct SessionListSuccess {
+            pub sessions: Vec<super::schemas::ActiveSession>,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(rename_all = "snake_case")]
+        pub enum SessionRevokeError {
+            #[error("Not found")]
+            NotFound,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[error(transparent)]
+        pub struct SessionRevokeFailure {
+            #[from]
+            pub error: SessionRevokeError,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(rename_all = "snake_case")]
+        pub enum SessionUnlockError {
+            #[error("Not found")]
+            NotFound,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[error(transparent)]
+        pub struct SessionUnlockFailure {
+            #[from]
+            pub error: SessionUnlockError,
+        }
+
         #[doc = "Authorization completed, now access token can be obtained."]
         #[derive(Debug, Serialize)]
         pub struct OAuthAuthorizeDone {
@@ -394,6 +633,9 @@ pub mod components {
         /// - `invalid_scope` — The requested scope is invalid, unknown, or malformed.
         /// - `server_error` — The authorization server encountered an unexpected condition which prevented it from fulfilling the request.
         /// - `temporarily_unavailable` — The authorization server is currently unable to handle the request due to a temporary overloading or maintenance of the server.
+        /// - `login_required` — `prompt=none` was requested without a session, or the authentication is older than `maxAge`.
+        /// - `reauthentication_required` — `prompt=login` was requested, the current user should enter credentials again, then the same request continues.
+        /// - `consent_required` — `prompt=none` was requested, but the user has not registered in the application yet.
         /// [OAuth2 Possible Errors](https://www.oauth.com/oauth2-servers/server-side-apps/possible-errors/)
         #[derive(Debug, Serialize, thiserror::Error)]
         pub enum OAuthAuthorizeRequestFailureError {
@@ -432,6 +674,18 @@ pub mod components {
             #[serde(rename = "temporarily_unavailable")]
             #[error("Temporarily unavailable")]
             TemporarilyUnavailable,
+
+            #[serde(rename = "login_required")]
+            #[error("Login required")]
+            LoginRequired,
+
+            #[serde(rename = "reauthentication_required")]
+            #[error("Reauthentication required")]
+            ReauthenticationRequired,
+
+            #[serde(rename = "consent_required")]
+            #[error("Consent required")]
+            ConsentRequired,
         }
 
         /// There are two different kinds of errors to handle. The first kind of error is when the developer did something wrong when creating the authorization request. The other kind of error is when the user rejects the request (clicks the “Deny” button).
@@ -453,6 +707,33 @@ pub mod components {
             pub state: Option<String>,
         }
 
+        #[doc = "User logged out, application can redirect user back"]
+        #[derive(Debug, Serialize)]
+        pub struct OAuthEndSessionDone {
+            #[doc = "User should be redirected to, if postLogoutRedirectUri passed"]
+            #[serde(rename = "redirectUri")]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub redirect_uri: Option<String>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub state: Option<String>,
+        }
+
+        #[doc = "Failed to end session"]
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[error(transparent)]
+        pub struct OAuthEndSessionFailure {
+            #[from]
+            pub error: OAuthEndSessionFailureError,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        pub enum OAuthEndSessionFailureError {
+            #[serde(rename = "invalid_request")]
+            #[error(transparent)]
+            InvalidRequest(#[serde(skip)] eyre::Report),
+        }
+
         /// The auth services validated the request and responds with an access token
         /// [OAuth2 Example Flow](https://www.oauth.com/oauth2-servers/server-side-apps/example-flow/)
         #[derive(Debug, Serialize)]
@@ -530,6 +811,66 @@ pub mod components {
             pub installed: Vec<super::schemas::Application>,
             pub available: Vec<super::schemas::Application>,
         }
+
+        #[doc = "Personal access token created"]
+        #[derive(Debug, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct AccessTokenCreateSuccess {
+            #[doc = "Value of the token, it is shown only once"]
+            pub token: String,
+            pub access_token: super::schemas::PersonalAccessToken,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        pub enum AccessTokenCreateFailureError {
+            #[serde(rename = "invalid_form")]
+            #[error(transparent)]
+            InvalidForm(
+                #[from]
+                #[serde(skip)]
+                validator::ValidationErrors,
+            ),
+        }
+
+        #[doc = "Failed to create personal access token"]
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[error("{error}")]
+        pub struct AccessTokenCreateFailure {
+            pub error: AccessTokenCreateFailureError,
+
+            #[doc = "Localized messages of invalid fields, set with invalid_form error"]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub messages: Option<std::collections::BTreeMap<String, Vec<String>>>,
+        }
+
+        impl From<AccessTokenCreateFailureError> for AccessTokenCreateFailure {
+            fn from(error: AccessTokenCreateFailureError) -> Self {
+                Self {
+                    error,
+                    messages: None,
+                }
+            }
+        }
+
+        #[derive(Debug, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct AccessTokensListSuccess {
+            pub access_tokens: Vec<super::schemas::PersonalAccessToken>,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(rename_all = "snake_case")]
+        pub enum AccessTokenRevokeError {
+            #[error("Not found")]
+            NotFound,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[error(transparent)]
+        pub struct AccessTokenRevokeFailure {
+            #[from]
+            pub error: AccessTokenRevokeError,
+        }
     }
 
     pub mod request_bodies {
@@ -538,10 +879,21 @@ pub mod components {
         #[derive(Debug, Serialize, Deserialize)]
         pub struct Register {
             pub email: String,
+
+            #[doc = "Required when registration is invite-only"]
+            #[serde(default)]
+            pub invite: Option<String>,
+        }
+
+        #[derive(Debug, Serialize, Deserialize)]
+        pub struct RegisterResend {
+            pub email: String,
         }
 
         #[derive(Debug, Serialize, Deserialize)]
         pub struct RegisterConfirmation {
+            pub email: String,
+
             #[serde(rename = "confirmationCode")]
             pub confirmation_code: String,
 
@@ -558,6 +910,10 @@ pub mod components {
         pub struct SessionCreate {
             pub email: String,
             pub password: String,
+
+            #[doc = "Keep the session for long time while it is used"]
+            #[serde(rename = "rememberMe", default)]
+            pub remember_me: bool,
         }
 
         #[derive(Debug, Serialize, Deserialize)]
@@ -566,6 +922,18 @@ pub mod components {
             pub delete_all_sessions: bool,
         }
 
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct SessionRevokeRequestBody {
+            pub session_id: uuid::Uuid,
+        }
+
+        #[derive(Debug, Deserialize)]
+        pub struct SessionUnlock {
+            #[doc = "Code from the email sent on account lockout"]
+            pub code: String,
+        }
+
         #[derive(Debug, Deserialize)]
         pub struct AccountEdit {
             #[serde(rename = "firstName")]
@@ -573,6 +941,10 @@ pub mod components {
 
             #[serde(rename = "lastName")]
             pub last_name: String,
+
+            #[doc = "Supported locale, not changed when omitted"]
+            #[serde(rename = "locale")]
+            pub locale: Option<String>,
         }
 
         /// responseType is set to code indicating that you want an authorization code as the response.
@@ -611,21 +983,53 @@ pub mod components {
             /// When the user is redirected back to your app, double check that the state value matches what you set it to originally.
             /// This will ensure an attacker can’t intercept the authorization flow.
             pub state: Option<String>,
+
+            #[doc = "Whether the user should be prompted for reauthentication."]
+            pub prompt: Option<OAuthAuthorizePrompt>,
+
+            /// Maximum authentication age in seconds.
+            /// If the user authenticated earlier, `login_required` error is returned.
+            #[serde(rename = "maxAge")]
+            pub max_age: Option<i64>,
         }
 
+        /// `none` — do not display any UI, fail with `login_required` or `consent_required` instead.
+        /// `login` — user should enter credentials even if already authenticated.
         #[derive(Debug, Serialize, Deserialize)]
-        pub enum OAuthAccessTokenExchangeGrantType {
-            #[serde(rename = "authorization_code")]
-            AuthorizationCode,
+        pub enum OAuthAuthorizePrompt {
+            #[serde(rename = "none")]
+            None,
+            #[serde(rename = "login")]
+            Login,
         }
 
         #[derive(Debug, Serialize, Deserialize)]
-        pub struct OAuthAccessTokenExchange {
-            pub grant_type: OAuthAccessTokenExchangeGrantType,
-            pub code: String,
-            pub redirect_uri: String,
-            pub client_id: uuid::Uuid,
-            pub client_secret: String,
+        pub struct OAuthEndSession {
+            #[doc = "Required if postLogoutRedirectUri is passed"]
+            #[serde(rename = "clientId")]
+            pub client_id: Option<uuid::Uuid>,
+
+            #[doc = "Should be registered for the application"]
+            #[serde(rename = "postLogoutRedirectUri")]
+            pub post_logout_redirect_uri: Option<String>,
+
+            #[doc = "Passed back to the application with redirect"]
+            pub state: Option<String>,
+        }
+
+        #[derive(Debug, Serialize, Deserialize)]
+        pub enum OAuthAccessTokenExchangeGrantType {
+            #[serde(rename = "authorization_code")]
+            AuthorizationCode,
+        }
+
+        #[derive(Debug, Serialize, Deserialize)]
+        pub struct OAuthAccessTokenExchange {
+            pub grant_type: OAuthAccessTokenExchangeGrantType,
+            pub code: String,
+            pub redirect_uri: String,
+            pub client_id: uuid::Uuid,
+            pub client_secret: String,
         }
 
         #[derive(Debug, Deserialize)]
@@ -633,6 +1037,21 @@ pub mod components {
         pub struct ApplicationGetRequestBody {
             pub application_id: uuid::Uuid,
         }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct AccessTokenCreate {
+            pub name: String,
+            #[serde(default)]
+            pub scopes: Vec<String>,
+            pub expires_in_days: i64,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct AccessTokenRevokeRequestBody {
+            pub access_token_id: uuid::Uuid,
+        }
     }
 
     pub mod schemas {
@@ -644,28 +1063,394 @@ pub mod components {
             #[serde(rename = "firstName")]
             pub first_name: String,
 
-            #[serde(rename = "lastName")]
-            pub last_name: String,
+            #[serde(rename = "lastName")]
+            pub last_name: String,
+
+            #[serde(rename = "email")]
+            pub email: String,
+
+            #[doc = "Emails and messages for the user are in this locale"]
+            #[serde(rename = "locale")]
+            pub locale: String,
+        }
+
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct Application {
+            pub id: uuid::Uuid,
+            pub title: String,
+            pub allowed_registrations: bool,
+            pub avatar: Option<String>,
+        }
+
+        #[doc = "Token to access own resources without OAuth flow. Value of the token is not included"]
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct PersonalAccessToken {
+            pub id: uuid::Uuid,
+            pub name: String,
+            pub scopes: Vec<String>,
+            pub created_at: chrono::DateTime<chrono::Utc>,
+            pub expires_at: chrono::DateTime<chrono::Utc>,
+            pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+        }
+
+        #[doc = "Session of the user on some device"]
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct ActiveSession {
+            pub id: uuid::Uuid,
+            pub user_agent: Option<String>,
+            pub ip: Option<String>,
+            pub created_at: chrono::DateTime<chrono::Utc>,
+            pub last_seen_at: chrono::DateTime<chrono::Utc>,
+            #[doc = "Request is made with this session"]
+            pub current: bool,
+        }
+    }
+}
+
+pub mod paths {
+    use super::components::responses;
+
+    pub mod register_request {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Created(responses::RegistrationRequestCreated),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Created(r) => HttpResponse::build(StatusCode::OK).json(r),
+                }
+            }
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            BadRequest(#[from] responses::RegisterFailed),
+            #[error(transparent)]
+            Unexpected(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type = match self {
+                    Self::BadRequest(_) => Some(ContentType::Json),
+                    _ => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
+
+    pub mod register_resend {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Created(responses::RegistrationRequestCreated),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Created(r) => HttpResponse::build(StatusCode::OK).json(r),
+                }
+            }
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            BadRequest(#[from] responses::RegisterFailed),
+            #[error(transparent)]
+            TooManyRequests(#[from] responses::RegisterResendTooManyRequests),
+            #[error(transparent)]
+            Unexpected(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type = match self {
+                    Self::BadRequest(_) | Self::TooManyRequests(_) => Some(ContentType::Json),
+                    _ => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Self::TooManyRequests(r) = self {
+                    res = res.insert_header((
+                        actix_web::http::header::RETRY_AFTER,
+                        r.retry_after.to_string(),
+                    ));
+                }
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
+
+    pub mod session_create {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Created(responses::SessionCreateSucceeded),
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            BadRequest(#[from] responses::SessionCreateFailed),
+            #[error(transparent)]
+            TooManyRequests(#[from] responses::SessionCreateTooManyAttempts),
+            #[error(transparent)]
+            Unexpected(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Created(r) => HttpResponse::build(StatusCode::CREATED).json(r),
+                }
+            }
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type = match self {
+                    Self::BadRequest(_) | Self::TooManyRequests(_) => Some(ContentType::Json),
+                    _ => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Self::TooManyRequests(r) = self {
+                    res = res.insert_header((
+                        actix_web::http::header::RETRY_AFTER,
+                        r.retry_after.to_string(),
+                    ));
+                }
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
+
+    pub mod session_delete {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Ok,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            BadRequest(#[from] responses::SessionDeleteFailure),
+            #[error(transparent)]
+            Unauthorized(#[serde(skip)] eyre::Report),
+            #[error(transparent)]
+            Unexpected(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Ok => HttpResponse::build(StatusCode::OK).finish(),
+                }
+            }
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type = match self {
+                    Self::BadRequest(_) => Some(ContentType::Json),
+                    _ => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
+
+    pub mod session_list {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Ok(responses::SessionListSuccess),
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            InternalServerError(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Ok(r) => HttpResponse::build(StatusCode::OK).json(r),
+                }
+            }
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type: Option<ContentType> = match self {
+                    Self::InternalServerError(_) => None,
+                };
 
-            #[serde(rename = "email")]
-            pub email: String,
-        }
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
 
-        #[derive(Debug, Serialize, Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        pub struct Application {
-            pub id: uuid::Uuid,
-            pub title: String,
-            pub allowed_registrations: bool,
-            pub avatar: Option<String>,
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
         }
     }
-}
-
-pub mod paths {
-    use super::components::responses;
 
-    pub mod register_request {
+    pub mod session_revoke {
         use actix_swagger::ContentType;
         use actix_web::http::StatusCode;
         use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
@@ -676,35 +1461,35 @@ pub mod paths {
         #[derive(Debug, Serialize)]
         #[serde(untagged)]
         pub enum Response {
-            Created(responses::RegistrationRequestCreated),
-        }
-
-        impl Responder for Response {
-            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
-                match self {
-                    Response::Created(r) => HttpResponse::build(StatusCode::OK).json(r),
-                }
-            }
+            Ok,
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
         #[serde(untagged)]
         pub enum Error {
             #[error(transparent)]
-            BadRequest(#[from] responses::RegisterFailed),
+            BadRequest(#[from] responses::SessionRevokeFailure),
             #[error(transparent)]
-            Unexpected(
+            InternalServerError(
                 #[from]
                 #[serde(skip)]
                 eyre::Report,
             ),
         }
 
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Ok => HttpResponse::build(StatusCode::OK).finish(),
+                }
+            }
+        }
+
         impl ResponseError for Error {
             fn status_code(&self) -> StatusCode {
                 match self {
-                    Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
                     Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
                 }
             }
 
@@ -729,7 +1514,7 @@ pub mod paths {
         }
     }
 
-    pub mod session_create {
+    pub mod session_unlock {
         use actix_swagger::ContentType;
         use actix_web::http::StatusCode;
         use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
@@ -740,16 +1525,16 @@ pub mod paths {
         #[derive(Debug, Serialize)]
         #[serde(untagged)]
         pub enum Response {
-            Created(responses::SessionCreateSucceeded),
+            Ok,
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
         #[serde(untagged)]
         pub enum Error {
             #[error(transparent)]
-            BadRequest(#[from] responses::SessionCreateFailed),
+            BadRequest(#[from] responses::SessionUnlockFailure),
             #[error(transparent)]
-            Unexpected(
+            InternalServerError(
                 #[from]
                 #[serde(skip)]
                 eyre::Report,
@@ -759,7 +1544,7 @@ pub mod paths {
         impl Responder for Response {
             fn respond_to(self, _: &HttpRequest) -> HttpResponse {
                 match self {
-                    Response::Created(r) => HttpResponse::build(StatusCode::CREATED).json(r),
+                    Response::Ok => HttpResponse::build(StatusCode::OK).finish(),
                 }
             }
         }
@@ -767,8 +1552,8 @@ pub mod paths {
         impl ResponseError for Error {
             fn status_code(&self) -> StatusCode {
                 match self {
-                    Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
                     Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
                 }
             }
 
@@ -793,7 +1578,7 @@ pub mod paths {
         }
     }
 
-    pub mod session_delete {
+    pub mod oauth_end_session {
         use actix_swagger::ContentType;
         use actix_web::http::StatusCode;
         use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
@@ -804,16 +1589,14 @@ pub mod paths {
         #[derive(Debug, Serialize)]
         #[serde(untagged)]
         pub enum Response {
-            Ok,
+            Ok(responses::OAuthEndSessionDone),
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
         #[serde(untagged)]
         pub enum Error {
             #[error(transparent)]
-            BadRequest(#[from] responses::SessionDeleteFailure),
-            #[error(transparent)]
-            Unauthorized(#[serde(skip)] eyre::Report),
+            BadRequest(#[from] responses::OAuthEndSessionFailure),
             #[error(transparent)]
             Unexpected(
                 #[from]
@@ -825,7 +1608,7 @@ pub mod paths {
         impl Responder for Response {
             fn respond_to(self, _: &HttpRequest) -> HttpResponse {
                 match self {
-                    Response::Ok => HttpResponse::build(StatusCode::OK).finish(),
+                    Response::Ok(r) => HttpResponse::build(StatusCode::OK).json(r),
                 }
             }
         }
@@ -835,7 +1618,6 @@ pub mod paths {
                 match self {
                     Error::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
                     Error::BadRequest(_) => StatusCode::BAD_REQUEST,
-                    Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
                 }
             }
 
@@ -1303,4 +2085,192 @@ pub mod paths {
             }
         }
     }
+
+    pub mod access_token_create {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Ok(responses::AccessTokenCreateSuccess),
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            BadRequest(#[from] responses::AccessTokenCreateFailure),
+            #[error(transparent)]
+            InternalServerError(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Ok(r) => HttpResponse::build(StatusCode::OK).json(r),
+                }
+            }
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type = match self {
+                    Self::BadRequest(_) => Some(ContentType::Json),
+                    _ => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
+
+    pub mod access_tokens_list {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Ok(responses::AccessTokensListSuccess),
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            InternalServerError(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Ok(r) => HttpResponse::build(StatusCode::OK).json(r),
+                }
+            }
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type: Option<ContentType> = match self {
+                    Self::InternalServerError(_) => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
+
+    pub mod access_token_revoke {
+        use actix_swagger::ContentType;
+        use actix_web::http::StatusCode;
+        use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+        use serde::Serialize;
+
+        use super::responses;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        pub enum Response {
+            Ok,
+        }
+
+        #[derive(Debug, Serialize, thiserror::Error)]
+        #[serde(untagged)]
+        pub enum Error {
+            #[error(transparent)]
+            BadRequest(#[from] responses::AccessTokenRevokeFailure),
+            #[error(transparent)]
+            InternalServerError(
+                #[from]
+                #[serde(skip)]
+                eyre::Report,
+            ),
+        }
+
+        impl Responder for Response {
+            fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+                match self {
+                    Response::Ok => HttpResponse::build(StatusCode::OK).finish(),
+                }
+            }
+        }
+
+        impl ResponseError for Error {
+            fn status_code(&self) -> StatusCode {
+                match self {
+                    Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+                    Error::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                }
+            }
+
+            fn error_response(&self) -> HttpResponse {
+                let content_type = match self {
+                    Self::BadRequest(_) => Some(ContentType::Json),
+                    _ => None,
+                };
+
+                let mut res = &mut HttpResponse::build(self.status_code());
+                if let Some(content_type) = content_type {
+                    res = res.content_type(content_type.to_string());
+
+                    match content_type {
+                        ContentType::Json => res.body(serde_json::to_string(self).unwrap()),
+                        ContentType::FormData => res.body(serde_plain::to_string(self).unwrap()),
+                    }
+                } else {
+                    HttpResponse::build(self.status_code()).finish()
+                }
+            }
+        }
+    }
 }
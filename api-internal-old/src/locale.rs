@@ -0,0 +1,27 @@
+use actix_web::http::header::ACCEPT_LANGUAGE;
+use futures::future::{ready, Ready};
+
+use accesso_core::services::negotiate_locale;
+
+/// Supported locale negotiated from `Accept-Language`, the default one without the header.
+/// Used for guests, signed in user has own locale
+#[derive(Debug, Clone, Copy)]
+pub struct RequestLocale(pub &'static str);
+
+impl actix_web::FromRequest for RequestLocale {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        _payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let accept_language = req
+            .headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        ready(Ok(Self(negotiate_locale(accept_language))))
+    }
+}
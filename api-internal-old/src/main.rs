@@ -3,7 +3,7 @@
 
 use std::sync::Arc;
 
-use actix_web::{middleware, web, HttpServer};
+use actix_web::{dev::Service as _, middleware, web, HttpServer};
 use eyre::WrapErr;
 use tracing_actix_web::TracingLogger;
 
@@ -12,6 +12,7 @@ use accesso_settings::Settings;
 use routes::account;
 
 mod generated;
+mod locale;
 mod routes;
 mod session;
 
@@ -37,6 +38,12 @@ async fn main() -> eyre::Result<()> {
         tracing::info!("==> PRODUCTION MODE in api-internal-old");
     }
 
+    actix_rt::spawn(accesso_app::run_logout_worker(settings.clone()));
+    actix_rt::spawn(accesso_app::run_email_worker(settings.clone()));
+    if settings.maintenance.run_in_server {
+        actix_rt::spawn(accesso_app::run_maintenance_worker(settings.clone()));
+    }
+
     let settings_clone = settings.clone();
 
     let mut server = HttpServer::new(move || {
@@ -46,6 +53,14 @@ async fn main() -> eyre::Result<()> {
                 let settings = settings.clone();
                 accesso_app::configure(config, settings)
             })
+            .wrap_fn(|req, srv| {
+                let response = srv.call(req);
+                async move {
+                    let mut response = response.await?;
+                    session::reissue_renewed_cookie(&mut response)?;
+                    Ok::<_, actix_web::Error>(response)
+                }
+            })
             .wrap(middleware::Compress::default())
             .wrap(
                 middleware::DefaultHeaders::new()
@@ -58,14 +73,22 @@ async fn main() -> eyre::Result<()> {
             .service(
                 generated::api::create()
                     .bind_oauth_authorize_request(routes::oauth::authorize::route)
+                    .bind_oauth_end_session(routes::oauth::end_session::route)
                     .bind_register_confirmation(routes::register::confirmation::route)
                     // .bind_register_request(routes::register::request::route)
+                    .bind_register_resend(routes::register::resend::route)
                     .bind_session_create(routes::session::create::route)
                     .bind_session_delete(routes::session::delete::route)
                     .bind_session_get(routes::session::get::route)
+                    .bind_session_list(routes::session::list::route)
+                    .bind_session_revoke(routes::session::revoke::route)
+                    .bind_session_unlock(routes::session::unlock::route)
                     .bind_account_edit(account::edit::route)
                     .bind_application_get(routes::application::get::route)
-                    .bind_applications_list(routes::application::list::route),
+                    .bind_applications_list(routes::application::list::route)
+                    .bind_access_token_create(routes::access_token::create::route)
+                    .bind_access_tokens_list(routes::access_token::list::route)
+                    .bind_access_token_revoke(routes::access_token::revoke::route),
             )
     });
 
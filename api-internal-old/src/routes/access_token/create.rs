@@ -0,0 +1,47 @@
+use actix_web::web::{Data, Json};
+
+use accesso_core::app::personal_access_token::{
+    PersonalAccessTokenCreateError, PersonalAccessTokenCreateForm, PersonalAccessTokens,
+};
+use accesso_core::services::locale::validation_messages;
+
+use crate::generated::{
+    components::{request_bodies, responses},
+    paths::access_token_create::{Error, Response},
+};
+use crate::session::Session;
+
+pub async fn route(
+    body: Json<request_bodies::AccessTokenCreate>,
+    app: Data<accesso_app::App>,
+    session: Session,
+) -> Result<Response, Error> {
+    let body = body.into_inner();
+    let form = PersonalAccessTokenCreateForm {
+        name: body.name,
+        scopes: body.scopes,
+        expires_in_days: body.expires_in_days,
+    };
+
+    let created = app
+        .personal_access_token_create(session.user.id, form)
+        .await
+        .map_err(|error| map_error(error, &session.user.locale))?;
+
+    Ok(Response::Ok(responses::AccessTokenCreateSuccess {
+        token: created.token.clone(),
+        access_token: super::map_access_token(created),
+    }))
+}
+
+fn map_error(error: PersonalAccessTokenCreateError, locale: &str) -> Error {
+    use PersonalAccessTokenCreateError::{InvalidForm, Unexpected};
+
+    match error {
+        InvalidForm(errors) => Error::BadRequest(responses::AccessTokenCreateFailure {
+            messages: Some(validation_messages(&errors, locale)),
+            error: errors.into(),
+        }),
+        Unexpected(report) => Error::InternalServerError(report),
+    }
+}
@@ -0,0 +1,28 @@
+use actix_web::web::Data;
+
+use accesso_core::app::personal_access_token::{
+    PersonalAccessTokens, PersonalAccessTokensListError,
+};
+
+use crate::generated::{
+    components::responses::AccessTokensListSuccess,
+    paths::access_tokens_list::{Error, Response},
+};
+use crate::session::Session;
+
+pub async fn route(app: Data<accesso_app::App>, session: Session) -> Result<Response, Error> {
+    let tokens = app
+        .personal_access_tokens_list(session.user.id)
+        .await
+        .map_err(map_error)?;
+
+    Ok(Response::Ok(AccessTokensListSuccess {
+        access_tokens: tokens.into_iter().map(super::map_access_token).collect(),
+    }))
+}
+
+fn map_error(error: PersonalAccessTokensListError) -> Error {
+    match error {
+        PersonalAccessTokensListError::Unexpected(report) => Error::InternalServerError(report),
+    }
+}
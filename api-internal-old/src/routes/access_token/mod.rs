@@ -0,0 +1,18 @@
+pub mod create;
+pub mod list;
+pub mod revoke;
+
+use accesso_core::models;
+
+use crate::generated::components::schemas;
+
+fn map_access_token(token: models::PersonalAccessToken) -> schemas::PersonalAccessToken {
+    schemas::PersonalAccessToken {
+        id: token.id,
+        name: token.name,
+        scopes: token.scopes,
+        created_at: token.created_at,
+        expires_at: token.expires_at,
+        last_used_at: token.last_used_at,
+    }
+}
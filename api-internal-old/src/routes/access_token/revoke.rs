@@ -0,0 +1,39 @@
+use actix_web::web::{Data, Json};
+
+use accesso_core::app::personal_access_token::{
+    PersonalAccessTokenRevokeError, PersonalAccessTokens,
+};
+
+use crate::generated::{
+    components::{
+        request_bodies::AccessTokenRevokeRequestBody,
+        responses::{
+            AccessTokenRevokeError as FailureVariant, AccessTokenRevokeFailure as Failure,
+        },
+    },
+    paths::access_token_revoke::{Error, Response},
+};
+use crate::session::Session;
+
+pub async fn route(
+    body: Json<AccessTokenRevokeRequestBody>,
+    app: Data<accesso_app::App>,
+    session: Session,
+) -> Result<Response, Error> {
+    app.personal_access_token_revoke(session.user.id, body.access_token_id)
+        .await
+        .map_err(map_error)?;
+
+    Ok(Response::Ok)
+}
+
+fn map_error(error: PersonalAccessTokenRevokeError) -> Error {
+    use PersonalAccessTokenRevokeError::{NotFound, Unexpected};
+
+    match error {
+        NotFound => Error::BadRequest(Failure {
+            error: FailureVariant::NotFound,
+        }),
+        Unexpected(report) => Error::InternalServerError(report),
+    }
+}
@@ -1,6 +1,7 @@
 use actix_web::web;
 
 use accesso_core::app::account::AccountEditError;
+use accesso_core::services::locale::validation_messages;
 
 use crate::generated::components::schemas::SessionUser;
 use crate::generated::components::{request_bodies, responses};
@@ -17,28 +18,35 @@ pub async fn route(
     let form = AccountEditForm {
         first_name: body.first_name.clone(),
         last_name: body.last_name.clone(),
+        locale: body.locale.clone(),
     };
 
     let user = app
         .account_edit(session.user.id, form)
         .await
-        .map_err(map_error)?;
+        .map_err(|error| map_error(error, &session.user.locale))?;
 
     Ok(generated::Response::Ok(responses::AccountEditSuccess {
         user: SessionUser {
             first_name: user.first_name,
             last_name: user.last_name,
             email: user.email,
+            locale: user.locale,
         },
     }))
 }
 
-fn map_error(error: AccountEditError) -> generated::Error {
-    use AccountEditError::{Unexpected, UserNotFound};
+fn map_error(error: AccountEditError, locale: &str) -> generated::Error {
+    use AccountEditError::{InvalidForm, Unexpected, UserNotFound};
 
     match error {
         UserNotFound => generated::Error::BadRequest(responses::AccountEditFailure {
             error: responses::AccountEditFailureError::InvalidPayload,
+            messages: None,
+        }),
+        InvalidForm(e) => generated::Error::BadRequest(responses::AccountEditFailure {
+            messages: Some(validation_messages(&e, locale)),
+            error: e.into(),
         }),
         Unexpected(report) => generated::Error::Unexpected(report),
     }
@@ -1,3 +1,4 @@
+pub mod access_token;
 pub mod account;
 pub mod application;
 pub mod oauth;
@@ -12,11 +12,11 @@ use responses::{
 };
 
 pub async fn route(
-    auth: Session,
+    auth: Option<Session>,
     body: web::Json<request_bodies::OAuthAuthorize>,
     app: web::Data<accesso_app::App>,
 ) -> Result<Response, Error> {
-    use accesso_core::app::oauth::authorize::{OAuthAuthorize, RequestAuthCode};
+    use accesso_core::app::oauth::authorize::{OAuthAuthorize, Prompt, RequestAuthCode};
 
     let form = RequestAuthCode {
         response_type: match body.response_type {
@@ -28,10 +28,15 @@ pub async fn route(
             scope.split(' ').map(ToOwned::to_owned).collect()
         }),
         state: body.state.clone(),
+        prompt: body.prompt.as_ref().map(|prompt| match prompt {
+            request_bodies::OAuthAuthorizePrompt::None => Prompt::None,
+            request_bodies::OAuthAuthorizePrompt::Login => Prompt::Login,
+        }),
+        max_age: body.max_age,
     };
 
     let created = app
-        .oauth_request_authorize_code(Some(auth.user), form)
+        .oauth_request_authorize_code(auth.map(|auth| (auth.session, auth.user)), form)
         .await
         .map_err(map_request_auth_code_error)?;
 
@@ -44,8 +49,9 @@ pub async fn route(
 
 fn map_request_auth_code_error(error: RequestAuthCodeFailed) -> Error {
     use RequestAuthCodeFailed::{
-        AccessDenied, InvalidRequest, InvalidScope, ServerError, TemporarilyUnavailable,
-        Unauthenticated, UnauthorizedClient, UnsupportedResponseType,
+        AccessDenied, ConsentRequired, InvalidRequest, InvalidScope, LoginRequired,
+        ReauthenticationRequired, ServerError, TemporarilyUnavailable, Unauthenticated,
+        UnauthorizedClient, UnsupportedResponseType,
     };
 
     match error {
@@ -79,6 +85,31 @@ fn map_request_auth_code_error(error: RequestAuthCodeFailed) -> Error {
             state,
         },
 
+        LoginRequired {
+            redirect_uri,
+            state,
+        } => Failure {
+            error: FailureVariant::LoginRequired,
+            redirect_uri: Some(redirect_uri),
+            state,
+        },
+
+        // Not redirected, the login form is shown for the current user
+        ReauthenticationRequired => Failure {
+            error: FailureVariant::ReauthenticationRequired,
+            redirect_uri: None,
+            state: None,
+        },
+
+        ConsentRequired {
+            redirect_uri,
+            state,
+        } => Failure {
+            error: FailureVariant::ConsentRequired,
+            redirect_uri: Some(redirect_uri),
+            state,
+        },
+
         UnauthorizedClient => Failure {
             error: FailureVariant::UnauthorizedClient,
             redirect_uri: None,
@@ -0,0 +1,61 @@
+use crate::generated::{
+    components::{request_bodies, responses},
+    paths::oauth_end_session::{Error, Response},
+};
+use crate::session::Session;
+use accesso_core::app::logout::EndSessionError;
+use actix_web::http::header::SET_COOKIE;
+use actix_web::http::HeaderValue;
+use actix_web::{web, Responder};
+use cookie::CookieBuilder;
+use eyre::WrapErr;
+use responses::{
+    OAuthEndSessionDone as Success, OAuthEndSessionFailure as Failure,
+    OAuthEndSessionFailureError as FailureVariant,
+};
+
+pub async fn route(
+    body: web::Json<request_bodies::OAuthEndSession>,
+    session_config: web::Data<accesso_app::SessionCookieConfig>,
+    app: web::Data<accesso_app::App>,
+    session: Option<Session>,
+) -> Result<impl Responder, Error> {
+    use accesso_core::app::logout::{EndSessionForm, Logout};
+
+    let form = EndSessionForm {
+        client_id: body.client_id,
+        post_logout_redirect_uri: body.post_logout_redirect_uri.clone(),
+        state: body.state.clone(),
+    };
+
+    let done = app
+        .logout_end_session(session.map(|auth| (auth.session, auth.user)), form)
+        .await
+        .map_err(map_end_session_error)?;
+
+    let cookie = CookieBuilder::new(session_config.name.to_owned(), "")
+        .expires(time::OffsetDateTime::now_utc())
+        .path(session_config.path.to_owned())
+        .secure(session_config.secure)
+        .http_only(session_config.http_only)
+        .finish();
+
+    let header_value = HeaderValue::from_str(&cookie.to_string())
+        .wrap_err("Could not create header value for cookie!")?;
+
+    Ok(Response::Ok(Success {
+        redirect_uri: done.redirect_uri,
+        state: done.state,
+    })
+    .with_header((SET_COOKIE, header_value)))
+}
+
+fn map_end_session_error(error: EndSessionError) -> Error {
+    match error {
+        EndSessionError::InvalidRequest(e) => Failure {
+            error: FailureVariant::InvalidRequest(e),
+        }
+        .into(),
+        EndSessionError::Unexpected(e) => e.into(),
+    }
+}
@@ -1 +1,2 @@
 pub mod authorize;
+pub mod end_session;
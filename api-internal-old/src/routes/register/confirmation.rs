@@ -3,17 +3,21 @@ use crate::generated::components::responses::{
     RegisterConfirmationFailed, RegisterConfirmationFailedError,
 };
 use crate::generated::paths::register_confirmation as confirm;
+use crate::locale::RequestLocale;
 use accesso_core::app::registrator::RegisterConfirmError;
+use accesso_core::services::locale::validation_messages;
 use actix_web::web;
 
 pub async fn route(
     body: web::Json<request_bodies::RegisterConfirmation>,
     app: web::Data<accesso_app::App>,
+    RequestLocale(locale): RequestLocale,
 ) -> Result<confirm::Response, confirm::Error> {
     use accesso_core::app::registrator::{RegisterForm, Registrator};
     use confirm::Response;
 
     let form = RegisterForm {
+        email: body.email.clone(),
         confirmation_code: body.confirmation_code.clone(),
         first_name: body.first_name.clone(),
         last_name: body.last_name.clone(),
@@ -22,14 +26,14 @@ pub async fn route(
 
     app.registrator_confirm(form)
         .await
-        .map_err(map_confirmation_error)?;
+        .map_err(|error| map_confirmation_error(error, locale))?;
 
     Ok(Response::Created)
 }
 
-fn map_confirmation_error(error: RegisterConfirmError) -> confirm::Error {
+fn map_confirmation_error(error: RegisterConfirmError, locale: &str) -> confirm::Error {
     use RegisterConfirmError::{
-        AlreadyActivated, CodeNotFound, EmailSenderError, InvalidForm, Unexpected,
+        AlreadyActivated, CodeNotFound, InvalidForm, InviteInvalid, Unexpected,
     };
     use RegisterConfirmationFailed as Failure;
 
@@ -37,16 +41,23 @@ fn map_confirmation_error(error: RegisterConfirmError) -> confirm::Error {
         Unexpected(e) => e.into(),
         CodeNotFound => Failure {
             error: RegisterConfirmationFailedError::CodeInvalidOrExpired,
+            messages: None,
         }
         .into(),
         AlreadyActivated(e) => Failure {
             error: RegisterConfirmationFailedError::EmailAlreadyActivated(e.into()),
+            messages: None,
+        }
+        .into(),
+        InviteInvalid => Failure {
+            error: RegisterConfirmationFailedError::InviteInvalid,
+            messages: None,
         }
         .into(),
         InvalidForm(e) => Failure {
+            messages: Some(validation_messages(&e, locale)),
             error: RegisterConfirmationFailedError::InvalidForm(e),
         }
         .into(),
-        EmailSenderError(e) => eyre::Report::from(e).into(),
     }
 }
@@ -1,2 +1,3 @@
 pub mod confirmation;
 pub mod request;
+pub mod resend;
@@ -1,20 +1,27 @@
 use crate::generated::components::{request_bodies, responses};
 use crate::generated::paths::register_request;
+use crate::locale::RequestLocale;
 use accesso_core::app::registrator::RegisterRequestError;
+use accesso_core::services::locale::validation_messages;
 use actix_web::web;
 
 #[tracing::instrument(skip(app))]
 pub async fn route(
     body: web::Json<request_bodies::Register>,
     app: web::Data<accesso_app::App>,
+    RequestLocale(locale): RequestLocale,
 ) -> Result<register_request::Response, register_request::Error> {
     use accesso_core::app::registrator::{CreateRegisterRequest, Registrator};
     use register_request::Response;
 
     let request = app
-        .registrator_create_request(CreateRegisterRequest::from_email(&body.email))
+        .registrator_create_request(CreateRegisterRequest {
+            email: body.email.clone(),
+            invite: body.invite.clone(),
+            locale: locale.to_owned(),
+        })
         .await
-        .map_err(map_register_request_error)?;
+        .map_err(|error| map_register_request_error(error, locale))?;
 
     Ok(Response::Created(responses::RegistrationRequestCreated {
         expires_at: request.expires_at.timestamp(),
@@ -22,17 +29,26 @@ pub async fn route(
 }
 
 #[allow(dead_code)]
-fn map_register_request_error(error: RegisterRequestError) -> register_request::Error {
-    use RegisterRequestError::{EmailAlreadyRegistered, EmailSenderError, InvalidForm, Unexpected};
+fn map_register_request_error(
+    error: RegisterRequestError,
+    locale: &str,
+) -> register_request::Error {
+    use RegisterRequestError::{EmailDomainRejected, InvalidForm, InviteInvalid, Unexpected};
 
     match error {
         Unexpected(e) => e.into(),
-        EmailSenderError(e) => eyre::Report::from(e).into(),
-        EmailAlreadyRegistered(email) => responses::RegisterFailed {
-            error: responses::RegisterFailedError::EmailAlreadyRegistered(email),
+        InviteInvalid => responses::RegisterFailed {
+            error: responses::RegisterFailedError::InviteInvalid,
+            messages: None,
+        }
+        .into(),
+        EmailDomainRejected(_) => responses::RegisterFailed {
+            error: responses::RegisterFailedError::EmailDomainNotAllowed,
+            messages: None,
         }
         .into(),
         InvalidForm(e) => responses::RegisterFailed {
+            messages: Some(validation_messages(&e, locale)),
             error: responses::RegisterFailedError::InvalidForm(e),
         }
         .into(),
@@ -0,0 +1,46 @@
+use crate::generated::components::{request_bodies, responses};
+use crate::generated::paths::register_resend;
+use crate::locale::RequestLocale;
+use accesso_core::app::registrator::RegisterResendError;
+use accesso_core::services::locale::validation_messages;
+use actix_web::web;
+
+#[tracing::instrument(skip(app))]
+pub async fn route(
+    body: web::Json<request_bodies::RegisterResend>,
+    app: web::Data<accesso_app::App>,
+    RequestLocale(locale): RequestLocale,
+) -> Result<register_resend::Response, register_resend::Error> {
+    use accesso_core::app::registrator::{Registrator, ResendRegisterRequest};
+    use register_resend::Response;
+
+    let request = app
+        .registrator_resend(ResendRegisterRequest {
+            email: body.email.clone(),
+        })
+        .await
+        .map_err(|error| map_register_resend_error(error, locale))?;
+
+    Ok(Response::Created(responses::RegistrationRequestCreated {
+        expires_at: request.expires_at.timestamp(),
+    }))
+}
+
+fn map_register_resend_error(error: RegisterResendError, locale: &str) -> register_resend::Error {
+    use RegisterResendError::{InvalidForm, TooManyRequests, Unexpected};
+
+    match error {
+        Unexpected(e) => e.into(),
+        TooManyRequests { retry_after } => responses::RegisterResendTooManyRequests {
+            error: responses::RegisterResendTooManyRequestsError::TooManyRequests,
+            // Round up, so client does not retry too early
+            retry_after: (retry_after.num_milliseconds() + 999) / 1000,
+        }
+        .into(),
+        InvalidForm(e) => responses::RegisterFailed {
+            messages: Some(validation_messages(&e, locale)),
+            error: responses::RegisterFailedError::InvalidForm(e),
+        }
+        .into(),
+    }
+}
@@ -1,8 +1,12 @@
-use crate::generated::components::responses::SessionCreateFailedError;
+use crate::generated::components::responses::{
+    SessionCreateFailedError, SessionCreateTooManyAttemptsError,
+};
 use crate::generated::components::{request_bodies, responses};
 use crate::generated::paths::session_create::{Error, Response};
+use crate::locale::RequestLocale;
 use accesso_app::AddCookieExt;
 use accesso_core::app::session::SessionCreateError;
+use accesso_core::services::locale::validation_messages;
 use actix_web::{web, HttpRequest, Responder};
 use eyre::WrapErr;
 
@@ -10,20 +14,29 @@ use eyre::WrapErr;
 pub async fn route(
     body: web::Json<request_bodies::SessionCreate>,
     session_config: web::Data<accesso_app::SessionCookieConfig>,
+    trusted_proxies: web::Data<accesso_app::TrustedProxies>,
     app: web::Data<accesso_app::App>,
     req: HttpRequest,
+    RequestLocale(locale): RequestLocale,
 ) -> Result<impl Responder, Error> {
     use accesso_core::app::session::{Session, SessionCreateForm};
 
     let form = SessionCreateForm {
         email: body.email.clone(),
         password: body.password.clone(),
+        remember_me: body.remember_me,
+        user_agent: req
+            .headers()
+            .get(actix_web::http::header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned),
+        ip: trusted_proxies.client_ip(&req),
     };
 
     let (session_token, user) = app
         .session_create(form)
         .await
-        .map_err(map_session_create_error)?;
+        .map_err(|error| map_session_create_error(error, locale))?;
 
     tracing::trace!(
         session_token = %session_token.token,
@@ -43,15 +56,24 @@ pub async fn route(
     Ok(response)
 }
 
-fn map_session_create_error(error: SessionCreateError) -> Error {
+fn map_session_create_error(error: SessionCreateError, locale: &str) -> Error {
     match error {
         SessionCreateError::Unexpected(e) => e.into(),
-        SessionCreateError::InvalidForm(e) => {
-            Error::BadRequest(responses::SessionCreateFailed { error: e.into() })
-        }
+        SessionCreateError::InvalidForm(e) => Error::BadRequest(responses::SessionCreateFailed {
+            messages: Some(validation_messages(&e, locale)),
+            error: e.into(),
+        }),
         SessionCreateError::InvalidCredentials => {
             Error::BadRequest(responses::SessionCreateFailed {
                 error: SessionCreateFailedError::InvalidCredentials,
+                messages: None,
+            })
+        }
+        SessionCreateError::TooManyAttempts { retry_after } => {
+            Error::TooManyRequests(responses::SessionCreateTooManyAttempts {
+                error: SessionCreateTooManyAttemptsError::TooManyAttempts,
+                // Round up to not allow the client to retry a bit earlier
+                retry_after: (retry_after.num_milliseconds() + 999) / 1000,
             })
         }
     }
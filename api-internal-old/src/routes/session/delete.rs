@@ -1,5 +1,5 @@
 use crate::generated::{
-    components::request_bodies,
+    components::{request_bodies, responses},
     paths::session_delete::{Error, Response},
 };
 use crate::session::Session;
@@ -20,7 +20,7 @@ pub async fn route(
 
     let strategy = match body.delete_all_sessions {
         true => SessionDeleteStrategy::All,
-        false => SessionDeleteStrategy::Single(session.token.to_owned()),
+        false => SessionDeleteStrategy::Single(session.session.clone()),
     };
 
     app.session_delete(&session.user, strategy)
@@ -44,5 +44,10 @@ pub async fn route(
 fn map_session_delete_error(error: SessionDeleteError) -> Error {
     match error {
         SessionDeleteError::Unexpected(e) => e.into(),
+        SessionDeleteError::NotFound => Error::BadRequest(responses::SessionDeleteFailure {
+            error: responses::SessionDeleteFailureError::InvalidPayload(eyre::eyre!(
+                "Session not found"
+            )),
+        }),
     }
 }
@@ -8,6 +8,7 @@ pub async fn route(session: Session) -> Result<Response, Error> {
             first_name: session.user.first_name,
             last_name: session.user.last_name,
             email: session.user.email,
+            locale: session.user.locale,
         },
     }))
 }
@@ -0,0 +1,33 @@
+use actix_web::web::Data;
+
+use accesso_core::app::session::{Session as _, SessionListError};
+
+use crate::generated::{
+    components::{responses::SessionListSuccess, schemas},
+    paths::session_list::{Error, Response},
+};
+use crate::session::Session;
+
+pub async fn route(app: Data<accesso_app::App>, session: Session) -> Result<Response, Error> {
+    let sessions = app.session_list(&session.user).await.map_err(map_error)?;
+
+    Ok(Response::Ok(SessionListSuccess {
+        sessions: sessions
+            .into_iter()
+            .map(|active| schemas::ActiveSession {
+                current: active.id == session.session.id,
+                id: active.id,
+                user_agent: active.user_agent,
+                ip: active.ip,
+                created_at: active.created_at,
+                last_seen_at: active.last_seen_at,
+            })
+            .collect(),
+    }))
+}
+
+fn map_error(error: SessionListError) -> Error {
+    match error {
+        SessionListError::Unexpected(report) => Error::InternalServerError(report),
+    }
+}
@@ -1,3 +1,6 @@
 pub mod create;
 pub mod delete;
 pub mod get;
+pub mod list;
+pub mod revoke;
+pub mod unlock;
@@ -0,0 +1,33 @@
+use actix_web::web::{Data, Json};
+
+use accesso_core::app::session::{Session as _, SessionDeleteError, SessionDeleteStrategy};
+
+use crate::generated::{
+    components::{
+        request_bodies::SessionRevokeRequestBody,
+        responses::{SessionRevokeError as FailureVariant, SessionRevokeFailure as Failure},
+    },
+    paths::session_revoke::{Error, Response},
+};
+use crate::session::Session;
+
+pub async fn route(
+    body: Json<SessionRevokeRequestBody>,
+    app: Data<accesso_app::App>,
+    session: Session,
+) -> Result<Response, Error> {
+    app.session_delete(&session.user, SessionDeleteStrategy::ById(body.session_id))
+        .await
+        .map_err(map_error)?;
+
+    Ok(Response::Ok)
+}
+
+fn map_error(error: SessionDeleteError) -> Error {
+    match error {
+        SessionDeleteError::NotFound => Error::BadRequest(Failure {
+            error: FailureVariant::NotFound,
+        }),
+        SessionDeleteError::Unexpected(report) => Error::InternalServerError(report),
+    }
+}
@@ -0,0 +1,31 @@
+use actix_web::web::{Data, Json};
+
+use accesso_core::app::session::{Session as _, SessionUnlockError};
+
+use crate::generated::{
+    components::{
+        request_bodies::SessionUnlock,
+        responses::{SessionUnlockError as FailureVariant, SessionUnlockFailure as Failure},
+    },
+    paths::session_unlock::{Error, Response},
+};
+
+pub async fn route(
+    body: Json<SessionUnlock>,
+    app: Data<accesso_app::App>,
+) -> Result<Response, Error> {
+    app.session_unlock(body.into_inner().code)
+        .await
+        .map_err(map_error)?;
+
+    Ok(Response::Ok)
+}
+
+fn map_error(error: SessionUnlockError) -> Error {
+    match error {
+        SessionUnlockError::NotFound => Error::BadRequest(Failure {
+            error: FailureVariant::NotFound,
+        }),
+        SessionUnlockError::Unexpected(report) => Error::InternalServerError(report),
+    }
+}
@@ -1,14 +1,57 @@
 use actix_web::{
+    dev::ServiceResponse,
     error::{ErrorInternalServerError, ErrorUnauthorized},
+    http::{header::SET_COOKIE, HeaderValue},
     web,
 };
+use cookie::Cookie;
 use futures::Future;
 use std::pin::Pin;
 
+/// Session renewed while the request was handled, its cookie should be issued again
+#[derive(Debug, Clone)]
+struct RenewedSession(accesso_core::models::SessionToken);
+
+/// Adds cookie with the new expiration time, if the session was renewed by [`Session`] extractor.
+/// Cookie already set by the handler, like the removed one on logout, is kept
+pub fn reissue_renewed_cookie<B>(
+    response: &mut ServiceResponse<B>,
+) -> Result<(), actix_web::Error> {
+    let renewed = response
+        .request()
+        .extensions_mut()
+        .remove::<RenewedSession>();
+    let session_config = response
+        .request()
+        .app_data::<web::Data<accesso_app::SessionCookieConfig>>()
+        .cloned();
+
+    if let (Some(RenewedSession(session)), Some(session_config)) = (renewed, session_config) {
+        let already_set = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| Cookie::parse(value).ok())
+            .any(|cookie| cookie.name() == session_config.name);
+
+        if already_set {
+            return Ok(());
+        }
+
+        let cookie = session_config.to_cookie(session);
+        let header_value =
+            HeaderValue::from_str(&cookie.to_string()).map_err(ErrorInternalServerError)?;
+        response.headers_mut().append(SET_COOKIE, header_value);
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct Session {
     pub user: accesso_core::models::User,
     pub token: String,
+    pub session: accesso_core::models::SessionToken,
 }
 
 impl actix_web::FromRequest for Session {
@@ -19,7 +62,10 @@ impl actix_web::FromRequest for Session {
         req: &actix_web::HttpRequest,
         _payload: &mut actix_web::dev::Payload,
     ) -> Self::Future {
-        use accesso_core::app::session::{Session, SessionResolveError::Unexpected};
+        use accesso_core::app::session::{
+            Session,
+            SessionResolveError::{InvalidDPoPProof, Unexpected},
+        };
 
         let req = req.clone();
 
@@ -34,8 +80,19 @@ impl actix_web::FromRequest for Session {
 
                     match app.session_resolve_by_cookie(token.clone()).await {
                         Err(Unexpected(_)) => Err(ErrorInternalServerError(Null)),
-                        Ok(None) => Err(ErrorUnauthorized(Null)),
-                        Ok(Some(user)) => Ok(Self { user, token }),
+                        Err(InvalidDPoPProof(_)) | Ok(None) => Err(ErrorUnauthorized(Null)),
+                        Ok(Some(resolved)) => {
+                            if resolved.renewed {
+                                req.extensions_mut()
+                                    .insert(RenewedSession(resolved.session.clone()));
+                            }
+
+                            Ok(Self {
+                                user: resolved.user,
+                                token,
+                                session: resolved.session,
+                            })
+                        }
                     }
                 } else {
                     tracing::warn!("No cookie found!");
@@ -83,6 +83,19 @@ pub mod components {
 
         pub struct AccessToken(pub String);
 
+        /// Optional DPoP proof JWT
+        pub struct DPoP(pub Option<String>);
+
+        impl FromRequest for DPoP {
+            type Error = actix_web::Error;
+            type Future = futures::future::Ready<Result<Self, Self::Error>>;
+
+            #[inline]
+            fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+                futures::future::ok(DPoP(extract_header(req, "DPoP".to_string()).ok()))
+            }
+        }
+
         impl FromRequest for AccessToken {
             type Error = actix_web::Error;
             type Future = futures::future::Ready<Result<Self, Self::Error>>;
@@ -157,11 +170,24 @@ pub mod components {
 
         #[derive(Debug, Serialize)]
         pub struct ViewerGetSuccess {
+            #[doc = "Requires `profile` scope"]
             #[serde(rename = "firstName")]
-            pub first_name: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub first_name: Option<String>,
 
+            #[doc = "Requires `profile` scope"]
             #[serde(rename = "lastName")]
-            pub last_name: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub last_name: Option<String>,
+
+            #[doc = "Requires `email` scope"]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub email: Option<String>,
+
+            #[doc = "Requires `email` scope"]
+            #[serde(rename = "emailVerified")]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub email_verified: Option<bool>,
 
             pub id: uuid::Uuid,
         }
@@ -175,6 +201,10 @@ pub mod components {
             #[serde(rename = "unauthorized")]
             #[error("Unauthorized")]
             Unauthorized,
+
+            #[serde(rename = "invalid_dpop_proof")]
+            #[error("Invalid DPoP proof")]
+            InvalidDPoPProof,
         }
 
         #[derive(Debug, Serialize, thiserror::Error)]
@@ -298,6 +328,9 @@ pub mod components {
         pub enum OAuthAccessTokenCreatedTokenType {
             #[serde(rename = "bearer")]
             Bearer,
+
+            #[serde(rename = "DPoP")]
+            DPoP,
         }
 
         /// When you can't exchange authorization code to access token
@@ -337,6 +370,10 @@ pub mod components {
             #[serde(rename = "unsupported_grant_type")]
             #[error("Unsupported grant type")]
             UnsupportedGrantType,
+
+            #[serde(rename = "invalid_dpop_proof")]
+            #[error("Invalid DPoP proof")]
+            InvalidDPoPProof,
         }
     }
 
@@ -1,2 +1,38 @@
+use accesso_core::app::session::TokenScheme;
+use accesso_core::services::dpop::DPoPRequest;
+use actix_web::HttpRequest;
+
 pub mod oauth;
 pub mod viewer;
+
+/// Access token can be passed with `Bearer` or `DPoP` scheme, or as is like bearer
+pub(crate) fn access_token_value(authorization: &str) -> (TokenScheme, &str) {
+    let (scheme, token) = match authorization.strip_prefix("DPoP ") {
+        Some(token) => (TokenScheme::DPoP, token),
+        None => (
+            TokenScheme::Bearer,
+            authorization
+                .strip_prefix("Bearer ")
+                .unwrap_or(authorization),
+        ),
+    };
+
+    (scheme, token.trim())
+}
+
+/// Proof is checked against method and uri of the current request
+pub(crate) fn dpop_request(req: &HttpRequest, proof: Option<String>) -> Option<DPoPRequest> {
+    proof.map(|proof| {
+        let connection = req.connection_info();
+        DPoPRequest {
+            proof,
+            method: req.method().to_string(),
+            uri: format!(
+                "{}://{}{}",
+                connection.scheme(),
+                connection.host(),
+                req.path()
+            ),
+        }
+    })
+}
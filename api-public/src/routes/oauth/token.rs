@@ -1,8 +1,8 @@
 use crate::generated::{
-    components::{request_bodies, responses},
+    components::{parameters, request_bodies, responses},
     paths::oauth_token::{Error, Response},
 };
-use actix_web::web;
+use actix_web::{web, HttpRequest};
 
 use responses::{
     OAuthAccessTokenCreated as Created, OAuthAccessTokenFailure as Failure,
@@ -15,6 +15,8 @@ use accesso_core::app::oauth::exchange::{
 
 pub async fn route(
     body: web::Json<request_bodies::OAuthAccessTokenExchange>,
+    dpop: parameters::DPoP,
+    req: HttpRequest,
     app: web::Data<accesso_app::App>,
 ) -> Result<Response, Error> {
     let grant_type = match body.grant_type {
@@ -29,6 +31,7 @@ pub async fn route(
         redirect_uri: body.redirect_uri.clone(),
         client_id: body.client_id,
         client_secret: body.client_secret.clone(),
+        dpop: crate::routes::dpop_request(&req, dpop.0),
     };
 
     let created = app
@@ -41,13 +44,15 @@ pub async fn route(
         expires_in: created.expires_in.timestamp(),
         token_type: match created.token_type {
             TokenType::Bearer => responses::OAuthAccessTokenCreatedTokenType::Bearer,
+            TokenType::DPoP => responses::OAuthAccessTokenCreatedTokenType::DPoP,
         },
     }))
 }
 
 fn map_exchange_failed(error: ExchangeFailed) -> Error {
     use ExchangeFailed::{
-        InvalidClient, InvalidGrant, InvalidRequest, InvalidScope, UnauthorizedClient, Unexpected,
+        InvalidClient, InvalidDPoPProof, InvalidGrant, InvalidRequest, InvalidScope,
+        UnauthorizedClient, Unexpected,
     };
 
     match error {
@@ -69,5 +74,9 @@ fn map_exchange_failed(error: ExchangeFailed) -> Error {
             error: FailureError::InvalidScope,
         }
         .into(),
+        InvalidDPoPProof(_) => Failure {
+            error: FailureError::InvalidDPoPProof,
+        }
+        .into(),
     }
 }
@@ -1,25 +1,39 @@
 use crate::generated::components::{parameters, responses};
 use crate::generated::paths::viewer_get::{Error, Response};
-use actix_web::web;
+use actix_web::{web, HttpRequest};
 
 use accesso_core::app::session::{Session, SessionResolveError};
+use accesso_core::models::{SCOPE_EMAIL, SCOPE_PROFILE};
 use responses::{
     ViewerGetFailure as Failure, ViewerGetFailureError as FailureError, ViewerGetSuccess as Success,
 };
 
 pub async fn route(
     access_token: parameters::AccessToken,
+    dpop: parameters::DPoP,
+    req: HttpRequest,
     app: web::Data<accesso_app::App>,
 ) -> Result<Response, Error> {
-    let user = app
-        .session_resolve_by_access_token(access_token.0)
+    let (scheme, token) = crate::routes::access_token_value(&access_token.0);
+    let viewer = app
+        .session_resolve_by_access_token(
+            token.to_owned(),
+            scheme,
+            crate::routes::dpop_request(&req, dpop.0),
+        )
         .await
         .map_err(map_session_resolve_error)?;
 
-    if let Some(user) = user {
+    if let Some(viewer) = viewer {
+        let profile = viewer.has_scope(SCOPE_PROFILE);
+        let email = viewer.has_scope(SCOPE_EMAIL);
+        let user = viewer.user;
+
         Ok(Response::Ok(Success {
-            first_name: user.first_name,
-            last_name: user.last_name,
+            first_name: profile.then(|| user.first_name),
+            last_name: profile.then(|| user.last_name),
+            email: email.then(|| user.email),
+            email_verified: email.then_some(user.email_verified),
             id: user.id,
         }))
     } else {
@@ -30,9 +44,12 @@ pub async fn route(
 }
 
 fn map_session_resolve_error(error: SessionResolveError) -> Error {
-    use SessionResolveError::Unexpected;
+    use SessionResolveError::{InvalidDPoPProof, Unexpected};
 
     match error {
         Unexpected(e) => Error::Unexpected(e),
+        InvalidDPoPProof(_) => Error::BadRequest(Failure {
+            error: FailureError::InvalidDPoPProof,
+        }),
     }
 }
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use uuid::Uuid;
+use validator::Validate;
 
 use accesso_core::{app::account, contracts::Repository, contracts::UserEditForm, models::User};
 
@@ -14,6 +15,8 @@ impl account::Account for App {
     ) -> Result<User, account::AccountEditError> {
         let db = self.get::<Service<dyn Repository>>()?;
 
+        form.validate()?;
+
         let updated_user = db
             .user_edit_by_id(
                 user_id,
@@ -21,6 +24,7 @@ impl account::Account for App {
                     first_name: Some(form.first_name),
                     last_name: Some(form.last_name),
                     email: None,
+                    locale: form.locale,
                 },
             )
             .await?;
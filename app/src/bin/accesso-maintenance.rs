@@ -0,0 +1,59 @@
+#![deny(warnings)]
+#![forbid(unsafe_code)]
+
+//! Runs maintenance worker outside of the api servers.
+//! Set `maintenance.run_in_server = false` to run only this one.
+//!
+//! `accesso-maintenance canonicalize-emails` recalculates canonical emails
+//! of existing users once and exits. The migration already did it for ASCII domains,
+//! the command is needed after rules change and to list accounts sharing a mailbox.
+
+use std::sync::Arc;
+
+use accesso_core::app::maintenance::Maintenance;
+use accesso_settings::Settings;
+use eyre::WrapErr;
+
+pub static APP_NAME: &str = "accesso-maintenance";
+
+fn main() -> eyre::Result<()> {
+    let settings = Arc::new(Settings::new("maintenance").wrap_err("failed to parse settings")?);
+    let command = std::env::args().nth(1);
+
+    actix_web::rt::System::new().block_on(async move {
+        let _guard = accesso_app::install_logger(APP_NAME.into(), &settings)?;
+
+        match command.as_deref() {
+            None => {
+                tracing::info!("==> maintenance worker started");
+                accesso_app::run_maintenance_worker(settings).await;
+            }
+            Some("canonicalize-emails") => canonicalize_emails(&settings).await?,
+            Some(command) => eyre::bail!("unknown command {}", command),
+        }
+
+        Ok(())
+    })
+}
+
+async fn canonicalize_emails(settings: &Settings) -> eyre::Result<()> {
+    let app = accesso_app::create_app(settings);
+    let report = app.maintenance_canonicalize_emails().await?;
+
+    for collision in &report.collisions {
+        tracing::warn!(
+            canonical_email = %collision.canonical_email,
+            kept_user_id = %collision.kept_user_id,
+            user_ids = ?collision.user_ids,
+            "Users share the same mailbox, others keep the old canonical email"
+        );
+    }
+
+    tracing::info!(
+        updated = report.updated,
+        collisions = report.collisions.len(),
+        "Canonical emails recalculated"
+    );
+
+    Ok(())
+}
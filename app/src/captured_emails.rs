@@ -0,0 +1,27 @@
+use accesso_core::services::Capture;
+use actix_web::{web, HttpResponse};
+
+use crate::{App, Service};
+
+#[derive(serde::Deserialize)]
+pub struct CapturedEmailsQuery {
+    recipient: String,
+}
+
+/// Registered only in debug mode with `capture` email transport, lets automated tests
+/// read confirmation codes without a real mailbox
+#[actix_web::get("/dev/emails")]
+pub async fn captured_emails_service(
+    app: web::Data<App>,
+    query: web::Query<CapturedEmailsQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let capture = app
+        .get::<Service<Capture>>()
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let emails = capture
+        .list(&query.recipient)
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(emails))
+}
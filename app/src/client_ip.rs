@@ -0,0 +1,93 @@
+use std::net::IpAddr;
+
+use actix_web::http::header::HeaderName;
+use actix_web::HttpRequest;
+
+/// Proxies allowed to report the client address in `X-Forwarded-For`.
+/// Header from anyone else is ignored, so client cannot choose the address it is counted by
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(pub Vec<IpAddr>);
+
+impl TrustedProxies {
+    /// Address of the nearest hop not being a trusted proxy
+    pub fn client_ip(&self, req: &HttpRequest) -> Option<String> {
+        let peer = req.peer_addr()?.ip();
+        if !self.0.contains(&peer) {
+            return Some(peer.to_string());
+        }
+
+        let forwarded_for = HeaderName::from_static("x-forwarded-for");
+        let hops: Vec<&str> = req
+            .headers()
+            .get_all(forwarded_for)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+
+        let mut client = peer;
+        // Proxies append the address they received the request from, so the last hops are ours
+        for hop in hops.into_iter().rev() {
+            match hop.parse::<IpAddr>() {
+                Ok(ip) if self.0.contains(&ip) => client = ip,
+                Ok(ip) => return Some(ip.to_string()),
+                Err(_) => break,
+            }
+        }
+
+        Some(client.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    const PROXY: &str = "10.0.0.1";
+
+    fn proxies() -> TrustedProxies {
+        TrustedProxies(vec![PROXY.parse().unwrap()])
+    }
+
+    fn request(peer: &str, forwarded_for: Option<&str>) -> HttpRequest {
+        let mut request =
+            TestRequest::default().peer_addr(format!("{}:443", peer).parse().unwrap());
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.insert_header(("X-Forwarded-For", forwarded_for));
+        }
+        request.to_http_request()
+    }
+
+    #[test]
+    fn ignores_forwarded_for_from_untrusted_peer() {
+        let req = request("203.0.113.7", Some("198.51.100.1"));
+
+        assert_eq!(proxies().client_ip(&req), Some("203.0.113.7".to_owned()));
+        assert_eq!(
+            TrustedProxies::default().client_ip(&req),
+            Some("203.0.113.7".to_owned())
+        );
+    }
+
+    #[test]
+    fn takes_nearest_untrusted_hop_behind_proxy() {
+        let req = request(PROXY, Some("192.0.2.1, 198.51.100.1, 10.0.0.1"));
+
+        assert_eq!(proxies().client_ip(&req), Some("198.51.100.1".to_owned()));
+    }
+
+    #[test]
+    fn stops_at_malformed_hop() {
+        let req = request(PROXY, Some("198.51.100.1, unknown"));
+
+        assert_eq!(proxies().client_ip(&req), Some(PROXY.to_owned()));
+    }
+
+    #[test]
+    fn uses_proxy_address_without_forwarded_for() {
+        let req = request(PROXY, None);
+
+        assert_eq!(proxies().client_ip(&req), Some(PROXY.to_owned()));
+    }
+}
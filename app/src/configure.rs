@@ -8,7 +8,7 @@ use tracing_log::LogTracer;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::{EnvFilter, Registry};
 
-use crate::health_service;
+use crate::{captured_emails_service, health_service, sendgrid_webhook_service};
 use actix_web::{http::StatusCode, web, HttpRequest, Responder};
 use opentelemetry::sdk::Resource;
 use opentelemetry::KeyValue;
@@ -83,28 +83,75 @@ pub fn install_logger(app_name: String, settings: &Settings) -> Result<WorkerGua
     Ok(guard)
 }
 
-pub fn configure(config: &mut ServiceConfig, settings: Arc<Settings>) {
+pub fn create_app(settings: &Settings) -> crate::App {
     use crate::Service;
-    use accesso_core::contracts::{EmailNotification, Repository, SecureGenerator};
+    use accesso_core::contracts::{EmailNotification, LogoutNotifier, Repository, SecureGenerator};
     use accesso_core::services;
-    use actix_web::web::Data;
-    use actix_web::HttpResponse;
+    use accesso_settings::EmailTransport;
 
     let db: Arc<dyn Repository> = Arc::new(accesso_db::Database::new(
         settings.database.connection_url(),
         settings.database.pool_size,
     ));
 
-    let emailer: Arc<dyn EmailNotification> =
-        Arc::new(services::Email::from(settings.sendgrid.clone()));
+    let capture = match settings.email.transport {
+        EmailTransport::Capture => Some(Arc::new(
+            services::Capture::new(settings.email.clone()).expect("Invalid capture settings"),
+        )),
+        _ => None,
+    };
+
+    let emailer: Arc<dyn EmailNotification> = match settings.email.transport {
+        EmailTransport::SendGrid => Arc::new(
+            services::Email::new(settings.email.clone(), settings.sendgrid.clone())
+                .expect("Invalid email templates"),
+        ),
+        EmailTransport::Smtp => Arc::new(
+            services::Smtp::new(settings.email.clone(), settings.smtp.clone())
+                .expect("Invalid smtp settings"),
+        ),
+        EmailTransport::Capture => capture.clone().expect("Capture is created above"),
+    };
 
-    let generator: Arc<dyn SecureGenerator> = Arc::new(services::Generator::new());
+    let generator: Arc<dyn SecureGenerator> = Arc::new(
+        services::Generator::try_from(settings.password_hashing.clone())
+            .expect("Invalid password hashing settings"),
+    );
 
-    let app = crate::App::builder()
+    let logout_notifier: Arc<dyn LogoutNotifier> = Arc::new(
+        services::BackChannelLogout::try_from(settings.logout.clone())
+            .expect("Invalid logout settings"),
+    );
+
+    let mut builder = crate::App::builder()
         .with_service(Service::from(db))
         .with_service(Service::from(emailer))
         .with_service(Service::from(generator))
-        .build();
+        .with_service(Service::from(logout_notifier))
+        .with_service(Service::new(settings.session.clone()))
+        .with_service(Service::new(settings.maintenance.clone()))
+        .with_service(Service::new(settings.login_protection.clone()))
+        .with_service(Service::new(settings.registration.clone()))
+        .with_service(Service::new(settings.email_domains.clone()));
+
+    if let Some(capture) = capture {
+        builder = builder.with_service(Service::from(capture));
+    }
+
+    if let Some(public_key) = &settings.sendgrid.webhook_public_key {
+        builder = builder.with_service(Service::new(
+            services::SendGridWebhook::new(public_key).expect("Invalid sendgrid webhook key"),
+        ));
+    }
+
+    builder.build()
+}
+
+pub fn configure(config: &mut ServiceConfig, settings: Arc<Settings>) {
+    use actix_web::web::Data;
+    use actix_web::HttpResponse;
+
+    let app = create_app(&settings);
 
     let session_cookie_config = crate::SessionCookieConfig {
         http_only: settings.cookies.http_only,
@@ -116,6 +163,9 @@ pub fn configure(config: &mut ServiceConfig, settings: Arc<Settings>) {
     config
         .app_data(Data::new(app))
         .app_data(Data::new(session_cookie_config))
+        .app_data(Data::new(crate::TrustedProxies(
+            settings.server.trusted_proxies.clone(),
+        )))
         .app_data(web::JsonConfig::default().error_handler(|err, _| {
             let error_message = format!("{}", err);
             actix_web::error::InternalError::from_response(
@@ -139,4 +189,13 @@ pub fn configure(config: &mut ServiceConfig, settings: Arc<Settings>) {
             .into()
         }))
         .service(health_service);
+
+    // Captured emails contain confirmation and unlock codes, never expose them in production
+    if settings.debug && settings.email.transport == accesso_settings::EmailTransport::Capture {
+        config.service(captured_emails_service);
+    }
+
+    if settings.sendgrid.webhook_public_key.is_some() {
+        config.service(sendgrid_webhook_service);
+    }
 }
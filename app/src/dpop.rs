@@ -0,0 +1,27 @@
+use accesso_core::contracts::{Repository, UnexpectedDatabaseError};
+use accesso_core::services::dpop::{DPoPError, DPoPRequest};
+
+use crate::{App, Service};
+
+/// Verifies the proof and accepts it only once.
+/// Returns JWK thumbprint of the key signing the proof
+pub(crate) async fn verify_dpop_proof<E>(
+    app: &App,
+    request: &DPoPRequest,
+    access_token: Option<&str>,
+) -> Result<String, E>
+where
+    E: From<DPoPError> + From<UnexpectedDatabaseError> + From<eyre::Report>,
+{
+    let db = app.get::<Service<dyn Repository>>()?;
+    let proof = request.verify(access_token)?;
+
+    if !db
+        .dpop_proof_use(proof.jkt.clone(), proof.jti, proof.expires_at)
+        .await?
+    {
+        return Err(DPoPError::Replayed.into());
+    }
+
+    Ok(proof.jkt)
+}
@@ -0,0 +1,27 @@
+use accesso_core::app::email_domain::{EmailDomainCheckError, EmailDomainPolicy};
+use accesso_core::contracts::Repository;
+use accesso_core::services::email_domain::check_email_domain;
+use accesso_settings::EmailDomains;
+use async_trait::async_trait;
+
+use crate::{App, Service};
+
+#[async_trait]
+impl EmailDomainPolicy for App {
+    async fn email_domain_check(
+        &self,
+        email: &str,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<(), EmailDomainCheckError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let settings = self.get::<Service<EmailDomains>>()?;
+
+        let rules = db.email_domain_rules_for(application_id).await?;
+
+        Ok(check_email_domain(
+            email,
+            &rules,
+            settings.block_disposable,
+        )?)
+    }
+}
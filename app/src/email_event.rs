@@ -0,0 +1,34 @@
+use accesso_core::app::email_event::{EmailEvents, EmailEventsIngestError};
+use accesso_core::contracts::Repository;
+use accesso_core::services::SendGridWebhook;
+use async_trait::async_trait;
+
+use crate::{App, Service};
+
+#[async_trait]
+impl EmailEvents for App {
+    async fn email_events_ingest_sendgrid(
+        &self,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+    ) -> Result<usize, EmailEventsIngestError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let webhook = self.get::<Service<SendGridWebhook>>()?;
+
+        webhook.verify(timestamp, signature, body)?;
+
+        let mut recorded = 0;
+
+        for event in webhook.parse_events(body)? {
+            let (email, status) = (event.email.clone(), event.status);
+
+            if db.email_event_record(event).await? {
+                tracing::info!(%email, status = status.as_str(), "Email marked undeliverable");
+                recorded += 1;
+            }
+        }
+
+        Ok(recorded)
+    }
+}
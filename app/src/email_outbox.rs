@@ -0,0 +1,246 @@
+use accesso_core::app::email_outbox::{EmailDispatchError, EmailOutbox};
+use accesso_core::contracts::{EmailNotification, Repository};
+use accesso_core::models::OutboxEmail;
+use accesso_db::chrono;
+use async_trait::async_trait;
+use eyre::WrapErr;
+
+use crate::{App, Service};
+
+#[async_trait]
+impl EmailOutbox for App {
+    async fn email_outbox_dispatch(&self, limit: i64) -> Result<usize, EmailDispatchError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let emailer = self.get::<Service<dyn EmailNotification>>()?;
+
+        let locked_until =
+            chrono::Utc::now() + chrono::Duration::seconds(OutboxEmail::CLAIM_LEASE_SECONDS);
+        let pending = db.email_outbox_claim_pending(limit, locked_until).await?;
+        let mut sent = 0;
+
+        for email in pending {
+            // Other claimed emails are still sent, this one is retried after the backoff
+            let status = match db.email_status_get(email.recipient.clone()).await {
+                Ok(status) => status,
+                Err(error) => {
+                    let error = format!("Could not get recipient email status: {}", error);
+                    email_mark_failed(self, &email, error).await?;
+                    continue;
+                }
+            };
+
+            if status.is_suppressed() {
+                tracing::info!(
+                    email.id = %email.id,
+                    email.status = status.as_str(),
+                    "Email is not sent to undeliverable recipient"
+                );
+                db.email_outbox_mark_failed(
+                    email.id,
+                    format!("Recipient email is {}", status.as_str()),
+                    None,
+                )
+                .await?;
+                continue;
+            }
+
+            match emailer
+                .send(
+                    email.recipient.clone(),
+                    email.locale.clone(),
+                    email.message.clone(),
+                )
+                .await
+            {
+                Ok(()) => {
+                    // Not marked email is sent again after the lease, so the error is returned
+                    db.email_outbox_mark_sent(email.id)
+                        .await
+                        .wrap_err("Could not mark email as sent")?;
+                    sent += 1;
+                }
+                Err(error) => email_mark_failed(self, &email, error.to_string()).await?,
+            }
+        }
+
+        Ok(sent)
+    }
+}
+
+/// Postpones the next attempt with backoff, or moves the email to dead letters after the last one
+async fn email_mark_failed(
+    app: &App,
+    email: &OutboxEmail,
+    error: String,
+) -> Result<(), EmailDispatchError> {
+    let db = app.get::<Service<dyn Repository>>()?;
+    let next_attempt_at = email.next_attempt_after_failure();
+
+    if next_attempt_at.is_some() {
+        tracing::warn!(
+            email.id = %email.id,
+            email.attempts = email.attempts + 1,
+            %error,
+            "Email sending failed"
+        );
+    } else {
+        tracing::error!(
+            email.id = %email.id,
+            email.idempotency_key = %email.idempotency_key,
+            %error,
+            "Email moved to dead letters after all attempts"
+        );
+    }
+
+    db.email_outbox_mark_failed(email.id, error, next_attempt_at)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::contracts::*;
+    use accesso_core::models::{EmailStatus, OutboxEmail};
+    use std::sync::Arc;
+
+    fn mock_app<R: Repository + 'static, E: EmailNotification + 'static>(
+        db: R,
+        emailer: E,
+    ) -> crate::App {
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let emailer: Arc<dyn EmailNotification> = Arc::new(emailer);
+
+        crate::App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::from(emailer))
+            .build()
+    }
+
+    fn queued(attempts: i32) -> OutboxEmail {
+        OutboxEmail {
+            id: uuid::Uuid::new_v4(),
+            recipient: "demo@domain.com".to_owned(),
+            locale: "en".to_owned(),
+            message: EmailMessage::AccountAlreadyExists,
+            idempotency_key: "demo-key".to_owned(),
+            created_at: chrono::Utc::now(),
+            attempts,
+            next_attempt_at: chrono::Utc::now(),
+            sent_at: None,
+            failed_at: None,
+            last_error: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn failed_email_is_retried_then_dead_lettered() {
+        let mut db = MockDb::new();
+        db.email_outbox
+            .expect_email_outbox_claim_pending()
+            .returning(|_, _| Ok(vec![queued(0), queued(OutboxEmail::MAX_ATTEMPTS - 1)]));
+        db.email_event
+            .expect_email_status_get()
+            .returning(|_| Ok(EmailStatus::Deliverable));
+        db.email_outbox
+            .expect_email_outbox_mark_failed()
+            .withf(|_, _, next_attempt_at| next_attempt_at.is_some())
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        db.email_outbox
+            .expect_email_outbox_mark_failed()
+            .withf(|_, _, next_attempt_at| next_attempt_at.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        db.email_outbox.expect_email_outbox_mark_sent().never();
+
+        let mut emailer = MockEmailNotification::new();
+        emailer
+            .expect_send()
+            .times(2)
+            .returning(|_, _, _| Err(SendEmailError::Unexpected(eyre::eyre!("Provider is down"))));
+
+        let sent = mock_app(db, emailer)
+            .email_outbox_dispatch(10)
+            .await
+            .unwrap();
+
+        assert_eq!(sent, 0);
+    }
+
+    #[actix_rt::test]
+    async fn failed_status_lookup_postpones_only_that_email() {
+        let mut db = MockDb::new();
+        db.email_outbox
+            .expect_email_outbox_claim_pending()
+            .returning(|_, _| {
+                let mut unknown = queued(0);
+                unknown.recipient = "unknown@domain.com".to_owned();
+                Ok(vec![unknown, queued(0)])
+            });
+        db.email_event
+            .expect_email_status_get()
+            .returning(|email| match email.as_str() {
+                "unknown@domain.com" => Err(UnexpectedDatabaseError::SqlxError(
+                    sqlx_core::error::Error::PoolTimedOut,
+                )),
+                _ => Ok(EmailStatus::Deliverable),
+            });
+        db.email_outbox
+            .expect_email_outbox_mark_failed()
+            .withf(|_, error, next_attempt_at| {
+                error.starts_with("Could not get recipient email status")
+                    && next_attempt_at.is_some()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        db.email_outbox
+            .expect_email_outbox_mark_sent()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut emailer = MockEmailNotification::new();
+        emailer
+            .expect_send()
+            .withf(|recipient, _, _| recipient == "demo@domain.com")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let sent = mock_app(db, emailer)
+            .email_outbox_dispatch(10)
+            .await
+            .unwrap();
+
+        assert_eq!(sent, 1);
+    }
+
+    #[actix_rt::test]
+    async fn email_to_bounced_recipient_is_dead_lettered_without_sending() {
+        let mut db = MockDb::new();
+        db.email_outbox
+            .expect_email_outbox_claim_pending()
+            .returning(|_, _| Ok(vec![queued(0)]));
+        db.email_event
+            .expect_email_status_get()
+            .withf(|email| email == "demo@domain.com")
+            .returning(|_| Ok(EmailStatus::Bounced));
+        db.email_outbox
+            .expect_email_outbox_mark_failed()
+            .withf(|_, error, next_attempt_at| {
+                error == "Recipient email is bounced" && next_attempt_at.is_none()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mut emailer = MockEmailNotification::new();
+        emailer.expect_send().never();
+
+        let sent = mock_app(db, emailer)
+            .email_outbox_dispatch(10)
+            .await
+            .unwrap();
+
+        assert_eq!(sent, 0);
+    }
+}
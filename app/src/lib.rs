@@ -3,16 +3,32 @@
 
 mod account;
 mod application;
+mod captured_emails;
+mod client_ip;
 mod configure;
 mod cookie;
+mod dpop;
+mod email_domain;
+mod email_event;
+mod email_outbox;
 mod health;
+mod logout;
+mod maintenance;
 mod oauth;
+mod personal_access_token;
 mod registrator;
+mod sendgrid_webhook;
 mod session;
+mod user_import;
+mod workers;
 
 pub use crate::cookie::{AddCookieExt, SessionCookieConfig};
-pub use configure::{configure, install_logger, not_found};
+pub(crate) use captured_emails::captured_emails_service;
+pub use client_ip::TrustedProxies;
+pub use configure::{configure, create_app, install_logger, not_found};
 pub(crate) use health::health_service;
+pub(crate) use sendgrid_webhook::sendgrid_webhook_service;
+pub use workers::{run_email_worker, run_logout_worker, run_maintenance_worker};
 
 use hashbrown::HashMap;
 use std::any::{Any, TypeId};
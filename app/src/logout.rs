@@ -0,0 +1,289 @@
+use crate::{App, Service};
+use accesso_core::app::logout::{
+    EndSessionDone, EndSessionError, EndSessionForm, Logout, LogoutDeliverError,
+};
+use accesso_core::app::session::{Session, SessionDeleteStrategy};
+use accesso_core::contracts::{LogoutNotifier, LogoutNotifyError, Repository};
+use accesso_core::models::{LogoutNotification, SessionToken, User};
+use accesso_db::chrono;
+use async_trait::async_trait;
+use eyre::WrapErr;
+
+#[async_trait]
+impl Logout for App {
+    async fn logout_end_session(
+        &self,
+        actor: Option<(SessionToken, User)>,
+        form: EndSessionForm,
+    ) -> Result<EndSessionDone, EndSessionError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+
+        let redirect_uri = match (form.client_id, form.post_logout_redirect_uri) {
+            (_, None) => None,
+            (None, Some(_)) => {
+                return Err(EndSessionError::InvalidRequest(eyre::eyre!(
+                    "client_id is required to redirect after logout"
+                )))
+            }
+            (Some(client_id), Some(redirect_uri)) => {
+                let client = db.application_find_by_id(client_id).await?.ok_or_else(|| {
+                    EndSessionError::InvalidRequest(eyre::eyre!(
+                        "No application with id {}",
+                        client_id
+                    ))
+                })?;
+
+                if !client.is_allowed_post_logout_redirect(&redirect_uri) {
+                    return Err(EndSessionError::InvalidRequest(eyre::eyre!(
+                        "Client id {} not allowed to redirect after logout",
+                        client.id
+                    )));
+                }
+
+                Some(redirect_uri)
+            }
+        };
+
+        // Session could be already deleted, logout is idempotent
+        if let Some((session, user)) = actor {
+            self.session_delete(&user, SessionDeleteStrategy::Single(session))
+                .await
+                .map_err(|error| EndSessionError::Unexpected(error.into()))?;
+        }
+
+        Ok(EndSessionDone {
+            state: redirect_uri.as_ref().and(form.state),
+            redirect_uri,
+        })
+    }
+
+    async fn logout_notifications_deliver(&self, limit: i64) -> Result<usize, LogoutDeliverError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let notifier = self.get::<Service<dyn LogoutNotifier>>()?;
+
+        let locked_until =
+            chrono::Utc::now() + chrono::Duration::seconds(LogoutNotification::CLAIM_LEASE_SECONDS);
+        let pending = db
+            .logout_notifications_claim_pending(limit, locked_until)
+            .await?;
+        let mut delivered = 0;
+
+        for notification in pending {
+            let result = match db.application_find_by_id(notification.client_id).await? {
+                Some(application) => {
+                    notifier
+                        .notify(&application, notification.user_id, notification.session_id)
+                        .await
+                }
+                None => Err(LogoutNotifyError::NotConfigured),
+            };
+
+            match result {
+                Ok(()) => {
+                    db.logout_notification_mark_delivered(notification.id)
+                        .await
+                        .wrap_err("Could not mark logout notification as delivered")?;
+                    delivered += 1;
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        notification.id = %notification.id,
+                        notification.attempts = notification.attempts + 1,
+                        %error,
+                        "Back-channel logout notification failed"
+                    );
+                    db.logout_notification_mark_failed(
+                        notification.id,
+                        notification.next_attempt_after_failure(),
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        Ok(delivered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::app::logout::EndSessionForm;
+    use accesso_core::contracts::{MockDb, MockLogoutNotifier};
+    use accesso_core::models::{Application, EmailStatus};
+    use std::sync::Arc;
+
+    const SESSION_ID: uuid::Uuid = uuid::Uuid::from_u128(42);
+
+    fn mock_app(db: MockDb, notifier: MockLogoutNotifier) -> crate::App {
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let notifier: Arc<dyn LogoutNotifier> = Arc::new(notifier);
+
+        crate::App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::from(notifier))
+            .build()
+    }
+
+    fn application() -> Application {
+        Application {
+            id: uuid::Uuid::new_v4(),
+            is_dev: false,
+            redirect_uri: vec!["https://app.example.com/callback".to_owned()],
+            title: "Demo".to_owned(),
+            secret_key: "secret".to_owned(),
+            allowed_registrations: true,
+            post_logout_redirect_uri: vec!["https://app.example.com/logged-out".to_owned()],
+            backchannel_logout_uri: Some("https://app.example.com/backchannel".to_owned()),
+            require_dpop: false,
+        }
+    }
+
+    fn actor() -> (SessionToken, User) {
+        let now = chrono::Utc::now();
+        let user = User {
+            id: uuid::Uuid::new_v4(),
+            email: "demo@domain.com".to_owned(),
+            canonical_email: "demo@domain.com".to_owned(),
+            password_hash: "hash".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            locale: "en".to_owned(),
+            email_status: EmailStatus::Deliverable,
+            email_verified: true,
+        };
+        let session = SessionToken {
+            id: SESSION_ID,
+            user_id: user.id,
+            token: "session-token".to_owned(),
+            expires_at: now + chrono::Duration::hours(1),
+            absolute_expires_at: now + chrono::Duration::days(1),
+            authenticated_at: now,
+            user_agent: None,
+            ip: None,
+            created_at: now,
+            last_seen_at: now,
+        };
+
+        (session, user)
+    }
+
+    fn notification(attempts: i32) -> LogoutNotification {
+        LogoutNotification {
+            id: uuid::Uuid::new_v4(),
+            client_id: uuid::Uuid::new_v4(),
+            // Tells notifications apart in the notifier
+            user_id: uuid::Uuid::from_u128(attempts as u128),
+            session_id: None,
+            created_at: chrono::Utc::now(),
+            attempts,
+            next_attempt_at: chrono::Utc::now(),
+            delivered_at: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn end_session_deletes_only_current_session() {
+        let mut db = MockDb::new();
+        db.application
+            .expect_application_find_by_id()
+            .returning(|_| Ok(Some(application())));
+        db.session
+            .expect_session_delete_token()
+            .withf(|token| token == "session-token")
+            .times(1)
+            .returning(|_| Ok(()));
+        db.session.expect_session_delete_by_user_id().never();
+        db.logout_notification
+            .expect_logout_notifications_create_for_user()
+            .never();
+        db.logout_notification
+            .expect_logout_notifications_create_for_session()
+            .withf(|_, session_id| *session_id == SESSION_ID)
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let done = mock_app(db, MockLogoutNotifier::new())
+            .logout_end_session(
+                Some(actor()),
+                EndSessionForm {
+                    client_id: Some(uuid::Uuid::new_v4()),
+                    post_logout_redirect_uri: Some("https://app.example.com/logged-out".to_owned()),
+                    state: Some("state".to_owned()),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            done,
+            EndSessionDone {
+                redirect_uri: Some("https://app.example.com/logged-out".to_owned()),
+                state: Some("state".to_owned()),
+            }
+        );
+    }
+
+    #[actix_rt::test]
+    async fn end_session_rejects_unregistered_redirect() {
+        let mut db = MockDb::new();
+        db.application
+            .expect_application_find_by_id()
+            .returning(|_| Ok(Some(application())));
+        db.session.expect_session_delete_token().never();
+
+        let result = mock_app(db, MockLogoutNotifier::new())
+            .logout_end_session(
+                Some(actor()),
+                EndSessionForm {
+                    client_id: Some(uuid::Uuid::new_v4()),
+                    post_logout_redirect_uri: Some("https://evil.example.com/".to_owned()),
+                    state: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(EndSessionError::InvalidRequest(_))));
+    }
+
+    #[actix_rt::test]
+    async fn failed_notification_is_retried_with_backoff() {
+        let mut db = MockDb::new();
+        db.logout_notification
+            .expect_logout_notifications_claim_pending()
+            .withf(|_, locked_until| *locked_until > chrono::Utc::now())
+            .returning(|_, _| Ok(vec![notification(0), notification(3)]));
+        db.application
+            .expect_application_find_by_id()
+            .returning(|_| Ok(Some(application())));
+        db.logout_notification
+            .expect_logout_notification_mark_delivered()
+            .times(1)
+            .returning(|_| Ok(()));
+        // Fourth attempt is postponed by 8 minutes
+        db.logout_notification
+            .expect_logout_notification_mark_failed()
+            .withf(|_, next_attempt_at| {
+                let delay = *next_attempt_at - chrono::Utc::now();
+                delay > chrono::Duration::minutes(7) && delay <= chrono::Duration::minutes(8)
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut notifier = MockLogoutNotifier::new();
+        notifier
+            .expect_notify()
+            .times(2)
+            .returning(|_, user_id, _| match user_id.as_u128() {
+                0 => Ok(()),
+                _ => Err(LogoutNotifyError::Rejected(503)),
+            });
+
+        let delivered = mock_app(db, notifier)
+            .logout_notifications_deliver(10)
+            .await
+            .unwrap();
+
+        assert_eq!(delivered, 1);
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use async_trait::async_trait;
+
+use accesso_core::app::maintenance::{
+    CanonicalEmailCollision, CanonicalizeReport, Maintenance, MaintenanceError, PurgeReport,
+};
+use accesso_core::contracts::{Repository, UnexpectedDatabaseError};
+use accesso_core::models::{AuthorizationCode, RegisterRequest};
+use accesso_core::services::canonical_email;
+use accesso_db::chrono;
+use accesso_settings::{LoginProtection, Maintenance as MaintenanceSettings};
+
+use crate::{App, Service};
+
+#[async_trait]
+impl Maintenance for App {
+    async fn maintenance_purge_expired(&self) -> Result<PurgeReport, MaintenanceError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let settings = self.get::<Service<MaintenanceSettings>>()?;
+        let login_protection = self.get::<Service<LoginProtection>>()?;
+
+        let now = chrono::Utc::now();
+        let batch = settings.batch_size;
+
+        let session_tokens_before =
+            now - chrono::Duration::hours(settings.session_tokens_retention);
+        let access_tokens_before = now - chrono::Duration::hours(settings.access_tokens_retention);
+        // Codes have no expiration time, only creation time
+        let authorization_codes_before = now
+            - AuthorizationCode::lifetime()
+            - chrono::Duration::hours(settings.authorization_codes_retention);
+        let registration_requests_before =
+            now - chrono::Duration::hours(settings.registration_requests_retention);
+        // Failures outside of the window are not counted anymore
+        let registration_failures_before = RegisterRequest::failures_window_start(now);
+        let sent_emails_before = now - chrono::Duration::hours(settings.sent_emails_retention);
+        // Counters older than failure window are reset anyway
+        let login_attempts_before =
+            now - chrono::Duration::minutes(login_protection.failure_window);
+
+        Ok(PurgeReport {
+            session_tokens: delete_in_batches(batch, || {
+                db.sessions_delete_expired(session_tokens_before, batch)
+            })
+            .await?,
+            access_tokens: delete_in_batches(batch, || {
+                db.access_tokens_delete_expired(access_tokens_before, batch)
+            })
+            .await?,
+            authorization_codes: delete_in_batches(batch, || {
+                db.auth_codes_delete_created_before(authorization_codes_before, batch)
+            })
+            .await?,
+            registration_requests: delete_in_batches(batch, || {
+                db.register_requests_delete_expired(registration_requests_before, batch)
+            })
+            .await?,
+            registration_failures: delete_in_batches(batch, || {
+                db.register_failures_delete_stale(registration_failures_before, batch)
+            })
+            .await?,
+            sent_emails: delete_in_batches(batch, || {
+                db.email_outbox_delete_sent(sent_emails_before, batch)
+            })
+            .await?,
+            login_attempts: delete_in_batches(batch, || {
+                db.login_attempts_delete_stale(login_attempts_before, batch)
+            })
+            .await?,
+            // Expired proof is rejected anyway, its `jti` is not needed
+            dpop_proofs: delete_in_batches(batch, || db.dpop_proofs_delete_expired(now, batch))
+                .await?,
+        })
+    }
+
+    async fn maintenance_canonicalize_emails(
+        &self,
+    ) -> Result<CanonicalizeReport, MaintenanceError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+
+        let mut by_canonical = BTreeMap::<_, Vec<_>>::new();
+        for user in db.user_list().await? {
+            by_canonical
+                .entry(canonical_email(&user.email))
+                .or_default()
+                .push(user);
+        }
+
+        let mut report = CanonicalizeReport::default();
+
+        for (canonical, mut users) in by_canonical {
+            // The same choice as in the migration recalculating canonical emails
+            users.sort_by_key(|user| {
+                (
+                    user.canonical_email != canonical,
+                    user.email.to_lowercase() != canonical,
+                    user.email.clone(),
+                    user.id,
+                )
+            });
+
+            let kept = &users[0];
+            if kept.canonical_email != canonical {
+                db.user_set_canonical_email(kept.id, canonical.clone())
+                    .await?;
+                report.updated += 1;
+            }
+
+            if users.len() > 1 {
+                report.collisions.push(CanonicalEmailCollision {
+                    canonical_email: canonical,
+                    kept_user_id: kept.id,
+                    user_ids: users[1..].iter().map(|user| user.id).collect(),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Repeats deletion until less than a full batch is deleted
+async fn delete_in_batches<F, Fut>(
+    batch: i64,
+    mut delete: F,
+) -> Result<u64, UnexpectedDatabaseError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<u64, UnexpectedDatabaseError>>,
+{
+    let mut total = 0;
+
+    loop {
+        let deleted = delete().await?;
+        total += deleted;
+
+        if deleted == 0 || deleted < batch as u64 {
+            return Ok(total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::contracts::MockDb;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[actix_rt::test]
+    async fn batches_stop_after_short_or_empty_batch() {
+        let calls = AtomicUsize::new(0);
+        let deleted = delete_in_batches(10, || {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            async move { Ok([10, 10, 3, 10][call]) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!((deleted, calls.load(Ordering::SeqCst)), (23, 3));
+
+        let calls = AtomicUsize::new(0);
+        let deleted = delete_in_batches(10, || {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            async move { Ok([10, 0, 10][call]) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!((deleted, calls.load(Ordering::SeqCst)), (10, 2));
+    }
+
+    #[actix_rt::test]
+    async fn purge_reports_deleted_rows_of_each_table() {
+        let mut db = MockDb::new();
+        let mut session_batches = vec![2, 5, 5];
+        db.session
+            .expect_sessions_delete_expired()
+            .withf(|_, limit| *limit == 5)
+            .times(3)
+            .returning(move |_, _| Ok(session_batches.pop().unwrap()));
+        db.access_token
+            .expect_access_tokens_delete_expired()
+            .times(1)
+            .returning(|_, _| Ok(1));
+        db.auth_code
+            .expect_auth_codes_delete_created_before()
+            .times(1)
+            .returning(|_, _| Ok(0));
+        db.requests
+            .expect_register_requests_delete_expired()
+            .times(1)
+            .returning(|_, _| Ok(4));
+        db.requests
+            .expect_register_failures_delete_stale()
+            .withf(|before, _| chrono::Utc::now() - *before >= RegisterRequest::lifetime())
+            .times(1)
+            .returning(|_, _| Ok(1));
+        db.email_outbox
+            .expect_email_outbox_delete_sent()
+            .times(1)
+            .returning(|_, _| Ok(0));
+        db.login_attempt
+            .expect_login_attempts_delete_stale()
+            .times(1)
+            .returning(|_, _| Ok(3));
+        db.dpop_proof
+            .expect_dpop_proofs_delete_expired()
+            .times(1)
+            .returning(|_, _| Ok(2));
+
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let app = crate::App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::new(MaintenanceSettings {
+                run_in_server: false,
+                interval: 60,
+                batch_size: 5,
+                session_tokens_retention: 24,
+                access_tokens_retention: 24,
+                authorization_codes_retention: 24,
+                registration_requests_retention: 24,
+                sent_emails_retention: 24,
+            }))
+            .with_service(Service::new(LoginProtection {
+                account_delay_after: 3,
+                account_lockout_after: 10,
+                ip_delay_after: 10,
+                ip_lockout_after: 100,
+                base_delay: 1,
+                max_delay: 60,
+                lockout_duration: 15,
+                failure_window: 60,
+            }))
+            .build();
+
+        let report = app.maintenance_purge_expired().await.unwrap();
+
+        assert_eq!(report.session_tokens, 12);
+        assert_eq!(report.access_tokens, 1);
+        assert_eq!(report.registration_requests, 4);
+        assert_eq!(report.registration_failures, 1);
+        assert_eq!(report.login_attempts, 3);
+        assert_eq!(report.dpop_proofs, 2);
+        assert_eq!(report.total(), 23);
+    }
+
+    fn user(email: &str, canonical_email: &str) -> accesso_core::models::User {
+        accesso_core::models::User {
+            id: uuid::Uuid::new_v4(),
+            email: email.to_owned(),
+            canonical_email: canonical_email.to_owned(),
+            password_hash: "hash".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            locale: "en".to_owned(),
+            email_status: accesso_core::models::EmailStatus::Deliverable,
+            email_verified: true,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn canonical_email_goes_to_one_account_of_mailbox() {
+        let dotted = user("John.Doe@gmail.com", "john.doe@gmail.com");
+        let plain = user("johndoe@gmail.com", "johndoe@gmail.com");
+        let tagged = user("Mary+news@me.com", "mary+news@me.com");
+        let ids = (dotted.id, plain.id, tagged.id);
+
+        let mut db = MockDb::new();
+        let users = vec![dotted, plain, tagged];
+        db.users
+            .expect_user_list()
+            .returning(move || Ok(users.clone()));
+        db.users
+            .expect_user_set_canonical_email()
+            .withf(move |id, canonical| *id == ids.2 && canonical == "mary@icloud.com")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let app = crate::App::builder()
+            .with_service(Service::from(db))
+            .build();
+
+        let report = app.maintenance_canonicalize_emails().await.unwrap();
+
+        assert_eq!(report.updated, 1);
+        assert_eq!(
+            report.collisions,
+            vec![CanonicalEmailCollision {
+                canonical_email: "johndoe@gmail.com".to_owned(),
+                kept_user_id: ids.1,
+                user_ids: vec![ids.0],
+            }]
+        );
+    }
+}
@@ -1,25 +1,28 @@
 use crate::{App, Service};
+use accesso_core::app::email_domain::{EmailDomainCheckError, EmailDomainPolicy};
 use accesso_core::app::oauth::authorize::{
-    AuthCodeCreated, OAuthAuthorize, RequestAuthCode, RequestAuthCodeFailed,
+    AuthCodeCreated, OAuthAuthorize, Prompt, RequestAuthCode, RequestAuthCodeFailed,
 };
 use accesso_core::contracts::{Repository, SecureGenerator};
-use accesso_core::models::{AuthorizationCode, User};
+use accesso_core::models::{AuthorizationCode, SessionToken, User};
 
 use accesso_db::chrono;
 use async_trait::async_trait;
 use eyre::WrapErr;
 use validator::Validate;
 
+/// User should sign in within this time after `prompt=login` is received, in seconds
+const REAUTHENTICATION_TIMEOUT_SECONDS: i64 = 10 * 60;
+
 #[async_trait]
 impl OAuthAuthorize for App {
     async fn oauth_request_authorize_code(
         &self,
-        actor: Option<User>,
+        actor: Option<(SessionToken, User)>,
         form: RequestAuthCode,
     ) -> Result<AuthCodeCreated, RequestAuthCodeFailed> {
         let db = self.get::<Service<dyn Repository>>()?;
         let generator = self.get::<Service<dyn SecureGenerator>>()?;
-        let actor = actor.ok_or(RequestAuthCodeFailed::Unauthenticated)?;
 
         form.validate()
             .map_err(|e| RequestAuthCodeFailed::InvalidRequest(e.into()))?;
@@ -56,6 +59,74 @@ impl OAuthAuthorize for App {
             });
         }
 
+        let (session, actor) = match actor {
+            Some(actor) => actor,
+            None if form.prompt == Some(Prompt::None) => {
+                return Err(RequestAuthCodeFailed::LoginRequired {
+                    redirect_uri: form.redirect_uri.clone(),
+                    state: form.state,
+                })
+            }
+            None => return Err(RequestAuthCodeFailed::Unauthenticated),
+        };
+
+        if form.prompt == Some(Prompt::Login) {
+            let since =
+                chrono::Utc::now() - chrono::Duration::seconds(REAUTHENTICATION_TIMEOUT_SECONDS);
+            let requested_at = db
+                .session_reauthentication_take(actor.id, client.id, since)
+                .await
+                .wrap_err("Could not get reauthentication request")?;
+
+            // Only credentials entered after the prompt was received satisfy it,
+            // then the repeated request with `prompt=login` continues
+            let reauthenticated = matches!(requested_at, Some(requested_at) if session.authenticated_at > requested_at);
+
+            if !reauthenticated {
+                db.session_reauthentication_request(actor.id, client.id)
+                    .await
+                    .wrap_err("Could not save reauthentication request")?;
+
+                return Err(RequestAuthCodeFailed::ReauthenticationRequired);
+            }
+        }
+
+        if let Some(max_age) = form.max_age {
+            if session.auth_age() > chrono::Duration::seconds(max_age) {
+                return Err(RequestAuthCodeFailed::LoginRequired {
+                    redirect_uri: form.redirect_uri.clone(),
+                    state: form.state,
+                });
+            }
+        }
+
+        let registration = db
+            .user_registration_find_for_client(&client, &actor)
+            .await
+            .wrap_err("Could not find user registration in database")?;
+
+        if registration.is_none() {
+            if form.prompt == Some(Prompt::None) {
+                return Err(RequestAuthCodeFailed::ConsentRequired {
+                    redirect_uri: form.redirect_uri.clone(),
+                    state: form.state,
+                });
+            }
+
+            // Closed applications accept only emails allowed by their domain rules
+            match self.email_domain_check(&actor.email, Some(client.id)).await {
+                Ok(()) => {}
+                Err(EmailDomainCheckError::Rejected(rejection)) => {
+                    tracing::info!(%rejection, client_id = %client.id, "User is not allowed to register in application");
+                    return Err(RequestAuthCodeFailed::AccessDenied {
+                        redirect_uri: form.redirect_uri.clone(),
+                        state: form.state,
+                    });
+                }
+                Err(EmailDomainCheckError::Unexpected(e)) => return Err(e.into()),
+            }
+        }
+
         // Check if actor already authorized with application
         // TODO: think about authorize confirmation
 
@@ -73,6 +144,10 @@ impl OAuthAuthorize for App {
             .await
             .wrap_err("Could not create auth code in database")?;
 
+        db.session_client_add(session.id, client.id)
+            .await
+            .wrap_err("Could not remember application of the session")?;
+
         Ok(AuthCodeCreated {
             code: created.code,
             redirect_uri: created.redirect_uri,
@@ -80,3 +155,222 @@ impl OAuthAuthorize for App {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::contracts::{MockDb, MockSecureGenerator};
+    use accesso_core::models::{Application, EmailStatus, UserRegistration};
+    use std::sync::Arc;
+
+    const REDIRECT_URI: &str = "https://app.example.com/callback";
+
+    fn mock_app(db: MockDb) -> crate::App {
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_generate_token()
+            .returning(|| "demo-code".to_owned());
+
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let generator: Arc<dyn SecureGenerator> = Arc::new(generator);
+
+        crate::App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::from(generator))
+            .build()
+    }
+
+    fn application(id: uuid::Uuid) -> Application {
+        Application {
+            id,
+            is_dev: false,
+            redirect_uri: vec![REDIRECT_URI.to_owned()],
+            title: "Demo".to_owned(),
+            secret_key: "secret".to_owned(),
+            allowed_registrations: true,
+            post_logout_redirect_uri: vec![],
+            backchannel_logout_uri: None,
+            require_dpop: false,
+        }
+    }
+
+    /// Registered user with an application, authenticated `auth_age` ago
+    fn db_registered() -> MockDb {
+        let mut db = MockDb::new();
+        db.application
+            .expect_application_find_by_id()
+            .returning(|id| Ok(Some(application(id))));
+        db.user_registrations
+            .expect_user_registration_find_for_client()
+            .returning(|client, user| {
+                Ok(Some(UserRegistration {
+                    id: uuid::Uuid::new_v4(),
+                    client_id: client.id,
+                    created_at: chrono::Utc::now(),
+                    user_id: user.id,
+                }))
+            });
+        db.auth_code.expect_auth_code_create().returning(Ok);
+        db.session
+            .expect_session_client_add()
+            .returning(|_, _| Ok(()));
+        db
+    }
+
+    fn actor(auth_age: chrono::Duration) -> (SessionToken, User) {
+        let now = chrono::Utc::now();
+        let user = User {
+            id: uuid::Uuid::new_v4(),
+            email: "demo@domain.com".to_owned(),
+            canonical_email: "demo@domain.com".to_owned(),
+            password_hash: "hash".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            locale: "en".to_owned(),
+            email_status: EmailStatus::Deliverable,
+            email_verified: true,
+        };
+        let session = SessionToken {
+            id: uuid::Uuid::new_v4(),
+            user_id: user.id,
+            token: "session-token".to_owned(),
+            expires_at: now + chrono::Duration::hours(1),
+            absolute_expires_at: now + chrono::Duration::days(1),
+            authenticated_at: now - auth_age,
+            user_agent: None,
+            ip: None,
+            created_at: now - auth_age,
+            last_seen_at: now,
+        };
+
+        (session, user)
+    }
+
+    fn form(prompt: Option<Prompt>, max_age: Option<i64>) -> RequestAuthCode {
+        RequestAuthCode {
+            response_type: "code".to_owned(),
+            client_id: uuid::Uuid::new_v4(),
+            redirect_uri: REDIRECT_URI.to_owned(),
+            scopes: vec![],
+            state: Some("state".to_owned()),
+            prompt,
+            max_age,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn prompt_none_without_session() {
+        let result = mock_app(db_registered())
+            .oauth_request_authorize_code(None, form(Some(Prompt::None), None))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RequestAuthCodeFailed::LoginRequired { state: Some(state), .. }) if state == "state"
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn prompt_none_with_session() {
+        let result = mock_app(db_registered())
+            .oauth_request_authorize_code(
+                Some(actor(chrono::Duration::days(3))),
+                form(Some(Prompt::None), None),
+            )
+            .await;
+
+        assert!(matches!(result, Ok(created) if created.code == "demo-code"));
+    }
+
+    #[actix_rt::test]
+    async fn prompt_login_asks_credentials_even_for_fresh_session() {
+        let mut db = db_registered();
+        db.session
+            .expect_session_reauthentication_take()
+            .returning(|_, _, _| Ok(None));
+        db.session
+            .expect_session_reauthentication_request()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        db.auth_code.checkpoint();
+        db.auth_code.expect_auth_code_create().never();
+
+        let result = mock_app(db)
+            .oauth_request_authorize_code(
+                Some(actor(chrono::Duration::seconds(5))),
+                form(Some(Prompt::Login), None),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RequestAuthCodeFailed::ReauthenticationRequired)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn prompt_login_continues_after_sign_in() {
+        let mut db = db_registered();
+        db.session
+            .expect_session_reauthentication_take()
+            .returning(|_, _, _| Ok(Some(chrono::Utc::now() - chrono::Duration::minutes(1))));
+        db.session.expect_session_reauthentication_request().never();
+
+        let result = mock_app(db)
+            .oauth_request_authorize_code(
+                Some(actor(chrono::Duration::seconds(5))),
+                form(Some(Prompt::Login), None),
+            )
+            .await;
+
+        assert!(matches!(result, Ok(created) if created.code == "demo-code"));
+    }
+
+    #[actix_rt::test]
+    async fn prompt_login_is_not_satisfied_by_earlier_sign_in() {
+        let mut db = db_registered();
+        db.session
+            .expect_session_reauthentication_take()
+            .returning(|_, _, _| Ok(Some(chrono::Utc::now() - chrono::Duration::minutes(1))));
+        db.session
+            .expect_session_reauthentication_request()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let result = mock_app(db)
+            .oauth_request_authorize_code(
+                Some(actor(chrono::Duration::minutes(2))),
+                form(Some(Prompt::Login), None),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RequestAuthCodeFailed::ReauthenticationRequired)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn max_age_limits_authentication_age() {
+        let outdated = mock_app(db_registered())
+            .oauth_request_authorize_code(
+                Some(actor(chrono::Duration::minutes(10))),
+                form(None, Some(300)),
+            )
+            .await;
+
+        assert!(matches!(
+            outdated,
+            Err(RequestAuthCodeFailed::LoginRequired { .. })
+        ));
+
+        let recent = mock_app(db_registered())
+            .oauth_request_authorize_code(
+                Some(actor(chrono::Duration::minutes(1))),
+                form(None, Some(300)),
+            )
+            .await;
+
+        assert!(recent.is_ok());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::dpop::verify_dpop_proof;
 use crate::{App, Service};
 use accesso_core::app::oauth::exchange::{
     AccessTokenCreated, ExchangeAccessTokenForm, ExchangeFailed, GrantType, OAuthExchange,
@@ -5,6 +6,7 @@ use accesso_core::app::oauth::exchange::{
 };
 use accesso_core::contracts::{Repository, SecureGenerator, UserRegistrationCreateError};
 use accesso_core::models::AccessToken;
+use accesso_core::services::dpop::DPoPError;
 
 use accesso_db::chrono;
 use async_trait::async_trait;
@@ -29,6 +31,7 @@ impl OAuthExchange for App {
             redirect_uri,
             client_id,
             client_secret,
+            dpop,
         } = form;
 
         match grant_type {
@@ -48,7 +51,7 @@ impl OAuthExchange for App {
                     .await?
                     .ok_or(ExchangeFailed::InvalidClient)?;
 
-                if !client.is_dev && !authorization_code.is_redirect_same(&redirect_uri) {
+                if !authorization_code.is_redirect_same(&redirect_uri) {
                     return Err(ExchangeFailed::InvalidGrant);
                 }
 
@@ -56,6 +59,14 @@ impl OAuthExchange for App {
                     return Err(ExchangeFailed::InvalidClient);
                 }
 
+                let dpop_jkt = match dpop {
+                    Some(dpop) => {
+                        Some(verify_dpop_proof::<ExchangeFailed>(self, &dpop, None).await?)
+                    }
+                    None if client.require_dpop => return Err(DPoPError::Missing.into()),
+                    None => None,
+                };
+
                 let user = db
                     .user_get_by_id(authorization_code.user_id)
                     .await?
@@ -86,6 +97,7 @@ impl OAuthExchange for App {
                     token: generator.generate_token_long(),
                     registration_id: registration.id,
                     scopes: authorization_code.scopes,
+                    dpop_jkt,
                 };
 
                 let created = db.access_token_create(access_token).await?;
@@ -94,7 +106,10 @@ impl OAuthExchange for App {
                 // TODO: add headers Cache-Control and Pragma
                 Ok(AccessTokenCreated {
                     access_token: created.token.clone(),
-                    token_type: TokenType::Bearer,
+                    token_type: match created.dpop_jkt {
+                        Some(_) => TokenType::DPoP,
+                        None => TokenType::Bearer,
+                    },
                     expires_in: created.expires_at,
                 })
             }
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use validator::Validate;
+
+use accesso_core::app::personal_access_token::{
+    PersonalAccessTokenCreateError, PersonalAccessTokenCreateForm, PersonalAccessTokenRevokeError,
+    PersonalAccessTokens, PersonalAccessTokensListError,
+};
+use accesso_core::contracts::{Repository, SecureGenerator};
+use accesso_core::models::PersonalAccessToken;
+use accesso_db::chrono;
+
+use crate::{App, Service};
+
+#[async_trait]
+impl PersonalAccessTokens for App {
+    async fn personal_access_token_create(
+        &self,
+        user_id: uuid::Uuid,
+        form: PersonalAccessTokenCreateForm,
+    ) -> Result<PersonalAccessToken, PersonalAccessTokenCreateError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let generator = self.get::<Service<dyn SecureGenerator>>()?;
+
+        form.validate()?;
+
+        let now = chrono::Utc::now();
+
+        let token = db
+            .personal_access_token_create(PersonalAccessToken {
+                id: uuid::Uuid::new_v4(),
+                user_id,
+                name: form.name,
+                token: generator.generate_token_long(),
+                scopes: form.scopes,
+                created_at: now,
+                expires_at: now + chrono::Duration::days(form.expires_in_days),
+                last_used_at: None,
+            })
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn personal_access_tokens_list(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<PersonalAccessToken>, PersonalAccessTokensListError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+
+        Ok(db.personal_access_tokens_list_for_user(user_id).await?)
+    }
+
+    async fn personal_access_token_revoke(
+        &self,
+        user_id: uuid::Uuid,
+        token_id: uuid::Uuid,
+    ) -> Result<(), PersonalAccessTokenRevokeError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+
+        match db.personal_access_token_delete(user_id, token_id).await? {
+            true => Ok(()),
+            false => Err(PersonalAccessTokenRevokeError::NotFound),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::contracts::{MockDb, MockSecureGenerator};
+    use accesso_core::models::{SCOPE_EMAIL, SCOPE_PROFILE};
+    use std::sync::Arc;
+
+    fn mock_app(db: MockDb, generator: MockSecureGenerator) -> App {
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let generator: Arc<dyn SecureGenerator> = Arc::new(generator);
+
+        App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::from(generator))
+            .build()
+    }
+
+    fn form(scopes: &[&str], expires_in_days: i64) -> PersonalAccessTokenCreateForm {
+        PersonalAccessTokenCreateForm {
+            name: "ci".to_owned(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_in_days,
+        }
+    }
+
+    fn assert_invalid(
+        result: Result<PersonalAccessToken, PersonalAccessTokenCreateError>,
+        field: &str,
+    ) {
+        match result {
+            Err(PersonalAccessTokenCreateError::InvalidForm(errors)) => {
+                assert!(errors.field_errors().contains_key(field), "{:?}", errors)
+            }
+            other => panic!("expected invalid {}, got {:?}", field, other),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn create_stores_token_with_requested_scopes() {
+        let mut db = MockDb::new();
+        db.personal_access_token
+            .expect_personal_access_token_create()
+            .times(1)
+            .returning(Ok);
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_generate_token_long()
+            .returning(|| "generated".to_owned());
+        let app = mock_app(db, generator);
+
+        let token = app
+            .personal_access_token_create(
+                uuid::Uuid::new_v4(),
+                form(
+                    &[SCOPE_PROFILE, SCOPE_EMAIL],
+                    PersonalAccessToken::MAX_LIFETIME_DAYS,
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(token.token, "generated");
+        assert_eq!(token.scopes, vec![SCOPE_PROFILE, SCOPE_EMAIL]);
+        assert_eq!(
+            token.expires_at - token.created_at,
+            chrono::Duration::days(PersonalAccessToken::MAX_LIFETIME_DAYS)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn create_rejects_unknown_scope() {
+        let app = mock_app(MockDb::new(), MockSecureGenerator::new());
+
+        let result = app
+            .personal_access_token_create(uuid::Uuid::new_v4(), form(&[SCOPE_PROFILE, "admin"], 30))
+            .await;
+
+        assert_invalid(result, "scopes");
+    }
+
+    #[actix_rt::test]
+    async fn create_rejects_lifetime_out_of_range() {
+        let app = mock_app(MockDb::new(), MockSecureGenerator::new());
+
+        for days in [0, PersonalAccessToken::MAX_LIFETIME_DAYS + 1] {
+            let result = app
+                .personal_access_token_create(uuid::Uuid::new_v4(), form(&[SCOPE_PROFILE], days))
+                .await;
+
+            assert_invalid(result, "expires_in_days");
+        }
+    }
+}
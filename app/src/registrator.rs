@@ -1,13 +1,16 @@
 use crate::{App, Service};
+use accesso_core::app::email_domain::EmailDomainPolicy;
 use accesso_core::app::registrator::{
-    CreateRegisterRequest, RegisterConfirmError, RegisterForm, RegisterRequestError, Registrator,
-    RequestCreated,
+    CreateRegisterRequest, RegisterConfirmError, RegisterForm, RegisterRequestError,
+    RegisterResendError, Registrator, RequestCreated, ResendRegisterRequest,
 };
 use accesso_core::contracts::{
-    EmailMessage, EmailNotification, Repository, SaveRegisterRequestError, SecureGenerator,
-    UserRegisterForm,
+    EmailMessage, Repository, SaveRegisterRequestError, SecureGenerator, UserRegisterForm,
 };
-use accesso_core::models::RegisterRequest;
+use accesso_core::models::{OutboxEmailForm, RegisterRequest};
+use accesso_core::services::canonical_email;
+use accesso_db::chrono;
+use accesso_settings::{Registration, RegistrationMode};
 use async_trait::async_trait;
 
 use eyre::WrapErr;
@@ -23,28 +26,83 @@ impl Registrator for App {
     ) -> Result<RequestCreated, RegisterRequestError> {
         let db = self.get::<Service<dyn Repository>>()?;
         let generator = self.get::<Service<dyn SecureGenerator>>()?;
-        let emailer = self.get::<Service<dyn EmailNotification>>()?;
 
         form.validate()?;
 
+        let (application_id, invite) = match check_invite(self, &form.invite).await? {
+            InviteCheck::Allowed {
+                application_id,
+                invite,
+            } => (application_id, invite),
+            InviteCheck::Rejected => return Err(RegisterRequestError::InviteInvalid),
+        };
+
+        self.email_domain_check(&form.email, application_id).await?;
+
         let user_exists = db
             .user_has_with_email(form.email.clone())
             .await
             .wrap_err("User existence query failed!")?;
 
+        // Response is the same for registered email, only the owner knows about the account
         if user_exists {
-            Err(RegisterRequestError::EmailAlreadyRegistered(
-                form.email.clone(),
+            let now = chrono::Utc::now();
+            // Repeated submits within a minute produce one email
+            let idempotency_key = format!(
+                "account_already_exists:{}:{}",
+                canonical_email(&form.email),
+                now.timestamp() / 60
+            );
+
+            // Owner's locale is not disclosed, the email is in the requested one
+            db.email_outbox_enqueue(OutboxEmailForm::new(
+                form.email,
+                form.locale,
+                EmailMessage::AccountAlreadyExists,
+                idempotency_key,
             ))
+            .await
+            .wrap_err("Could not queue account exists email")?;
+
+            Ok(RequestCreated {
+                expires_at: now + RegisterRequest::lifetime(),
+            })
         } else {
+            let registration = self.get::<Service<Registration>>()?;
+            let now = chrono::Utc::now();
+
+            // New request sends a code too, so it waits for the resend cooldown.
+            // The response doesn't tell it, like for a registered email
+            let latest = db
+                .register_requests_get_by_email(form.email.clone(), 1)
+                .await
+                .wrap_err("Could not get register requests for email")?
+                .into_iter()
+                .find(|request| request.expires_at > now);
+
+            if let Some(latest) = latest {
+                let next_send_at =
+                    latest.last_sent_at + chrono::Duration::seconds(registration.resend_cooldown);
+
+                if next_send_at > now {
+                    return Ok(RequestCreated {
+                        expires_at: latest.expires_at,
+                    });
+                }
+            }
+
             let mut generate_count = 0u8;
 
             let request: RegisterRequest = loop {
                 generate_count += 1;
 
                 let code = generator.confirmation_code();
-                let request = RegisterRequest::new(form.email.clone(), code.clone());
-                let result = db.register_request_save(request.clone()).await;
+                let request = RegisterRequest {
+                    invite: invite.clone(),
+                    ..RegisterRequest::new(form.email.clone(), code.clone(), form.locale.clone())
+                };
+                let email = confirmation_email(&request);
+                let result = db.register_request_save(request.clone(), Some(email)).await;
 
                 if let Err(SaveRegisterRequestError::CodeAlreadyExists) = result {
                     if generate_count <= MAX_CODE_INSERT_ATTEMPTS {
@@ -55,14 +113,12 @@ impl Registrator for App {
                 break result.wrap_err("Register request save failed");
             }?;
 
-            emailer
-                .send(
-                    form.email,
-                    EmailMessage::RegisterConfirmation {
-                        code: request.code.clone(),
-                    },
-                )
-                .await?;
+            db.register_requests_keep_latest(
+                request.email.clone(),
+                registration.max_requests_per_email,
+            )
+            .await
+            .wrap_err("Could not delete outdated register requests")?;
 
             Ok(RequestCreated {
                 expires_at: request.expires_at,
@@ -70,40 +126,104 @@ impl Registrator for App {
         }
     }
 
+    async fn registrator_resend(
+        &self,
+        form: ResendRegisterRequest,
+    ) -> Result<RequestCreated, RegisterResendError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+        let registration = self.get::<Service<Registration>>()?;
+
+        form.validate()?;
+
+        let now = chrono::Utc::now();
+        let latest = db
+            .register_requests_get_by_email(form.email, 1)
+            .await
+            .wrap_err("Could not get register requests for email")?
+            .into_iter()
+            .find(|request| request.expires_at > now);
+
+        match latest {
+            Some(mut request) => {
+                let next_send_at =
+                    request.last_sent_at + chrono::Duration::seconds(registration.resend_cooldown);
+
+                if next_send_at > now {
+                    return Err(RegisterResendError::TooManyRequests {
+                        retry_after: next_send_at - now,
+                    });
+                }
+
+                request.last_sent_at = now;
+                db.register_request_mark_sent(request.code.clone(), confirmation_email(&request))
+                    .await
+                    .wrap_err("Could not mark register request as sent")?;
+
+                Ok(RequestCreated {
+                    expires_at: request.expires_at,
+                })
+            }
+            None => Ok(RequestCreated {
+                expires_at: now + RegisterRequest::lifetime(),
+            }),
+        }
+    }
+
     async fn registrator_confirm(&self, form: RegisterForm) -> Result<(), RegisterConfirmError> {
         let db = self.get::<Service<dyn Repository>>()?;
         let generator = self.get::<Service<dyn SecureGenerator>>()?;
-        let emailer = self.get::<Service<dyn EmailNotification>>()?;
+        let registration = self.get::<Service<Registration>>()?;
 
         form.validate()?;
 
         let code = form.confirmation_code.clone();
+        let failures_since = RegisterRequest::failures_window_start(chrono::Utc::now());
+
+        // Requests of the mailbox are deleted already, new ones can't be confirmed either
+        let failures = db
+            .register_failures_count(form.email.clone(), failures_since)
+            .await
+            .wrap_err("Could not count wrong confirmation codes")?;
+
+        if failures >= registration.max_confirm_attempts {
+            return Err(RegisterConfirmError::CodeNotFound);
+        }
 
         match db
-            .register_request_get_by_code(code)
+            .register_request_get_by_email_and_code(form.email.clone(), code)
             .await
-            .wrap_err("Could not get register request with code: {}")?
+            .wrap_err("Could not get register request with code")?
         {
             Some(request) => {
-                let password_hash = generator.password_hash(form.password).0;
+                // Invite of the request is claimed with the user, it could be used meanwhile
+                if let InviteCheck::Rejected = check_invite(self, &request.invite).await? {
+                    return Err(RegisterConfirmError::InviteInvalid);
+                }
 
-                let created_user = db
-                    .user_register(UserRegisterForm {
-                        id: uuid::Uuid::new_v4(),
-                        email: request.email,
-                        password_hash,
-                        first_name: form.first_name,
-                        last_name: form.last_name,
-                    })
-                    .await?;
+                let password_hash = generator.password_hash(form.password).await?;
+                let finished_email = OutboxEmailForm::new(
+                    request.email.clone(),
+                    request.locale.clone(),
+                    EmailMessage::RegisterFinished {
+                        first_name: form.first_name.clone(),
+                        last_name: form.last_name.clone(),
+                    },
+                    format!("register_finished:{}", request.code),
+                );
 
-                emailer
-                    .send(
-                        created_user.email.clone(),
-                        EmailMessage::RegisterFinished {
-                            first_name: created_user.first_name,
-                            last_name: created_user.last_name,
+                let created_user = db
+                    .user_register(
+                        UserRegisterForm {
+                            id: uuid::Uuid::new_v4(),
+                            email: request.email,
+                            password_hash,
+                            first_name: form.first_name,
+                            last_name: form.last_name,
+                            locale: request.locale,
+                            email_verified: true,
+                            invite: request.invite,
                         },
+                        Some(finished_email),
                     )
                     .await?;
 
@@ -116,26 +236,95 @@ impl Registrator for App {
 
                 Ok(())
             }
-            None => Err(RegisterConfirmError::CodeNotFound),
+            None => {
+                let invalidated = db
+                    .register_requests_register_failure(
+                        form.email.clone(),
+                        registration.max_confirm_attempts,
+                        failures_since,
+                    )
+                    .await
+                    .wrap_err("Could not count wrong confirmation code")?;
+
+                if invalidated > 0 {
+                    tracing::info!(
+                        invalidated,
+                        "Register requests deleted after too many wrong codes"
+                    );
+                }
+
+                Err(RegisterConfirmError::CodeNotFound)
+            }
         }
     }
 }
 
+/// Each sending of the code is a separate email
+fn confirmation_email(request: &RegisterRequest) -> OutboxEmailForm {
+    OutboxEmailForm::new(
+        request.email.clone(),
+        request.locale.clone(),
+        EmailMessage::RegisterConfirmation {
+            code: request.code.clone(),
+        },
+        format!(
+            "register_confirmation:{}:{}",
+            request.code,
+            request.last_sent_at.timestamp()
+        ),
+    )
+}
+
+enum InviteCheck {
+    /// Registration is open, or the invite exists and is not used yet.
+    /// Domain rules of the invite application apply to the email
+    Allowed {
+        application_id: Option<uuid::Uuid>,
+        /// The valid invite, `None` for open registration without it
+        invite: Option<String>,
+    },
+    Rejected,
+}
+
+async fn check_invite(app: &App, invite: &Option<String>) -> eyre::Result<InviteCheck> {
+    let registration = app.get::<Service<Registration>>()?;
+    let db = app.get::<Service<dyn Repository>>()?;
+
+    let invite = match invite {
+        Some(invite) => db
+            .invite_get(invite.clone())
+            .await
+            .wrap_err("Could not get invite")?
+            .filter(|invite| !invite.is_used()),
+        None => None,
+    };
+
+    Ok(match invite {
+        Some(invite) => InviteCheck::Allowed {
+            application_id: invite.application_id,
+            invite: Some(invite.invite),
+        },
+        None if registration.mode == RegistrationMode::Open => InviteCheck::Allowed {
+            application_id: None,
+            invite: None,
+        },
+        None => InviteCheck::Rejected,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use accesso_core::contracts::*;
+    use accesso_core::services::email_domain::EmailDomainRejection;
+    use accesso_settings::EmailDomains;
     use std::any::TypeId;
     use std::sync::Arc;
 
-    fn mock_app<
-        R: Repository + 'static,
-        G: SecureGenerator + 'static,
-        E: EmailNotification + 'static,
-    >(
+    fn mock_app<R: Repository + 'static, G: SecureGenerator + 'static>(
         db: R,
         generator: G,
-        emailer: E,
+        mode: RegistrationMode,
     ) -> crate::App {
         let db: Arc<dyn Repository> = Arc::new(db);
         let db: Service<dyn Repository> = Service::from(db);
@@ -143,17 +332,22 @@ mod tests {
         let generator: Arc<dyn SecureGenerator> = Arc::new(generator);
         let generator: Service<dyn SecureGenerator> = Service::from(generator);
 
-        let emailer: Arc<dyn EmailNotification> = Arc::new(emailer);
-        let emailer: Service<dyn EmailNotification> = Service::from(emailer);
-
         println!(
             "typeid of db: {:?}",
             TypeId::of::<Service<dyn Repository>>()
         );
         crate::App::builder()
             .with_service(db)
-            .with_service(emailer)
             .with_service(generator)
+            .with_service(Service::new(EmailDomains {
+                block_disposable: true,
+            }))
+            .with_service(Service::new(Registration {
+                mode,
+                max_confirm_attempts: 5,
+                resend_cooldown: 60,
+                max_requests_per_email: 3,
+            }))
             .build()
     }
 
@@ -162,11 +356,9 @@ mod tests {
         let app = mock_app(
             MockDb::new(),
             MockSecureGenerator::new(),
-            MockEmailNotification::new(),
+            RegistrationMode::Open,
         );
-        let form = CreateRegisterRequest {
-            email: "demo".to_owned(),
-        };
+        let form = CreateRegisterRequest::from_email("demo");
 
         let result = app.registrator_create_request(form).await;
 
@@ -177,22 +369,476 @@ mod tests {
     async fn create_request_user_exists() {
         let mut db = MockDb::new();
         let email = "demo@domain.com";
+        db.email_domain_rule
+            .expect_email_domain_rules_for()
+            .returning(|_| Ok(vec![]));
         db.users
             .expect_user_has_with_email()
             .returning(|_| Ok(true));
+        db.requests.expect_register_request_save().never();
+        db.email_outbox
+            .expect_email_outbox_enqueue()
+            .withf(move |form| {
+                form.recipient == email
+                    && matches!(form.message, EmailMessage::AccountAlreadyExists)
+            })
+            .times(1)
+            .returning(|_| Ok(true));
 
-        let app = mock_app(db, MockSecureGenerator::new(), MockEmailNotification::new());
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Open);
 
         println!("{:?}", &app);
 
+        let form = CreateRegisterRequest::from_email(email);
+
+        let result = app.registrator_create_request(form).await;
+
+        assert!(matches!(result, Ok(RequestCreated { .. })));
+    }
+
+    #[actix_rt::test]
+    async fn create_request_new_email() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_requests_get_by_email()
+            .returning(|_, _| Ok(vec![]));
+        let email = "demo@domain.com";
+        db.email_domain_rule
+            .expect_email_domain_rules_for()
+            .returning(|_| Ok(vec![]));
+        db.users
+            .expect_user_has_with_email()
+            .returning(|_| Ok(false));
+        db.requests
+            .expect_register_request_save()
+            .withf(move |request, form| {
+                matches!(form, Some(form) if form.recipient == email
+                    && matches!(&form.message, EmailMessage::RegisterConfirmation { code } if *code == request.code))
+            })
+            .times(1)
+            .returning(|request, _| Ok(request));
+        db.requests
+            .expect_register_requests_keep_latest()
+            .withf(|_, count| *count == 3)
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_confirmation_code()
+            .returning(|| "demo-code".to_owned());
+
+        let app = mock_app(db, generator, RegistrationMode::Open);
+
+        let form = CreateRegisterRequest::from_email(email);
+
+        let result = app.registrator_create_request(form).await;
+
+        assert!(matches!(result, Ok(RequestCreated { .. })));
+    }
+
+    #[actix_rt::test]
+    async fn create_request_disposable_email() {
+        let mut db = MockDb::new();
+        db.email_domain_rule
+            .expect_email_domain_rules_for()
+            .withf(|application_id| application_id.is_none())
+            .returning(|_| Ok(vec![]));
+        db.users.expect_user_has_with_email().never();
+
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Open);
+        let form = CreateRegisterRequest::from_email("demo@mailinator.com");
+
+        let result = app.registrator_create_request(form).await;
+
+        assert!(matches!(
+            result,
+            Err(RegisterRequestError::EmailDomainRejected(
+                EmailDomainRejection::Disposable
+            ))
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn create_request_used_invite() {
+        let mut db = MockDb::new();
+        db.invite.expect_invite_get().returning(|invite| {
+            Ok(Some(accesso_core::models::ApplicationInvite {
+                invite,
+                created_at: chrono::Utc::now(),
+                application_id: None,
+                user_id: Some(uuid::Uuid::new_v4()),
+                registered_at: Some(chrono::Utc::now()),
+            }))
+        });
+        db.users.expect_user_has_with_email().never();
+
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Invite);
         let form = CreateRegisterRequest {
-            email: email.to_owned(),
+            email: "demo@domain.com".to_owned(),
+            invite: Some("used-invite".to_owned()),
+            locale: "en".to_owned(),
         };
 
         let result = app.registrator_create_request(form).await;
 
+        assert!(matches!(result, Err(RegisterRequestError::InviteInvalid)));
+    }
+
+    #[actix_rt::test]
+    async fn confirm_queues_finished_email_with_user_in_request_locale() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_failures_count()
+            .returning(|_, _| Ok(0));
+        db.requests
+            .expect_register_request_get_by_email_and_code()
+            .returning(|email, code| Ok(Some(RegisterRequest::new(email, code, "ru".to_owned()))));
+        db.users
+            .expect_user_register()
+            .withf(|form, email| {
+                form.locale == "ru"
+                    && matches!(email, Some(email) if email.recipient == form.email
+                    && email.locale == "ru"
+                    && email.idempotency_key == "register_finished:demo-code"
+                    && matches!(&email.message, EmailMessage::RegisterFinished { first_name, .. } if first_name == "Demo"))
+            })
+            .times(1)
+            .returning(|form, _| {
+                Ok(accesso_core::models::User {
+                    id: form.id,
+                    canonical_email: form.email.clone(),
+                    email: form.email,
+                    password_hash: form.password_hash,
+                    first_name: form.first_name,
+                    last_name: form.last_name,
+                    locale: form.locale,
+                    email_status: accesso_core::models::EmailStatus::Deliverable,
+                    email_verified: form.email_verified,
+                })
+            });
+        db.requests
+            .expect_register_requests_delete_all_for_email()
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_password_hash()
+            .returning(|_| Ok("demo-hash".to_owned()));
+
+        let app = mock_app(db, generator, RegistrationMode::Open);
+        let form = RegisterForm {
+            email: "demo@domain.com".to_owned(),
+            confirmation_code: "demo-code".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            password: "demo-password".to_owned(),
+        };
+
+        let result = app.registrator_confirm(form).await;
+
+        assert!(matches!(result, Ok(())));
+    }
+
+    #[actix_rt::test]
+    async fn confirm_fails_for_mailbox_registered_meanwhile() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_failures_count()
+            .returning(|_, _| Ok(0));
+        db.requests
+            .expect_register_request_get_by_email_and_code()
+            .returning(|email, code| Ok(Some(RegisterRequest::new(email, code, "en".to_owned()))));
+        // Another request of the same mailbox is confirmed first, unique canonical email fails
+        db.users
+            .expect_user_register()
+            .times(1)
+            .returning(|_, _| Err(RegisterUserError::EmailAlreadyExists));
+        db.requests
+            .expect_register_requests_delete_all_for_email()
+            .never();
+
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_password_hash()
+            .returning(|_| Ok("demo-hash".to_owned()));
+
+        let app = mock_app(db, generator, RegistrationMode::Open);
+        let form = RegisterForm {
+            email: "john.doe@gmail.com".to_owned(),
+            confirmation_code: "demo-code".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            password: "demo-password".to_owned(),
+        };
+
+        let result = app.registrator_confirm(form).await;
+
+        assert!(matches!(
+            result,
+            Err(RegisterConfirmError::AlreadyActivated(_))
+        ));
+    }
+
+    fn invited_request(email: String, code: String) -> RegisterRequest {
+        RegisterRequest {
+            invite: Some("demo-invite".to_owned()),
+            ..RegisterRequest::new(email, code, "en".to_owned())
+        }
+    }
+
+    fn unused_invite(invite: String) -> accesso_core::models::ApplicationInvite {
+        accesso_core::models::ApplicationInvite {
+            invite,
+            created_at: chrono::Utc::now(),
+            application_id: None,
+            user_id: None,
+            registered_at: None,
+        }
+    }
+
+    fn confirm_form() -> RegisterForm {
+        RegisterForm {
+            email: "demo@domain.com".to_owned(),
+            confirmation_code: "demo-code".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            password: "demo-password".to_owned(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn create_request_keeps_invite() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_requests_get_by_email()
+            .returning(|_, _| Ok(vec![]));
+        db.invite
+            .expect_invite_get()
+            .returning(|invite| Ok(Some(unused_invite(invite))));
+        db.email_domain_rule
+            .expect_email_domain_rules_for()
+            .returning(|_| Ok(vec![]));
+        db.users
+            .expect_user_has_with_email()
+            .returning(|_| Ok(false));
+        db.requests
+            .expect_register_request_save()
+            .withf(|request, _| request.invite.as_deref() == Some("demo-invite"))
+            .times(1)
+            .returning(|request, _| Ok(request));
+        db.requests
+            .expect_register_requests_keep_latest()
+            .returning(|_, _| Ok(0));
+
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_confirmation_code()
+            .returning(|| "demo-code".to_owned());
+
+        let app = mock_app(db, generator, RegistrationMode::Invite);
+        let form = CreateRegisterRequest {
+            email: "demo@domain.com".to_owned(),
+            invite: Some("demo-invite".to_owned()),
+            locale: "en".to_owned(),
+        };
+
+        assert!(app.registrator_create_request(form).await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn confirm_claims_invite_of_request() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_failures_count()
+            .returning(|_, _| Ok(0));
+        db.requests
+            .expect_register_request_get_by_email_and_code()
+            .returning(|email, code| Ok(Some(invited_request(email, code))));
+        db.invite
+            .expect_invite_get()
+            .withf(|invite| invite == "demo-invite")
+            .returning(|invite| Ok(Some(unused_invite(invite))));
+        db.users
+            .expect_user_register()
+            .withf(|form, _| form.invite.as_deref() == Some("demo-invite"))
+            .times(1)
+            .returning(|form, _| {
+                Ok(accesso_core::models::User {
+                    id: form.id,
+                    canonical_email: form.email.clone(),
+                    email: form.email,
+                    password_hash: form.password_hash,
+                    first_name: form.first_name,
+                    last_name: form.last_name,
+                    locale: form.locale,
+                    email_status: accesso_core::models::EmailStatus::Deliverable,
+                    email_verified: form.email_verified,
+                })
+            });
+        db.requests
+            .expect_register_requests_delete_all_for_email()
+            .returning(|_| Ok(1));
+
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_password_hash()
+            .returning(|_| Ok("demo-hash".to_owned()));
+
+        let app = mock_app(db, generator, RegistrationMode::Invite);
+
+        assert!(matches!(
+            app.registrator_confirm(confirm_form()).await,
+            Ok(())
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn confirm_fails_for_invite_claimed_meanwhile() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_failures_count()
+            .returning(|_, _| Ok(0));
+        db.requests
+            .expect_register_request_get_by_email_and_code()
+            .returning(|email, code| Ok(Some(invited_request(email, code))));
+        db.invite
+            .expect_invite_get()
+            .returning(|invite| Ok(Some(unused_invite(invite))));
+        // Another confirmation with the same invite has committed first
+        db.users
+            .expect_user_register()
+            .times(1)
+            .returning(|_, _| Err(RegisterUserError::InviteAlreadyUsed));
+        db.requests
+            .expect_register_requests_delete_all_for_email()
+            .never();
+
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_password_hash()
+            .returning(|_| Ok("demo-hash".to_owned()));
+
+        let app = mock_app(db, generator, RegistrationMode::Invite);
+
+        assert!(matches!(
+            app.registrator_confirm(confirm_form()).await,
+            Err(RegisterConfirmError::InviteInvalid)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn confirm_wrong_code_counts_failure() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_failures_count()
+            .returning(|_, _| Ok(0));
+        db.requests
+            .expect_register_request_get_by_email_and_code()
+            .returning(|_, _| Ok(None));
+        db.requests
+            .expect_register_requests_register_failure()
+            .withf(|email, max_attempts, since| {
+                email == "demo@domain.com"
+                    && *max_attempts == 5
+                    && chrono::Utc::now() - *since >= RegisterRequest::lifetime()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(1));
+
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Open);
+        let form = RegisterForm {
+            email: "demo@domain.com".to_owned(),
+            confirmation_code: "wrong-demo-code".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            password: "demo-password".to_owned(),
+        };
+
+        let result = app.registrator_confirm(form).await;
+
+        assert!(matches!(result, Err(RegisterConfirmError::CodeNotFound)));
+    }
+
+    #[actix_rt::test]
+    async fn confirm_after_too_many_failures_of_mailbox() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_failures_count()
+            .withf(|email, _| email == "john.doe@gmail.com")
+            .returning(|_, _| Ok(5));
+        // Even the right code of a new request is not accepted
+        db.requests
+            .expect_register_request_get_by_email_and_code()
+            .never();
+        db.users.expect_user_register().never();
+
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Open);
+        let form = RegisterForm {
+            email: "john.doe@gmail.com".to_owned(),
+            ..confirm_form()
+        };
+
+        let result = app.registrator_confirm(form).await;
+
+        assert!(matches!(result, Err(RegisterConfirmError::CodeNotFound)));
+    }
+
+    #[actix_rt::test]
+    async fn create_request_during_cooldown_sends_nothing() {
+        let mut db = MockDb::new();
+        db.email_domain_rule
+            .expect_email_domain_rules_for()
+            .returning(|_| Ok(vec![]));
+        db.users
+            .expect_user_has_with_email()
+            .returning(|_| Ok(false));
+        db.requests
+            .expect_register_requests_get_by_email()
+            .returning(|email, _| {
+                let mut request =
+                    RegisterRequest::new(email, "demo-code".to_owned(), "en".to_owned());
+                request.last_sent_at = chrono::Utc::now() - chrono::Duration::seconds(10);
+                Ok(vec![request])
+            });
+        db.requests.expect_register_request_save().never();
+        db.requests.expect_register_requests_keep_latest().never();
+
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Open);
+
+        let result = app
+            .registrator_create_request(CreateRegisterRequest::from_email("demo@domain.com"))
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn resend_during_cooldown() {
+        let mut db = MockDb::new();
+        db.requests
+            .expect_register_requests_get_by_email()
+            .returning(|email, _| {
+                let mut request =
+                    RegisterRequest::new(email, "demo-code".to_owned(), "en".to_owned());
+                request.last_sent_at = chrono::Utc::now() - chrono::Duration::seconds(10);
+                Ok(vec![request])
+            });
+        db.requests.expect_register_request_mark_sent().never();
+
+        let app = mock_app(db, MockSecureGenerator::new(), RegistrationMode::Open);
+        let form = ResendRegisterRequest {
+            email: "demo@domain.com".to_owned(),
+        };
+
+        let result = app.registrator_resend(form).await;
+
         assert!(matches!(
             result,
-            Err(RegisterRequestError::EmailAlreadyRegistered(e)) if e == *email));
+            Err(RegisterResendError::TooManyRequests { retry_after })
+                if retry_after > chrono::Duration::seconds(40)
+                    && retry_after <= chrono::Duration::seconds(60)
+        ));
     }
 }
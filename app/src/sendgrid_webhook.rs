@@ -0,0 +1,36 @@
+use accesso_core::app::email_event::{EmailEvents, EmailEventsIngestError};
+use accesso_core::services::sendgrid_webhook::{SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::App;
+
+/// Registered only when `sendgrid.webhook_public_key` is set.
+/// SendGrid retries only 5xx responses, so unsigned requests get 400
+#[actix_web::post("/webhooks/sendgrid")]
+pub async fn sendgrid_webhook_service(
+    app: web::Data<App>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> Result<HttpResponse, actix_web::Error> {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_owned()
+    };
+
+    match app
+        .email_events_ingest_sendgrid(&header(TIMESTAMP_HEADER), &header(SIGNATURE_HEADER), &body)
+        .await
+    {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(EmailEventsIngestError::Rejected(error)) => {
+            tracing::warn!(%error, "SendGrid webhook rejected");
+            Ok(HttpResponse::BadRequest().finish())
+        }
+        Err(EmailEventsIngestError::Unexpected(error)) => {
+            Err(actix_web::error::ErrorInternalServerError(error))
+        }
+    }
+}
@@ -1,46 +1,110 @@
+use crate::dpop::verify_dpop_proof;
 use crate::{App, Service};
 use accesso_core::app::session::{
-    RepoError, Session, SessionCreateError, SessionCreateForm, SessionDeleteError,
-    SessionDeleteStrategy, SessionResolveError,
+    CookieSession, RepoError, Session, SessionCreateError, SessionCreateForm, SessionDeleteError,
+    SessionDeleteStrategy, SessionListError, SessionResolveError, SessionUnlockError, TokenScheme,
+    TokenViewer,
 };
 use accesso_core::contracts::{
-    GetUserBySessionError, Repository, SecureGenerator, UserCredentials,
+    EmailMessage, GetUserBySessionError, Repository, SecureGenerator, UserCredentials,
 };
-use accesso_core::models::{SessionToken, User};
+use accesso_core::models::{LoginAttemptKind, OutboxEmailForm, SessionToken, User};
+use accesso_core::services::canonical_email;
+use accesso_core::services::dpop::{DPoPError, DPoPRequest};
 use async_trait::async_trait;
 
 use accesso_db::chrono;
+use accesso_settings::{LoginProtection, SessionLifetime};
 use validator::Validate;
 
 const MAX_TOKEN_CREATE_ATTEMPTS: u8 = 10;
-const SESSION_TOKEN_LIVE_DAYS: u8 = 14;
+/// Longer user agent is truncated before saving
+const USER_AGENT_MAX_LENGTH: usize = 512;
 
 #[async_trait]
 impl Session for App {
     async fn session_resolve_by_cookie(
         &self,
         cookie: String,
-    ) -> Result<Option<User>, SessionResolveError> {
+    ) -> Result<Option<CookieSession>, SessionResolveError> {
         let db = self.get::<Service<dyn Repository>>()?;
+        let lifetime = self.get::<Service<SessionLifetime>>()?;
 
-        match db.get_user_by_session_token(cookie).await {
-            Err(GetUserBySessionError::Unexpected(e)) => Err(SessionResolveError::Unexpected(e)),
-            Err(GetUserBySessionError::NotFound) => Ok(None),
-            Ok(user) => Ok(Some(user)),
+        let (mut session, user) = match db.get_user_by_session_token(cookie).await {
+            Err(GetUserBySessionError::Unexpected(e)) => {
+                return Err(SessionResolveError::Unexpected(e))
+            }
+            Err(GetUserBySessionError::NotFound) => return Ok(None),
+            Ok(found) => found,
+        };
+
+        let idle_lifetime = chrono::Duration::hours(lifetime.idle_lifetime);
+        let renewed = session.needs_renewal(idle_lifetime);
+
+        if renewed {
+            session.expires_at = session.renewed_expires_at(idle_lifetime);
+            db.session_extend(session.id, session.expires_at)
+                .await
+                .map_err(|error| SessionResolveError::Unexpected(error.into()))?;
+        } else if session.is_last_seen_outdated() {
+            db.session_mark_seen(session.id)
+                .await
+                .map_err(|error| SessionResolveError::Unexpected(error.into()))?;
         }
+
+        Ok(Some(CookieSession {
+            session,
+            user,
+            renewed,
+        }))
     }
 
     async fn session_resolve_by_access_token(
         &self,
         access_token: String,
-    ) -> Result<Option<User>, SessionResolveError> {
+        scheme: TokenScheme,
+        dpop: Option<DPoPRequest>,
+    ) -> Result<Option<TokenViewer>, SessionResolveError> {
         let db = self.get::<Service<dyn Repository>>()?;
 
-        match db.get_user_by_access_token(access_token).await {
-            Err(GetUserBySessionError::Unexpected(e)) => Err(SessionResolveError::Unexpected(e)),
-            Err(GetUserBySessionError::NotFound) => Ok(None),
-            Ok(user) => Ok(Some(user)),
+        let (token, user) = match db.get_user_by_access_token(access_token.clone()).await {
+            Err(GetUserBySessionError::Unexpected(e)) => {
+                return Err(SessionResolveError::Unexpected(e))
+            }
+            // Personal access tokens are accepted everywhere OAuth access token is
+            Err(GetUserBySessionError::NotFound) => {
+                return match db.get_user_by_personal_access_token(access_token).await {
+                    Err(GetUserBySessionError::Unexpected(e)) => {
+                        Err(SessionResolveError::Unexpected(e))
+                    }
+                    Err(GetUserBySessionError::NotFound) => Ok(None),
+                    Ok((token, user)) => Ok(Some(TokenViewer {
+                        user,
+                        scopes: token.scopes,
+                    })),
+                };
+            }
+            Ok(found) => found,
+        };
+
+        if let Some(jkt) = &token.dpop_jkt {
+            // Bound token sent as bearer could be sent so by anyone who got it
+            if scheme != TokenScheme::DPoP {
+                return Err(DPoPError::SchemeMismatch.into());
+            }
+
+            let dpop = dpop.ok_or(DPoPError::Missing)?;
+            let proof_jkt =
+                verify_dpop_proof::<SessionResolveError>(self, &dpop, Some(&token.token)).await?;
+            if proof_jkt != *jkt {
+                return Err(DPoPError::KeyMismatch.into());
+            }
         }
+
+        Ok(Some(TokenViewer {
+            user,
+            scopes: token.granted_scopes(),
+        }))
     }
 
     async fn session_create(
@@ -49,50 +113,111 @@ impl Session for App {
     ) -> Result<(SessionToken, User), SessionCreateError> {
         let db = self.get::<Service<dyn Repository>>()?;
         let generator = self.get::<Service<dyn SecureGenerator>>()?;
+        let lifetime = self.get::<Service<SessionLifetime>>()?;
 
         form.validate()?;
 
-        let hashed_input_password = generator.password_hash(form.password.clone());
+        let now = chrono::Utc::now();
+
+        // Counted for the email even if user does not exist, to not reveal registered emails
+        let mut counters = vec![(LoginAttemptKind::Account, canonical_email(&form.email))];
+        if let Some(ip) = &form.ip {
+            counters.push((LoginAttemptKind::Ip, ip.clone()));
+        }
+
+        if let Some(retry_after) = login_retry_after(self, &counters, now).await? {
+            return Err(SessionCreateError::TooManyAttempts { retry_after });
+        }
 
         let found_user = db
-            .user_find_by_credentials(UserCredentials {
-                email: form.email,
-                password_hash: hashed_input_password.0,
-            })
+            .user_find_by_credentials(UserCredentials { email: form.email })
             .await?;
 
-        if let Some(user) = found_user {
-            if !generator.verify_hash(user.password_hash.as_bytes(), &form.password) {
+        // Password is verified exactly once on every path,
+        // response time should not reveal whether the email is registered
+        let verified = match &found_user {
+            Some(user) => {
+                generator
+                    .verify_hash(&user.password_hash, &form.password)
+                    .await?
+            }
+            None => {
+                generator
+                    .verify_hash(generator.dummy_hash(), &form.password)
+                    .await?;
+                false
+            }
+        };
+
+        let user = match found_user {
+            Some(user) if verified => user,
+            found_user => {
+                login_register_failure(self, counters, found_user.as_ref(), now).await?;
                 return Err(SessionCreateError::InvalidCredentials);
             }
+        };
 
-            let mut insert_attempt = 0u8;
+        db.login_attempts_clear(LoginAttemptKind::Account, user.canonical_email.clone())
+            .await?;
+
+        if generator.hash_needs_upgrade(&user.password_hash) {
+            // Login should not fail, hash will be upgraded next time
+            if let Err(error) = upgrade_password_hash(self, &user, form.password.clone()).await {
+                tracing::warn!(%error, "Could not upgrade password hash");
+            }
+        }
 
-            let session: SessionToken = loop {
-                insert_attempt += 1;
+        let absolute_lifetime = match form.remember_me {
+            true => lifetime.remember_me_lifetime,
+            false => lifetime.absolute_lifetime,
+        };
+        let absolute_expires_at = now + chrono::Duration::hours(absolute_lifetime);
+        let expires_at =
+            (now + chrono::Duration::hours(lifetime.idle_lifetime)).min(absolute_expires_at);
 
-                let token = generator.generate_token();
-                let result = db
-                    .session_create(SessionToken {
-                        user_id: user.id,
-                        token,
-                        expires_at: chrono::Utc::now()
-                            + chrono::Duration::days(SESSION_TOKEN_LIVE_DAYS as i64),
-                    })
-                    .await;
-
-                if let Err(RepoError::TokenAlreadyExists) = result {
-                    if insert_attempt <= MAX_TOKEN_CREATE_ATTEMPTS {
-                        continue;
-                    }
+        let mut insert_attempt = 0u8;
+
+        let session: SessionToken = loop {
+            insert_attempt += 1;
+
+            let token = generator.generate_token();
+            let result = db
+                .session_create(SessionToken {
+                    id: uuid::Uuid::new_v4(),
+                    user_id: user.id,
+                    token,
+                    expires_at,
+                    absolute_expires_at,
+                    authenticated_at: now,
+                    user_agent: form
+                        .user_agent
+                        .as_ref()
+                        .map(|agent| agent.chars().take(USER_AGENT_MAX_LENGTH).collect()),
+                    ip: form.ip.clone(),
+                    created_at: now,
+                    last_seen_at: now,
+                })
+                .await;
+
+            if let Err(RepoError::TokenAlreadyExists) = result {
+                if insert_attempt <= MAX_TOKEN_CREATE_ATTEMPTS {
+                    continue;
                 }
+            }
 
-                break result;
-            }?;
+            break result;
+        }?;
 
-            Ok((session, user))
+        Ok((session, user))
+    }
+
+    async fn session_unlock(&self, code: String) -> Result<(), SessionUnlockError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+
+        if db.login_attempts_unlock_by_code(code).await? {
+            Ok(())
         } else {
-            Err(SessionCreateError::InvalidCredentials)
+            Err(SessionUnlockError::NotFound)
         }
     }
 
@@ -103,15 +228,679 @@ impl Session for App {
     ) -> Result<(), SessionDeleteError> {
         let db = self.get::<Service<dyn Repository>>()?;
         match strategy {
-            SessionDeleteStrategy::All => db
-                .session_delete_by_user_id(user.id)
-                .await
-                .map_err(Into::into),
+            SessionDeleteStrategy::All => {
+                db.session_delete_by_user_id(user.id).await?;
 
-            SessionDeleteStrategy::Single(token) => db
-                .session_delete_token(token.as_ref())
-                .await
-                .map_err(Into::into),
+                // Applications should know that user is logged out everywhere
+                db.logout_notifications_create_for_user(user.id).await?;
+            }
+            SessionDeleteStrategy::Single(session) => {
+                // Queued before deletion, the session's applications are forgotten with it
+                db.logout_notifications_create_for_session(user.id, session.id)
+                    .await?;
+                db.session_delete_token(&session.token).await?;
+            }
+            SessionDeleteStrategy::ById(id) => {
+                db.logout_notifications_create_for_session(user.id, id)
+                    .await?;
+                if !db.session_delete_by_id(user.id, id).await? {
+                    return Err(SessionDeleteError::NotFound);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn session_list(&self, user: &User) -> Result<Vec<SessionToken>, SessionListError> {
+        let db = self.get::<Service<dyn Repository>>()?;
+
+        Ok(db.sessions_list_for_user(user.id).await?)
+    }
+}
+
+/// Time left until login is allowed for all counters of the attempt
+async fn login_retry_after(
+    app: &App,
+    counters: &[(LoginAttemptKind, String)],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<chrono::Duration>, SessionCreateError> {
+    let db = app.get::<Service<dyn Repository>>()?;
+    let protection = app.get::<Service<LoginProtection>>()?;
+
+    let mut allowed_at = now;
+
+    for (kind, key) in counters {
+        if let Some(attempts) = db.login_attempts_get(*kind, key.clone()).await? {
+            let delay_after = match kind {
+                LoginAttemptKind::Account => protection.account_delay_after,
+                LoginAttemptKind::Ip => protection.ip_delay_after,
+            };
+            allowed_at = allowed_at.max(attempts.next_attempt_at(
+                delay_after,
+                chrono::Duration::seconds(protection.base_delay),
+                chrono::Duration::seconds(protection.max_delay),
+            ));
+        }
+    }
+
+    Ok((allowed_at > now).then(|| allowed_at - now))
+}
+
+/// Password is known only on login,
+/// so hash made with outdated algorithm or cost is replaced right then
+async fn upgrade_password_hash(
+    app: &App,
+    user: &User,
+    password: String,
+) -> Result<(), SessionCreateError> {
+    let db = app.get::<Service<dyn Repository>>()?;
+    let generator = app.get::<Service<dyn SecureGenerator>>()?;
+
+    let password_hash = generator.password_hash(password).await?;
+    db.user_password_reset(user.id, password_hash).await?;
+
+    Ok(())
+}
+
+/// Counts failure and locks out login after too many failures.
+/// Owner of the locked account receives an email to unlock it
+async fn login_register_failure(
+    app: &App,
+    counters: Vec<(LoginAttemptKind, String)>,
+    user: Option<&User>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<(), SessionCreateError> {
+    let db = app.get::<Service<dyn Repository>>()?;
+    let generator = app.get::<Service<dyn SecureGenerator>>()?;
+    let protection = app.get::<Service<LoginProtection>>()?;
+
+    for (kind, key) in counters {
+        let attempts = db
+            .login_attempt_register_failure(
+                kind,
+                key.clone(),
+                now - chrono::Duration::minutes(protection.failure_window),
+            )
+            .await?;
+
+        let lockout_after = match kind {
+            LoginAttemptKind::Account => protection.account_lockout_after,
+            LoginAttemptKind::Ip => protection.ip_lockout_after,
+        };
+        if attempts.failures < lockout_after || attempts.locked_until.is_some() {
+            continue;
+        }
+
+        let locked_until = now + chrono::Duration::minutes(protection.lockout_duration);
+        let unlock_code = match (kind, user) {
+            (LoginAttemptKind::Account, Some(_)) => Some(generator.generate_token()),
+            _ => None,
+        };
+        db.login_attempt_lock(kind, key, locked_until, unlock_code.clone())
+            .await?;
+
+        if let (Some(code), Some(user)) = (unlock_code, user) {
+            let email = OutboxEmailForm::new(
+                user.email.clone(),
+                user.locale.clone(),
+                EmailMessage::AccountLocked { code: code.clone() },
+                format!("account_locked:{}", code),
+            );
+
+            // Login should fail with the same error even if email is not queued
+            if let Err(error) = db.email_outbox_enqueue(email).await {
+                tracing::error!(%error, "Could not queue account unlock email");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::contracts::{MockDb, MockSecureGenerator};
+    use accesso_core::models::{
+        AccessToken, EmailStatus, LoginAttempts, SCOPE_EMAIL, SCOPE_PROFILE,
+    };
+    use accesso_core::services::dpop;
+    use std::sync::Arc;
+
+    const DUMMY_HASH: &str = "dummy-hash";
+
+    fn mock_app(db: MockDb, generator: MockSecureGenerator) -> App {
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let generator: Arc<dyn SecureGenerator> = Arc::new(generator);
+
+        App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::from(generator))
+            .with_service(Service::new(SessionLifetime::default()))
+            .with_service(Service::new(LoginProtection {
+                account_delay_after: 3,
+                account_lockout_after: 10,
+                ip_delay_after: 10,
+                ip_lockout_after: 100,
+                base_delay: 1,
+                max_delay: 60,
+                lockout_duration: 15,
+                failure_window: 60,
+            }))
+            .build()
+    }
+
+    fn db_counting_failures() -> MockDb {
+        let mut db = MockDb::new();
+        db.login_attempt
+            .expect_login_attempts_get()
+            .returning(|_, _| Ok(None));
+        db.login_attempt
+            .expect_login_attempt_register_failure()
+            .returning(|kind, key, _| {
+                Ok(LoginAttempts {
+                    kind,
+                    key,
+                    failures: 1,
+                    last_failure_at: chrono::Utc::now(),
+                    locked_until: None,
+                    unlock_code: None,
+                })
+            });
+        db
+    }
+
+    fn user() -> User {
+        User {
+            id: uuid::Uuid::new_v4(),
+            email: "demo@domain.com".to_owned(),
+            canonical_email: "demo@domain.com".to_owned(),
+            password_hash: "user-hash".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            locale: "en".to_owned(),
+            email_status: EmailStatus::Deliverable,
+            email_verified: true,
+        }
+    }
+
+    /// Login with the right password, created session is returned as is
+    fn db_logging_in() -> MockDb {
+        let mut db = MockDb::new();
+        db.login_attempt
+            .expect_login_attempts_get()
+            .returning(|_, _| Ok(None));
+        db.login_attempt
+            .expect_login_attempts_clear()
+            .returning(|_, _| Ok(()));
+        db.users
+            .expect_user_find_by_credentials()
+            .returning(|_| Ok(Some(user())));
+        db.session.expect_session_create().returning(Ok);
+        db
+    }
+
+    fn generator_verifying(needs_upgrade: bool) -> MockSecureGenerator {
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_verify_hash()
+            .withf(|hash, _| hash == "user-hash")
+            .returning(|_, _| Ok(true));
+        generator
+            .expect_hash_needs_upgrade()
+            .return_const(needs_upgrade);
+        generator
+            .expect_generate_token()
+            .returning(|| "session-token".to_owned());
+        generator
+    }
+
+    fn cookie_session(expires_in: chrono::Duration, absolute_in: chrono::Duration) -> SessionToken {
+        let now = chrono::Utc::now();
+        SessionToken {
+            id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            token: "session-token".to_owned(),
+            expires_at: now + expires_in,
+            absolute_expires_at: now + absolute_in,
+            authenticated_at: now - chrono::Duration::days(1),
+            user_agent: None,
+            ip: None,
+            created_at: now - chrono::Duration::days(1),
+            last_seen_at: now,
         }
     }
+
+    fn form() -> SessionCreateForm {
+        SessionCreateForm {
+            email: "demo@domain.com".to_owned(),
+            password: "wrong-password".to_owned(),
+            remember_me: false,
+            user_agent: None,
+            ip: Some("127.0.0.1".to_owned()),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn create_unknown_email_verifies_dummy_hash() {
+        let mut db = db_counting_failures();
+        db.users
+            .expect_user_find_by_credentials()
+            .returning(|_| Ok(None));
+
+        let mut generator = MockSecureGenerator::new();
+        generator.expect_password_hash().never();
+        generator
+            .expect_dummy_hash()
+            .times(1)
+            .return_const(DUMMY_HASH.to_owned());
+        generator
+            .expect_verify_hash()
+            .withf(|hash, _| hash == DUMMY_HASH)
+            .times(1)
+            .returning(|_, _| Ok(false));
+
+        let result = mock_app(db, generator).session_create(form()).await;
+
+        assert!(matches!(
+            result,
+            Err(SessionCreateError::InvalidCredentials)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn create_wrong_password_verifies_user_hash() {
+        let mut db = db_counting_failures();
+        db.users.expect_user_find_by_credentials().returning(|_| {
+            Ok(Some(User {
+                id: uuid::Uuid::new_v4(),
+                email: "demo@domain.com".to_owned(),
+                canonical_email: "demo@domain.com".to_owned(),
+                password_hash: "user-hash".to_owned(),
+                first_name: "Demo".to_owned(),
+                last_name: "User".to_owned(),
+                locale: "en".to_owned(),
+                email_status: EmailStatus::Deliverable,
+                email_verified: true,
+            }))
+        });
+
+        let mut generator = MockSecureGenerator::new();
+        generator.expect_password_hash().never();
+        generator.expect_dummy_hash().never();
+        generator
+            .expect_verify_hash()
+            .withf(|hash, _| hash == "user-hash")
+            .times(1)
+            .returning(|_, _| Ok(false));
+
+        let result = mock_app(db, generator).session_create(form()).await;
+
+        assert!(matches!(
+            result,
+            Err(SessionCreateError::InvalidCredentials)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn create_without_remember_me_keeps_default_absolute_lifetime() {
+        let app = mock_app(db_logging_in(), generator_verifying(false));
+        let (session, _) = app.session_create(form()).await.unwrap();
+        let now = chrono::Utc::now();
+
+        assert!(session.expires_at - now <= chrono::Duration::hours(24));
+        assert!(session.expires_at - now > chrono::Duration::hours(23));
+        assert!(session.absolute_expires_at - now > chrono::Duration::days(13));
+        assert!(session.absolute_expires_at - now <= chrono::Duration::days(14));
+    }
+
+    #[actix_rt::test]
+    async fn create_upgrades_outdated_password_hash() {
+        let mut db = db_logging_in();
+        db.users
+            .expect_user_password_reset()
+            .withf(|_, hash| hash == "new-hash")
+            .times(1)
+            .returning(|_, _| Ok(Some(user())));
+        let mut generator = generator_verifying(true);
+        generator
+            .expect_password_hash()
+            .withf(|password| password == "wrong-password")
+            .times(1)
+            .returning(|_| Ok("new-hash".to_owned()));
+
+        mock_app(db, generator)
+            .session_create(form())
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn create_keeps_up_to_date_password_hash() {
+        let mut db = db_logging_in();
+        db.users.expect_user_password_reset().never();
+
+        mock_app(db, generator_verifying(false))
+            .session_create(form())
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn create_with_remember_me_uses_long_absolute_lifetime() {
+        let app = mock_app(db_logging_in(), generator_verifying(false));
+        let now = chrono::Utc::now();
+
+        let (session, _) = app
+            .session_create(SessionCreateForm {
+                remember_me: true,
+                ..form()
+            })
+            .await
+            .unwrap();
+
+        assert!(session.absolute_expires_at - now > chrono::Duration::days(89));
+    }
+
+    #[actix_rt::test]
+    async fn resolve_renews_session_close_to_idle_expiration() {
+        let mut db = MockDb::new();
+        db.session
+            .expect_get_user_by_session_token()
+            .returning(|_| {
+                Ok((
+                    cookie_session(chrono::Duration::hours(2), chrono::Duration::days(10)),
+                    user(),
+                ))
+            });
+        db.session
+            .expect_session_extend()
+            .withf(|_, expires_at| *expires_at - chrono::Utc::now() > chrono::Duration::hours(23))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let resolved = mock_app(db, MockSecureGenerator::new())
+            .session_resolve_by_cookie("session-token".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(resolved.renewed);
+    }
+
+    #[actix_rt::test]
+    async fn resolve_renews_session_created_with_default_lifetime() {
+        let app = mock_app(db_logging_in(), generator_verifying(false));
+        let (mut session, _) = app.session_create(form()).await.unwrap();
+
+        // An hour before the session would expire by idleness
+        let idle_lifetime = chrono::Duration::hours(SessionLifetime::default().idle_lifetime);
+        let age = idle_lifetime - chrono::Duration::hours(1);
+        session.expires_at -= age;
+        session.absolute_expires_at -= age;
+
+        let mut db = MockDb::new();
+        db.session
+            .expect_get_user_by_session_token()
+            .returning(move |_| Ok((session.clone(), user())));
+        db.session
+            .expect_session_extend()
+            .withf(move |_, expires_at| *expires_at - chrono::Utc::now() > idle_lifetime / 2)
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let resolved = mock_app(db, MockSecureGenerator::new())
+            .session_resolve_by_cookie("session-token".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(resolved.renewed);
+    }
+
+    #[actix_rt::test]
+    async fn resolve_does_not_extend_past_absolute_expiration() {
+        let mut db = MockDb::new();
+        db.session
+            .expect_get_user_by_session_token()
+            .returning(|_| {
+                let session =
+                    cookie_session(chrono::Duration::hours(2), chrono::Duration::hours(2));
+                Ok((session, user()))
+            });
+        db.session.expect_session_extend().never();
+        db.session.expect_session_mark_seen().never();
+
+        let resolved = mock_app(db, MockSecureGenerator::new())
+            .session_resolve_by_cookie("session-token".to_owned())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!resolved.renewed);
+    }
+
+    #[actix_rt::test]
+    async fn logout_everywhere_notifies_applications() {
+        let mut db = MockDb::new();
+        db.session
+            .expect_session_delete_by_user_id()
+            .times(1)
+            .returning(|_| Ok(()));
+        db.logout_notification
+            .expect_logout_notifications_create_for_user()
+            .times(1)
+            .returning(|_| Ok(2));
+
+        mock_app(db, MockSecureGenerator::new())
+            .session_delete(&user(), SessionDeleteStrategy::All)
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn revoking_one_session_notifies_only_its_applications() {
+        let session_id = uuid::Uuid::new_v4();
+        let mut db = MockDb::new();
+        db.session
+            .expect_session_delete_by_id()
+            .times(1)
+            .returning(|_, _| Ok(true));
+        db.logout_notification
+            .expect_logout_notifications_create_for_user()
+            .never();
+        db.logout_notification
+            .expect_logout_notifications_create_for_session()
+            .withf(move |_, session| *session == session_id)
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        mock_app(db, MockSecureGenerator::new())
+            .session_delete(&user(), SessionDeleteStrategy::ById(session_id))
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn revoking_session_by_id_is_limited_to_the_user() {
+        let user = user();
+        let user_id = user.id;
+        let session_id = uuid::Uuid::new_v4();
+        let mut db = MockDb::new();
+        db.session
+            .expect_session_delete_by_id()
+            .withf(move |user, session| *user == user_id && *session == session_id)
+            .times(1)
+            .returning(|_, _| Ok(true));
+        db.logout_notification
+            .expect_logout_notifications_create_for_session()
+            .withf(move |user, _| *user == user_id)
+            .returning(|_, _| Ok(0));
+
+        mock_app(db, MockSecureGenerator::new())
+            .session_delete(&user, SessionDeleteStrategy::ById(session_id))
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn revoking_unknown_session_fails() {
+        let mut db = MockDb::new();
+        db.logout_notification
+            .expect_logout_notifications_create_for_session()
+            .returning(|_, _| Ok(0));
+        db.session
+            .expect_session_delete_by_id()
+            .returning(|_, _| Ok(false));
+
+        let result = mock_app(db, MockSecureGenerator::new())
+            .session_delete(&user(), SessionDeleteStrategy::ById(uuid::Uuid::new_v4()))
+            .await;
+
+        assert!(matches!(result, Err(SessionDeleteError::NotFound)));
+    }
+
+    #[actix_rt::test]
+    async fn lists_sessions_of_the_user() {
+        let user = user();
+        let user_id = user.id;
+        let mut db = MockDb::new();
+        db.session
+            .expect_sessions_list_for_user()
+            .withf(move |user| *user == user_id)
+            .times(1)
+            .returning(|_| {
+                Ok(vec![
+                    cookie_session(chrono::Duration::hours(20), chrono::Duration::days(10)),
+                    cookie_session(chrono::Duration::hours(2), chrono::Duration::days(1)),
+                ])
+            });
+
+        let sessions = mock_app(db, MockSecureGenerator::new())
+            .session_list(&user)
+            .await
+            .unwrap();
+
+        assert_eq!(sessions.len(), 2);
+    }
+
+    fn db_with_access_token(scopes: &[&str]) -> MockDb {
+        let scopes: Vec<String> = scopes.iter().map(|scope| scope.to_string()).collect();
+        let mut db = MockDb::new();
+        db.session
+            .expect_get_user_by_access_token()
+            .returning(move |token| {
+                Ok((
+                    AccessToken {
+                        token,
+                        scopes: scopes.clone(),
+                        expires_at: chrono::Utc::now() + AccessToken::lifetime(),
+                        registration_id: uuid::Uuid::new_v4(),
+                        dpop_jkt: None,
+                    },
+                    user(),
+                ))
+            });
+        db
+    }
+
+    #[actix_rt::test]
+    async fn access_token_without_scopes_reads_profile_only() {
+        let viewer = mock_app(db_with_access_token(&[]), MockSecureGenerator::new())
+            .session_resolve_by_access_token("token".to_owned(), TokenScheme::Bearer, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(viewer.has_scope(SCOPE_PROFILE));
+        assert!(!viewer.has_scope(SCOPE_EMAIL));
+    }
+
+    #[actix_rt::test]
+    async fn access_token_reads_only_granted_scopes() {
+        let viewer = mock_app(
+            db_with_access_token(&[SCOPE_EMAIL]),
+            MockSecureGenerator::new(),
+        )
+        .session_resolve_by_access_token("token".to_owned(), TokenScheme::Bearer, None)
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(viewer.has_scope(SCOPE_EMAIL));
+        assert!(!viewer.has_scope(SCOPE_PROFILE));
+    }
+
+    /// Access token bound to the key of [`dpop::testing`] proofs
+    fn db_with_bound_token(proof_is_new: bool) -> MockDb {
+        let mut db = MockDb::new();
+        db.session
+            .expect_get_user_by_access_token()
+            .returning(|token| {
+                Ok((
+                    AccessToken {
+                        token,
+                        scopes: vec![],
+                        expires_at: chrono::Utc::now() + AccessToken::lifetime(),
+                        registration_id: uuid::Uuid::new_v4(),
+                        dpop_jkt: Some(dpop::testing::jkt()),
+                    },
+                    user(),
+                ))
+            });
+        db.dpop_proof
+            .expect_dpop_proof_use()
+            .withf(|jkt, jti, _| *jkt == dpop::testing::jkt() && jti == "proof-1")
+            .returning(move |_, _, _| Ok(proof_is_new));
+        db
+    }
+
+    #[actix_rt::test]
+    async fn bound_access_token_accepted_with_proof() {
+        let viewer = mock_app(db_with_bound_token(true), MockSecureGenerator::new())
+            .session_resolve_by_access_token(
+                "token".to_owned(),
+                TokenScheme::DPoP,
+                Some(dpop::testing::resource_proof("proof-1", "token")),
+            )
+            .await
+            .unwrap();
+
+        assert!(viewer.is_some());
+    }
+
+    #[actix_rt::test]
+    async fn bound_access_token_rejected_with_bearer_scheme() {
+        let result = mock_app(db_with_bound_token(true), MockSecureGenerator::new())
+            .session_resolve_by_access_token(
+                "token".to_owned(),
+                TokenScheme::Bearer,
+                Some(dpop::testing::resource_proof("proof-1", "token")),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(SessionResolveError::InvalidDPoPProof(
+                DPoPError::SchemeMismatch
+            ))
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn replayed_proof_rejected() {
+        let result = mock_app(db_with_bound_token(false), MockSecureGenerator::new())
+            .session_resolve_by_access_token(
+                "token".to_owned(),
+                TokenScheme::DPoP,
+                Some(dpop::testing::resource_proof("proof-1", "token")),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(SessionResolveError::InvalidDPoPProof(DPoPError::Replayed))
+        ));
+    }
 }
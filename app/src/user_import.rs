@@ -0,0 +1,203 @@
+use accesso_core::app::user_import::{
+    UserImport, UserImportError, UserImportReport, UserImportRow, UserImportRowError,
+};
+use accesso_core::contracts::{Repository, SecureGenerator, UserRegisterForm};
+use accesso_core::models::User;
+use accesso_core::services::DEFAULT_LOCALE;
+use async_trait::async_trait;
+use validator::Validate;
+
+use crate::{App, Service};
+
+#[async_trait]
+impl UserImport for App {
+    async fn users_import(&self, rows: Vec<UserImportRow>) -> UserImportReport {
+        let mut report = UserImportReport::default();
+
+        for (row, form) in rows.into_iter().enumerate() {
+            let email = form.email.clone();
+
+            match import_row(self, form).await {
+                Ok(user) => report.created.push(user),
+                Err(error) => report.failed.push(UserImportRowError { row, email, error }),
+            }
+        }
+
+        tracing::info!(
+            created = report.created.len(),
+            failed = report.failed.len(),
+            "Users imported"
+        );
+
+        report
+    }
+}
+
+async fn import_row(app: &App, form: UserImportRow) -> Result<User, UserImportError> {
+    let db = app.get::<Service<dyn Repository>>()?;
+    let generator = app.get::<Service<dyn SecureGenerator>>()?;
+
+    form.validate()?;
+
+    if !generator.hash_is_supported(&form.password_hash) {
+        return Err(UserImportError::UnsupportedPasswordHash);
+    }
+
+    // Finds accounts with the legacy canonical email too. Canonical email is unique,
+    // so the insert still fails for the email imported meanwhile or earlier in the batch
+    if db.user_has_with_email(form.email.clone()).await? {
+        return Err(UserImportError::EmailAlreadyRegistered);
+    }
+
+    Ok(db
+        .user_register(
+            UserRegisterForm {
+                id: uuid::Uuid::new_v4(),
+                email: form.email,
+                password_hash: form.password_hash,
+                first_name: form.first_name,
+                last_name: form.last_name,
+                locale: form.locale.unwrap_or_else(|| DEFAULT_LOCALE.to_owned()),
+                // Legacy apps may never have confirmed the email
+                email_verified: false,
+                invite: None,
+            },
+            None,
+        )
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accesso_core::contracts::{MockDb, MockSecureGenerator, RegisterUserError};
+    use accesso_core::models::EmailStatus;
+    use accesso_core::services::canonical_email;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+
+    const EXISTING_EMAIL: &str = "existing@domain.com";
+
+    /// Users table with unique canonical email and one registered account
+    fn db_registering() -> MockDb {
+        let mut db = MockDb::new();
+        db.users
+            .expect_user_has_with_email()
+            .returning(|email| Ok(email == EXISTING_EMAIL));
+
+        let registered = Mutex::new(HashSet::new());
+        db.users.expect_user_register().returning(move |form, _| {
+            let canonical_email = canonical_email(&form.email);
+            if !registered.lock().unwrap().insert(canonical_email.clone()) {
+                return Err(RegisterUserError::EmailAlreadyExists);
+            }
+
+            Ok(User {
+                id: form.id,
+                email: form.email,
+                canonical_email,
+                password_hash: form.password_hash,
+                first_name: form.first_name,
+                last_name: form.last_name,
+                locale: form.locale,
+                email_status: EmailStatus::Deliverable,
+                email_verified: form.email_verified,
+            })
+        });
+        db
+    }
+
+    fn generator() -> MockSecureGenerator {
+        let mut generator = MockSecureGenerator::new();
+        generator
+            .expect_hash_is_supported()
+            .returning(|hash| hash.starts_with("$2b$"));
+        generator
+    }
+
+    fn mock_app(db: MockDb) -> App {
+        let db: Arc<dyn Repository> = Arc::new(db);
+        let generator: Arc<dyn SecureGenerator> = Arc::new(generator());
+
+        App::builder()
+            .with_service(Service::from(db))
+            .with_service(Service::from(generator))
+            .build()
+    }
+
+    fn row(email: &str) -> UserImportRow {
+        UserImportRow {
+            email: email.to_owned(),
+            password_hash: "$2b$12$hash".to_owned(),
+            first_name: "Demo".to_owned(),
+            last_name: "User".to_owned(),
+            locale: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn imports_valid_rows_as_unverified() {
+        let report = mock_app(db_registering())
+            .users_import(vec![row("first@domain.com"), row("second@domain.com")])
+            .await;
+
+        assert!(report.failed.is_empty());
+        assert_eq!(report.created.len(), 2);
+        assert!(report.created.iter().all(|user| !user.email_verified));
+    }
+
+    #[actix_rt::test]
+    async fn reports_failed_rows_and_imports_the_rest() {
+        let report = mock_app(db_registering())
+            .users_import(vec![
+                row("john.doe@gmail.com"),
+                row("johndoe+news@gmail.com"),
+                row(EXISTING_EMAIL),
+                UserImportRow {
+                    password_hash: "$1$md5$hash".to_owned(),
+                    ..row("md5@domain.com")
+                },
+                row("not an email"),
+                row("last@domain.com"),
+            ])
+            .await;
+
+        let created: Vec<_> = report
+            .created
+            .iter()
+            .map(|user| user.email.as_str())
+            .collect();
+        assert_eq!(created, vec!["john.doe@gmail.com", "last@domain.com"]);
+
+        let failed: Vec<_> = report
+            .failed
+            .iter()
+            .map(|failed| (failed.row, failed.email.as_str()))
+            .collect();
+        assert_eq!(
+            failed,
+            vec![
+                (1, "johndoe+news@gmail.com"),
+                (2, EXISTING_EMAIL),
+                (3, "md5@domain.com"),
+                (4, "not an email"),
+            ]
+        );
+        assert!(matches!(
+            report.failed[0].error,
+            UserImportError::EmailAlreadyRegistered
+        ));
+        assert!(matches!(
+            report.failed[1].error,
+            UserImportError::EmailAlreadyRegistered
+        ));
+        assert!(matches!(
+            report.failed[2].error,
+            UserImportError::UnsupportedPasswordHash
+        ));
+        assert!(matches!(
+            report.failed[3].error,
+            UserImportError::InvalidForm(_)
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use accesso_core::app::email_outbox::EmailOutbox;
+use accesso_core::app::logout::Logout;
+use accesso_core::app::maintenance::{Maintenance, PurgeReport};
+use accesso_settings::Settings;
+use opentelemetry::KeyValue;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Count of notifications sent at once
+const LOGOUT_NOTIFICATIONS_BATCH: i64 = 50;
+
+/// Count of emails claimed by the dispatcher at once
+const EMAIL_OUTBOX_BATCH: i64 = 50;
+
+/// Delivers back-channel logout notifications until the process exits.
+/// Failed notifications are retried with backoff by the next iterations.
+pub async fn run_logout_worker(settings: Arc<Settings>) {
+    let app = crate::create_app(&settings);
+    let interval = Duration::from_secs(settings.logout.worker_interval);
+
+    loop {
+        match app
+            .logout_notifications_deliver(LOGOUT_NOTIFICATIONS_BATCH)
+            .await
+        {
+            // Probably more pending notifications are waiting
+            Ok(delivered) if delivered as i64 == LOGOUT_NOTIFICATIONS_BATCH => continue,
+            Ok(_) => {}
+            Err(error) => tracing::error!(%error, "Could not deliver logout notifications"),
+        }
+
+        actix_web::rt::time::sleep(interval).await;
+    }
+}
+
+/// Sends queued emails until the process exits.
+/// Failed emails are retried with backoff by the next iterations.
+pub async fn run_email_worker(settings: Arc<Settings>) {
+    let app = crate::create_app(&settings);
+    let interval = Duration::from_secs(settings.email.outbox_interval);
+
+    loop {
+        match app.email_outbox_dispatch(EMAIL_OUTBOX_BATCH).await {
+            // Probably more queued emails are waiting
+            Ok(sent) if sent as i64 == EMAIL_OUTBOX_BATCH => continue,
+            Ok(_) => {}
+            Err(error) => tracing::error!(%error, "Could not dispatch queued emails"),
+        }
+
+        actix_web::rt::time::sleep(interval).await;
+    }
+}
+
+/// Purges expired tokens, codes and requests until the process exits
+pub async fn run_maintenance_worker(settings: Arc<Settings>) {
+    let app = crate::create_app(&settings);
+    let interval = Duration::from_secs(settings.maintenance.interval);
+
+    let meter = opentelemetry::global::meter("accesso");
+    let purged_rows = meter
+        .u64_counter("maintenance.purged_rows")
+        .with_description("Expired rows deleted by maintenance worker")
+        .init();
+    let run_duration = meter
+        .f64_value_recorder("maintenance.run_duration")
+        .with_description("Duration of maintenance run, in seconds")
+        .init();
+    let failed_runs = meter
+        .u64_counter("maintenance.failed_runs")
+        .with_description("Maintenance runs failed with error")
+        .init();
+
+    loop {
+        let started = Instant::now();
+
+        match app.maintenance_purge_expired().await {
+            Ok(report) => {
+                let PurgeReport {
+                    session_tokens,
+                    access_tokens,
+                    authorization_codes,
+                    registration_requests,
+                    registration_failures,
+                    sent_emails,
+                    login_attempts,
+                    dpop_proofs,
+                } = report;
+
+                for (table, count) in [
+                    ("session_tokens", session_tokens),
+                    ("access_tokens", access_tokens),
+                    ("authorization_codes", authorization_codes),
+                    ("registration_requests", registration_requests),
+                    ("registration_failures", registration_failures),
+                    ("email_outbox", sent_emails),
+                    ("login_attempts", login_attempts),
+                    ("dpop_proofs", dpop_proofs),
+                ] {
+                    purged_rows.add(count, &[KeyValue::new("table", table)]);
+                }
+
+                tracing::info!(
+                    session_tokens,
+                    access_tokens,
+                    authorization_codes,
+                    registration_requests,
+                    registration_failures,
+                    sent_emails,
+                    login_attempts,
+                    dpop_proofs,
+                    total = report.total(),
+                    "Expired rows purged"
+                );
+            }
+            Err(error) => {
+                failed_runs.add(1, &[]);
+                tracing::error!(%error, "Could not purge expired rows");
+            }
+        }
+
+        run_duration.record(started.elapsed().as_secs_f64(), &[]);
+
+        actix_web::rt::time::sleep(interval).await;
+    }
+}
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 
 use crate::contracts::repo::{UnexpectedDatabaseError, UserEditError};
 use crate::models::User;
+use crate::services::locale::validate_locale;
 
 #[async_trait]
 pub trait Account {
@@ -12,10 +13,13 @@ pub trait Account {
     ) -> Result<User, AccountEditError>;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Validate)]
 pub struct AccountEditForm {
     pub first_name: String,
     pub last_name: String,
+    /// Locale is not changed when not set
+    #[validate(custom = "validate_locale")]
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +27,9 @@ pub enum AccountEditError {
     #[error("User not found")]
     UserNotFound,
 
+    #[error(transparent)]
+    InvalidForm(#[from] validator::ValidationErrors),
+
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
 }
@@ -37,6 +44,8 @@ impl From<UserEditError> for AccountEditError {
     fn from(error: UserEditError) -> Self {
         match error {
             UserEditError::UserNotFound => Self::UserNotFound,
+            // Account edit does not change the email
+            UserEditError::EmailAlreadyExists => Self::Unexpected(error.into()),
             UserEditError::Unexpected(report) => Self::Unexpected(report),
         }
     }
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::contracts::repo::UnexpectedDatabaseError;
+use crate::services::email_domain::EmailDomainRejection;
+
+#[async_trait]
+pub trait EmailDomainPolicy {
+    /// Global domain rules apply always, rules of the application only if it is set
+    async fn email_domain_check(
+        &self,
+        email: &str,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<(), EmailDomainCheckError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailDomainCheckError {
+    #[error(transparent)]
+    Rejected(#[from] EmailDomainRejection),
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<UnexpectedDatabaseError> for EmailDomainCheckError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::contracts::repo::UnexpectedDatabaseError;
+use crate::services::sendgrid_webhook::SendGridWebhookError;
+
+#[async_trait]
+pub trait EmailEvents {
+    /// Verifies the signed SendGrid webhook body and records its bounces and complaints.
+    /// Returns count of new events, repeated deliveries are ignored
+    async fn email_events_ingest_sendgrid(
+        &self,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+    ) -> Result<usize, EmailEventsIngestError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailEventsIngestError {
+    #[error(transparent)]
+    Rejected(#[from] SendGridWebhookError),
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<UnexpectedDatabaseError> for EmailEventsIngestError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use crate::contracts::repo::UnexpectedDatabaseError;
+
+#[async_trait]
+pub trait EmailOutbox {
+    /// Sends queued emails which attempt time has come, returns count of sent.
+    /// Failed emails are retried with backoff, then moved to dead letters
+    async fn email_outbox_dispatch(&self, limit: i64) -> Result<usize, EmailDispatchError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailDispatchError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<UnexpectedDatabaseError> for EmailDispatchError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::contracts::repo::UnexpectedDatabaseError;
+use crate::models::{SessionToken, User};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Logout {
+    /// RP-Initiated Logout: deletes current session and resolves where to redirect user
+    /// https://openid.net/specs/openid-connect-rpinitiated-1_0.html
+    async fn logout_end_session(
+        &self,
+        actor: Option<(SessionToken, User)>,
+        form: EndSessionForm,
+    ) -> Result<EndSessionDone, EndSessionError>;
+
+    /// Sends pending back-channel logout notifications, returns count of delivered
+    async fn logout_notifications_deliver(&self, limit: i64) -> Result<usize, LogoutDeliverError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EndSessionForm {
+    /// Required if `post_logout_redirect_uri` is passed
+    pub client_id: Option<uuid::Uuid>,
+    /// Should be registered for the application
+    pub post_logout_redirect_uri: Option<String>,
+    /// Passed back to the application with redirect
+    pub state: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct EndSessionDone {
+    pub redirect_uri: Option<String>,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EndSessionError {
+    /// Unknown client or not registered post logout redirect uri
+    #[error("Invalid request: {0}")]
+    InvalidRequest(eyre::Report),
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogoutDeliverError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<UnexpectedDatabaseError> for EndSessionError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
+
+impl From<UnexpectedDatabaseError> for LogoutDeliverError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
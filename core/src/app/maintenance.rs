@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+
+use crate::contracts::repo::UnexpectedDatabaseError;
+
+#[async_trait]
+pub trait Maintenance {
+    /// Deletes rows expired longer than retention window ago.
+    /// Rows are deleted in batches to avoid long locks on the tables
+    async fn maintenance_purge_expired(&self) -> Result<PurgeReport, MaintenanceError>;
+
+    /// Recalculates canonical emails of all users after canonicalization rules change.
+    /// Of users sharing the new canonical email only one gets it, others keep the old one,
+    /// sign in with their exact email and are reported to be merged or removed
+    async fn maintenance_canonicalize_emails(&self)
+        -> Result<CanonicalizeReport, MaintenanceError>;
+}
+
+/// Count of deleted rows for each table
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PurgeReport {
+    pub session_tokens: u64,
+    pub access_tokens: u64,
+    pub authorization_codes: u64,
+    pub registration_requests: u64,
+    pub registration_failures: u64,
+    pub sent_emails: u64,
+    pub login_attempts: u64,
+    pub dpop_proofs: u64,
+}
+
+impl PurgeReport {
+    pub fn total(&self) -> u64 {
+        self.session_tokens
+            + self.access_tokens
+            + self.authorization_codes
+            + self.registration_requests
+            + self.registration_failures
+            + self.sent_emails
+            + self.login_attempts
+            + self.dpop_proofs
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CanonicalizeReport {
+    /// Count of users with changed canonical email
+    pub updated: u64,
+    pub collisions: Vec<CanonicalEmailCollision>,
+}
+
+/// Different accounts that turned out to be the same mailbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalEmailCollision {
+    pub canonical_email: String,
+    /// Account which got the canonical email
+    pub kept_user_id: uuid::Uuid,
+    /// Accounts left with the old canonical email
+    pub user_ids: Vec<uuid::Uuid>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MaintenanceError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<UnexpectedDatabaseError> for MaintenanceError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
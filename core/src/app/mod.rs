@@ -1,5 +1,12 @@
 pub mod account;
 pub mod application;
+pub mod email_domain;
+pub mod email_event;
+pub mod email_outbox;
+pub mod logout;
+pub mod maintenance;
 pub mod oauth;
+pub mod personal_access_token;
 pub mod registrator;
 pub mod session;
+pub mod user_import;
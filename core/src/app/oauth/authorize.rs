@@ -1,11 +1,11 @@
-use crate::models::User;
+use crate::models::{SessionToken, User};
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait OAuthAuthorize {
     async fn oauth_request_authorize_code(
         &self,
-        actor: Option<User>,
+        actor: Option<(SessionToken, User)>,
         form: RequestAuthCode,
     ) -> Result<AuthCodeCreated, RequestAuthCodeFailed>;
 }
@@ -30,6 +30,25 @@ pub struct RequestAuthCode {
     /// When the user is redirected back to your app, double check that the state value matches what you set it to originally.
     /// This will ensure an attacker can’t intercept the authorization flow.
     pub state: Option<String>,
+
+    /// Whether the authorization server prompts the user for reauthentication and consent
+    pub prompt: Option<Prompt>,
+
+    /// Maximum authentication age in seconds.
+    /// If the elapsed time is greater than this value, the user must be reauthenticated
+    #[validate(range(min = 0))]
+    pub max_age: Option<i64>,
+}
+
+/// https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prompt {
+    /// Do not display any authentication or consent UI,
+    /// fail with `login_required` or `consent_required` instead
+    None,
+    /// Require the user to enter credentials even if a session exists.
+    /// Fails with `ReauthenticationRequired` until the user signs in after the prompt is received
+    Login,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -58,6 +77,26 @@ pub enum RequestAuthCodeFailed {
         state: Option<String>,
     },
 
+    /// The user is not authenticated with `prompt=none`, or the authentication is older
+    /// than allowed by `max_age`. The user is redirected back to the application
+    #[error("Login required: {redirect_uri}")]
+    LoginRequired {
+        redirect_uri: String,
+        state: Option<String>,
+    },
+
+    /// `prompt=login` was requested, the current user should enter credentials again.
+    /// The same request continues after the user signs in
+    #[error("Reauthentication required")]
+    ReauthenticationRequired,
+
+    /// The user has not confirmed access for the application yet
+    #[error("Consent required: {redirect_uri}")]
+    ConsentRequired {
+        redirect_uri: String,
+        state: Option<String>,
+    },
+
     /// The application is not authorized to request an authorization code using this method: The redirect_URI of the service either is incorrect or not provided.
     #[error("Unauthorized application")]
     UnauthorizedClient,
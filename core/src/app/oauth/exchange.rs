@@ -1,4 +1,5 @@
 use crate::contracts::UnexpectedDatabaseError;
+use crate::services::dpop::{DPoPError, DPoPRequest};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -34,11 +35,16 @@ pub struct ExchangeAccessTokenForm {
     pub client_id: uuid::Uuid,
 
     pub client_secret: String,
+
+    /// Access token will be bound to the key of DPoP proof
+    /// https://datatracker.ietf.org/doc/html/rfc9449#section-5
+    pub dpop: Option<DPoPRequest>,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum TokenType {
     Bearer,
+    DPoP,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -61,6 +67,8 @@ pub enum ExchangeFailed {
     InvalidScope,
     #[error("Unauthorized application")]
     UnauthorizedClient,
+    #[error("Invalid DPoP proof: {0}")]
+    InvalidDPoPProof(#[from] DPoPError),
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
 }
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+
+use crate::contracts::repo::UnexpectedDatabaseError;
+use crate::models::{PersonalAccessToken, SCOPES};
+use validator::ValidationError;
+
+#[async_trait]
+pub trait PersonalAccessTokens {
+    /// Created token is returned with its value, it is the only time user can see it
+    async fn personal_access_token_create(
+        &self,
+        user_id: uuid::Uuid,
+        form: PersonalAccessTokenCreateForm,
+    ) -> Result<PersonalAccessToken, PersonalAccessTokenCreateError>;
+
+    async fn personal_access_tokens_list(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<PersonalAccessToken>, PersonalAccessTokensListError>;
+
+    async fn personal_access_token_revoke(
+        &self,
+        user_id: uuid::Uuid,
+        token_id: uuid::Uuid,
+    ) -> Result<(), PersonalAccessTokenRevokeError>;
+}
+
+#[derive(Debug, Clone, Validate)]
+pub struct PersonalAccessTokenCreateForm {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    /// Only known scopes, see [`SCOPES`]
+    #[validate(custom = "validate_scopes")]
+    pub scopes: Vec<String>,
+
+    /// Not longer than [`PersonalAccessToken::MAX_LIFETIME_DAYS`]
+    #[validate(custom = "validate_lifetime_days")]
+    pub expires_in_days: i64,
+}
+
+fn validate_scopes(scopes: &[String]) -> Result<(), ValidationError> {
+    match scopes.iter().all(|scope| SCOPES.contains(&scope.as_str())) {
+        true => Ok(()),
+        false => Err(ValidationError::new("scope")),
+    }
+}
+
+fn validate_lifetime_days(days: i64) -> Result<(), ValidationError> {
+    if (1..=PersonalAccessToken::MAX_LIFETIME_DAYS).contains(&days) {
+        return Ok(());
+    }
+
+    let mut error = ValidationError::new("range");
+    error.add_param("min".into(), &1);
+    error.add_param("max".into(), &PersonalAccessToken::MAX_LIFETIME_DAYS);
+    Err(error)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PersonalAccessTokenCreateError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+    #[error("Invalid form: {0}")]
+    InvalidForm(#[from] validator::ValidationErrors),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PersonalAccessTokensListError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PersonalAccessTokenRevokeError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+    #[error("Token not found")]
+    NotFound,
+}
+
+impl From<UnexpectedDatabaseError> for PersonalAccessTokenCreateError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
+
+impl From<UnexpectedDatabaseError> for PersonalAccessTokensListError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
+
+impl From<UnexpectedDatabaseError> for PersonalAccessTokenRevokeError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::contracts::{RegisterUserError, SendEmailError};
+use crate::app::email_domain::EmailDomainCheckError;
+use crate::contracts::{HashingError, RegisterUserError};
+use crate::services::email_domain::EmailDomainRejection;
+use crate::services::DEFAULT_LOCALE;
 use async_trait::async_trait;
 use chrono::Utc;
 
@@ -9,6 +12,13 @@ pub trait Registrator {
         form: CreateRegisterRequest,
     ) -> Result<RequestCreated, RegisterRequestError>;
 
+    /// Sends the code of the latest request again.
+    /// Responds the same way if there is no request for the email
+    async fn registrator_resend(
+        &self,
+        form: ResendRegisterRequest,
+    ) -> Result<RequestCreated, RegisterResendError>;
+
     async fn registrator_confirm(&self, form: RegisterForm) -> Result<(), RegisterConfirmError>;
 }
 
@@ -16,10 +26,26 @@ pub trait Registrator {
 pub struct CreateRegisterRequest {
     #[validate(email)]
     pub email: String,
+
+    /// Required when registration is invite-only
+    pub invite: Option<String>,
+
+    /// Negotiated from `Accept-Language`, the user is registered with it
+    pub locale: String,
+}
+
+#[derive(Debug, Clone, Validate)]
+pub struct ResendRegisterRequest {
+    #[validate(email)]
+    pub email: String,
 }
 
 #[derive(Debug, Clone, Validate)]
 pub struct RegisterForm {
+    /// Wrong codes are counted for requests of this email
+    #[validate(email)]
+    pub email: String,
+
     #[validate(length(min = 7))]
     pub confirmation_code: String,
 
@@ -42,10 +68,29 @@ pub struct RequestCreated {
 pub enum RegisterRequestError {
     #[error(transparent)]
     InvalidForm(#[from] validator::ValidationErrors),
-    #[error("Email already registered {0}")]
-    EmailAlreadyRegistered(String),
-    #[error("Failed to send email: {0}")]
-    EmailSenderError(#[from] SendEmailError),
+    #[error("Invite not found or already used")]
+    InviteInvalid,
+    #[error(transparent)]
+    EmailDomainRejected(EmailDomainRejection),
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<EmailDomainCheckError> for RegisterRequestError {
+    fn from(e: EmailDomainCheckError) -> Self {
+        match e {
+            EmailDomainCheckError::Rejected(rejection) => Self::EmailDomainRejected(rejection),
+            EmailDomainCheckError::Unexpected(e) => Self::Unexpected(e),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegisterResendError {
+    #[error(transparent)]
+    InvalidForm(#[from] validator::ValidationErrors),
+    #[error("Code was sent recently, retry after {retry_after}")]
+    TooManyRequests { retry_after: chrono::Duration },
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
 }
@@ -54,20 +99,28 @@ pub enum RegisterRequestError {
 pub enum RegisterConfirmError {
     #[error("{0}")]
     InvalidForm(#[from] validator::ValidationErrors),
+    /// Also after too many wrong codes for the mailbox during the lifetime of a request
     #[error("Code not found")]
     CodeNotFound,
+    #[error("Invite not found or already used")]
+    InviteInvalid,
     #[error("Code already activated: {0}")]
     AlreadyActivated(#[source] RegisterUserError),
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
-    #[error("Failed to send email: {0}")]
-    EmailSenderError(#[from] SendEmailError),
+}
+
+impl From<HashingError> for RegisterConfirmError {
+    fn from(e: HashingError) -> Self {
+        Self::Unexpected(e.into())
+    }
 }
 
 impl From<RegisterUserError> for RegisterConfirmError {
     fn from(e: RegisterUserError) -> Self {
         match e {
             RegisterUserError::EmailAlreadyExists => Self::AlreadyActivated(e),
+            RegisterUserError::InviteAlreadyUsed => Self::InviteInvalid,
             _ => Self::Unexpected(e.into()),
         }
     }
@@ -80,6 +133,8 @@ impl CreateRegisterRequest {
     {
         Self {
             email: email.into(),
+            invite: None,
+            locale: DEFAULT_LOCALE.to_owned(),
         }
     }
 }
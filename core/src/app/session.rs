@@ -1,42 +1,92 @@
 use crate::contracts::repo::UnexpectedDatabaseError;
+use crate::contracts::HashingError;
 use crate::models::{SessionToken, User};
+use crate::services::dpop::{DPoPError, DPoPRequest};
 use async_trait::async_trait;
 
 pub use crate::contracts::repo::SessionCreateError as RepoError;
 
 #[async_trait]
 pub trait Session {
+    /// Session close to expiration is renewed
     async fn session_resolve_by_cookie(
         &self,
         cookie: String,
-    ) -> Result<Option<User>, SessionResolveError>;
+    ) -> Result<Option<CookieSession>, SessionResolveError>;
 
+    /// Access token bound to DPoP key requires DPoP scheme and proof of possession
     async fn session_resolve_by_access_token(
         &self,
         access_token: String,
-    ) -> Result<Option<User>, SessionResolveError>;
+        scheme: TokenScheme,
+        dpop: Option<DPoPRequest>,
+    ) -> Result<Option<TokenViewer>, SessionResolveError>;
 
+    /// Failed attempts are counted per email and per client IP.
+    /// Each next attempt is delayed, then login is locked out for a while
     async fn session_create(
         &self,
         form: SessionCreateForm,
     ) -> Result<(SessionToken, User), SessionCreateError>;
 
+    /// Unlocks account by the code from the email sent on lockout
+    async fn session_unlock(&self, code: String) -> Result<(), SessionUnlockError>;
+
     async fn session_delete(
         &self,
         user: &User,
         strategy: SessionDeleteStrategy,
     ) -> Result<(), SessionDeleteError>;
+
+    /// Active sessions of the user on all devices
+    async fn session_list(&self, user: &User) -> Result<Vec<SessionToken>, SessionListError>;
+}
+
+/// Session resolved by cookie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieSession {
+    pub session: SessionToken,
+    pub user: User,
+    /// Expiration of the session is moved forward, cookie should be issued again
+    pub renewed: bool,
+}
+
+/// Authorization scheme the access token is sent with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenScheme {
+    /// `Bearer` or the token as is
+    Bearer,
+    DPoP,
+}
+
+/// User resolved by OAuth or personal access token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenViewer {
+    pub user: User,
+    /// What the token allows to read about the user
+    pub scopes: Vec<String>,
+}
+
+impl TokenViewer {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
 }
 
 pub enum SessionDeleteStrategy {
     All,
-    Single(String),
+    /// Current session of the user
+    Single(SessionToken),
+    /// Session on another device, selected from the sessions list
+    ById(uuid::Uuid),
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum SessionResolveError {
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
+    #[error("Invalid DPoP proof: {0}")]
+    InvalidDPoPProof(#[from] DPoPError),
 }
 
 #[derive(Debug, Validate, PartialEq, Eq, Hash)]
@@ -46,6 +96,13 @@ pub struct SessionCreateForm {
 
     #[validate(length(min = 8))]
     pub password: String,
+
+    /// Chooses long absolute lifetime of the session instead of short one
+    pub remember_me: bool,
+
+    /// Device which creates the session
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -56,12 +113,48 @@ pub enum SessionCreateError {
     InvalidForm(#[from] validator::ValidationErrors),
     #[error("Invalid credentials")]
     InvalidCredentials,
+    #[error("Too many failed attempts, retry after {} seconds", retry_after.num_seconds())]
+    TooManyAttempts { retry_after: chrono::Duration },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionUnlockError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+    #[error("Unlock code not found")]
+    NotFound,
+}
+
+impl From<UnexpectedDatabaseError> for SessionUnlockError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum SessionDeleteError {
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
+    #[error("Session not found")]
+    NotFound,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionListError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
+impl From<UnexpectedDatabaseError> for SessionResolveError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
+
+impl From<UnexpectedDatabaseError> for SessionListError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
 }
 
 impl From<UnexpectedDatabaseError> for SessionDeleteError {
@@ -75,6 +168,12 @@ impl From<UnexpectedDatabaseError> for SessionCreateError {
         Self::Unexpected(e.into())
     }
 }
+
+impl From<HashingError> for SessionCreateError {
+    fn from(e: HashingError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
 impl From<RepoError> for SessionCreateError {
     fn from(error: RepoError) -> Self {
         match error {
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+
+use crate::contracts::repo::{RegisterUserError, UnexpectedDatabaseError};
+use crate::models::User;
+use crate::services::locale::validate_locale;
+
+#[async_trait]
+pub trait UserImport {
+    /// Creates users with password hashes made by other applications.
+    /// Rows are imported one by one, failed row does not stop the import.
+    /// Foreign hash is upgraded to the native format on the first login
+    async fn users_import(&self, rows: Vec<UserImportRow>) -> UserImportReport;
+}
+
+#[derive(Debug, Clone, Validate, PartialEq, Eq)]
+pub struct UserImportRow {
+    #[validate(email)]
+    pub email: String,
+
+    /// bcrypt hash `$2b$12$…` or PHC string of argon2, scrypt or PBKDF2 hash
+    pub password_hash: String,
+
+    #[validate(length(min = 2))]
+    pub first_name: String,
+
+    #[validate(length(min = 2))]
+    pub last_name: String,
+
+    /// The default locale when not set
+    #[validate(custom = "validate_locale")]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct UserImportReport {
+    pub created: Vec<User>,
+    pub failed: Vec<UserImportRowError>,
+}
+
+#[derive(Debug)]
+pub struct UserImportRowError {
+    /// Index of the row in the import, starting from zero
+    pub row: usize,
+    pub email: String,
+    pub error: UserImportError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UserImportError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+    #[error("Invalid form: {0}")]
+    InvalidForm(#[from] validator::ValidationErrors),
+    #[error("Password hash format is not supported")]
+    UnsupportedPasswordHash,
+    /// Email is registered already or repeated earlier in the same import
+    #[error("Email already registered")]
+    EmailAlreadyRegistered,
+}
+
+impl From<UnexpectedDatabaseError> for UserImportError {
+    fn from(e: UnexpectedDatabaseError) -> Self {
+        Self::Unexpected(e.into())
+    }
+}
+
+impl From<RegisterUserError> for UserImportError {
+    fn from(e: RegisterUserError) -> Self {
+        match e {
+            RegisterUserError::EmailAlreadyExists => Self::EmailAlreadyRegistered,
+            RegisterUserError::InviteAlreadyUsed => Self::Unexpected(e.into()),
+            RegisterUserError::Unexpected(e) => Self::Unexpected(e),
+        }
+    }
+}
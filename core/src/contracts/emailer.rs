@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 #[cfg(feature = "testing")]
 use mockall::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, thiserror::Error)]
 pub enum SendEmailError {
@@ -9,6 +9,16 @@ pub enum SendEmailError {
     ReqwestError(#[from] reqwest::Error),
     #[error("Serialize json error: {0}")]
     SerdeJsonError(#[from] serde_json::Error),
+    #[error("SMTP error: {0}")]
+    SmtpError(#[from] lettre::transport::smtp::Error),
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(#[from] lettre::address::AddressError),
+    #[error("Could not build email: {0}")]
+    MessageError(#[from] lettre::error::Error),
+    #[error("Could not capture email: {0}")]
+    CaptureError(#[from] crate::services::capture::CaptureError),
+    #[error("Template error: {0}")]
+    TemplateError(#[from] crate::services::email_templates::EmailTemplatesError),
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
 }
@@ -16,10 +26,17 @@ pub enum SendEmailError {
 #[cfg_attr(feature = "testing", automock)]
 #[async_trait]
 pub trait EmailNotification: Send + Sync {
-    async fn send(&self, email: String, content: EmailMessage) -> Result<(), SendEmailError>;
+    /// Unknown locale falls back to the default templates
+    async fn send(
+        &self,
+        email: String,
+        locale: String,
+        content: EmailMessage,
+    ) -> Result<(), SendEmailError>;
 }
 
-#[derive(Debug, Serialize)]
+/// Stored as json in the outbox, renaming a variant or field needs a data migration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EmailMessage {
     RegisterConfirmation {
         code: String,
@@ -28,4 +45,11 @@ pub enum EmailMessage {
         first_name: String,
         last_name: String,
     },
+    /// Registration is requested for the email which already has an account.
+    /// Sent instead of confirmation code, so response does not reveal registered emails
+    AccountAlreadyExists,
+    /// Too many failed logins, code allows to unlock the account before lockout ends
+    AccountLocked {
+        code: String,
+    },
 }
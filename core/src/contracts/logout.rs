@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::models::Application;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogoutNotifyError {
+    #[error("Reqwest error: {0}")]
+    ReqwestError(#[from] reqwest::Error),
+    #[error("Could not sign logout token: {0}")]
+    JwtError(#[from] jsonwebtoken::errors::Error),
+    #[error("Application has no back-channel logout uri")]
+    NotConfigured,
+    #[error("Application responded with status {0}")]
+    Rejected(u16),
+}
+
+/// Delivers logout token to the application
+/// https://openid.net/specs/openid-connect-backchannel-1_0.html#BCRequest
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait LogoutNotifier: Send + Sync {
+    async fn notify(
+        &self,
+        application: &Application,
+        user_id: uuid::Uuid,
+        session_id: Option<uuid::Uuid>,
+    ) -> Result<(), LogoutNotifyError>;
+}
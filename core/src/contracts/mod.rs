@@ -1,8 +1,10 @@
 pub use emailer::*;
+pub use logout::*;
 pub use repo::*;
 pub use secure::*;
 
 pub mod emailer;
+pub mod logout;
 pub mod repo;
 pub mod secure;
 
@@ -10,6 +12,14 @@ pub trait Repository:
     AccessTokenRepo
     + AuthCodeRepo
     + ApplicationRepo
+    + DPoPProofRepo
+    + EmailDomainRuleRepo
+    + EmailEventRepo
+    + EmailOutboxRepo
+    + InviteRepo
+    + LoginAttemptRepo
+    + LogoutNotificationRepo
+    + PersonalAccessTokenRepo
     + RequestsRepo
     + SessionRepo
     + UserRegistrationsRepo
@@ -23,6 +33,14 @@ impl<T> Repository for T where
     T: AccessTokenRepo
         + AuthCodeRepo
         + ApplicationRepo
+        + DPoPProofRepo
+        + EmailDomainRuleRepo
+        + EmailEventRepo
+        + EmailOutboxRepo
+        + InviteRepo
+        + LoginAttemptRepo
+        + LogoutNotificationRepo
+        + PersonalAccessTokenRepo
         + RequestsRepo
         + SessionRepo
         + UserRegistrationsRepo
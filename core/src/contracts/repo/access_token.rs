@@ -24,6 +24,13 @@ pub trait AccessTokenRepo {
         &self,
         user_id: uuid::Uuid,
     ) -> Result<u64, UnexpectedDatabaseError>;
+
+    /// Deletes at most `limit` access tokens expired before `before`
+    async fn access_tokens_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
 }
 
 #[cfg(feature = "testing")]
@@ -57,4 +64,14 @@ impl AccessTokenRepo for crate::contracts::MockDb {
             .access_tokens_delete_all_for_user(user_id)
             .await
     }
+
+    async fn access_tokens_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.access_token
+            .access_tokens_delete_expired(before, limit)
+            .await
+    }
 }
@@ -4,7 +4,7 @@ use mockall::*;
 use uuid::Uuid;
 
 use super::UnexpectedDatabaseError;
-use crate::models::Application;
+use crate::models::{Application, RedirectUriError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ApplicationCreateError {
@@ -22,6 +22,30 @@ pub struct ApplicationForm {
     /// Should be generated by call-side
     pub secret_key: String,
     pub allowed_registrations: bool,
+    pub post_logout_redirect_uri: Vec<String>,
+    pub backchannel_logout_uri: Option<String>,
+    pub require_dpop: bool,
+}
+
+impl ApplicationForm {
+    /// Check every redirect URI against [`Application::check_redirect_uri`] policy
+    pub fn validate_redirect_uri(&self) -> Result<(), (String, RedirectUriError)> {
+        self.redirect_uri
+            .iter()
+            .chain(self.post_logout_redirect_uri.iter())
+            .try_for_each(|uri| {
+                Application::check_redirect_uri(uri, self.is_dev)
+                    .map(|_| ())
+                    .map_err(|error| (uri.clone(), error))
+            })?;
+
+        if let Some(uri) = &self.backchannel_logout_uri {
+            Application::check_backchannel_logout_uri(uri, self.is_dev)
+                .map_err(|error| (uri.clone(), error))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl From<Application> for ApplicationForm {
@@ -32,6 +56,9 @@ impl From<Application> for ApplicationForm {
             title: app.title,
             secret_key: app.secret_key,
             allowed_registrations: app.allowed_registrations,
+            post_logout_redirect_uri: app.post_logout_redirect_uri,
+            backchannel_logout_uri: app.backchannel_logout_uri,
+            require_dpop: app.require_dpop,
         }
     }
 }
@@ -17,6 +17,13 @@ pub trait AuthCodeRepo {
         &self,
         code: String,
     ) -> Result<Option<AuthorizationCode>, UnexpectedDatabaseError>;
+
+    /// Deletes at most `limit` authorization codes created before `before`
+    async fn auth_codes_delete_created_before(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
 }
 
 #[cfg(feature = "testing")]
@@ -35,4 +42,14 @@ impl AuthCodeRepo for crate::contracts::MockDb {
     ) -> Result<Option<AuthorizationCode>, UnexpectedDatabaseError> {
         self.auth_code.auth_code_read(code).await
     }
+
+    async fn auth_codes_delete_created_before(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.auth_code
+            .auth_codes_delete_created_before(before, limit)
+            .await
+    }
 }
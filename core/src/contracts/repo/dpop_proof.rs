@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait DPoPProofRepo {
+    /// Stores `jti` of the proof signed by the key.
+    /// Returns `false` if the key used such `jti` already
+    async fn dpop_proof_use(
+        &self,
+        jkt: String,
+        jti: String,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, UnexpectedDatabaseError>;
+
+    async fn dpop_proofs_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl DPoPProofRepo for crate::contracts::MockDb {
+    async fn dpop_proof_use(
+        &self,
+        jkt: String,
+        jti: String,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        self.dpop_proof.dpop_proof_use(jkt, jti, expires_at).await
+    }
+
+    async fn dpop_proofs_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.dpop_proof
+            .dpop_proofs_delete_expired(before, limit)
+            .await
+    }
+}
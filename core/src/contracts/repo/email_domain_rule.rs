@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+use crate::models::EmailDomainRule;
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait EmailDomainRuleRepo {
+    async fn email_domain_rules_list(
+        &self,
+    ) -> Result<Vec<EmailDomainRule>, UnexpectedDatabaseError>;
+
+    /// Global rules and rules of the application, if it is set
+    async fn email_domain_rules_for(
+        &self,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<EmailDomainRule>, UnexpectedDatabaseError>;
+
+    /// Returns `None` if the same rule already exists
+    async fn email_domain_rule_create(
+        &self,
+        rule: EmailDomainRule,
+    ) -> Result<Option<EmailDomainRule>, UnexpectedDatabaseError>;
+
+    async fn email_domain_rule_delete(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<EmailDomainRule>, UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl EmailDomainRuleRepo for crate::contracts::MockDb {
+    async fn email_domain_rules_list(
+        &self,
+    ) -> Result<Vec<EmailDomainRule>, UnexpectedDatabaseError> {
+        self.email_domain_rule.email_domain_rules_list().await
+    }
+
+    async fn email_domain_rules_for(
+        &self,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<EmailDomainRule>, UnexpectedDatabaseError> {
+        self.email_domain_rule
+            .email_domain_rules_for(application_id)
+            .await
+    }
+
+    async fn email_domain_rule_create(
+        &self,
+        rule: EmailDomainRule,
+    ) -> Result<Option<EmailDomainRule>, UnexpectedDatabaseError> {
+        self.email_domain_rule.email_domain_rule_create(rule).await
+    }
+
+    async fn email_domain_rule_delete(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<EmailDomainRule>, UnexpectedDatabaseError> {
+        self.email_domain_rule.email_domain_rule_delete(id).await
+    }
+}
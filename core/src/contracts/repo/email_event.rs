@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+use crate::models::{EmailEvent, EmailStatus};
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait EmailEventRepo {
+    /// Stores the event, suppresses the mailbox and sets its status to users and register requests.
+    /// Returns false if the event is already recorded
+    async fn email_event_record(&self, event: EmailEvent) -> Result<bool, UnexpectedDatabaseError>;
+
+    /// Bounced or complained status of the mailbox, kept until reset
+    async fn email_status_get(&self, email: String)
+        -> Result<EmailStatus, UnexpectedDatabaseError>;
+
+    /// Latest events first
+    async fn email_events_list(
+        &self,
+        email: String,
+    ) -> Result<Vec<EmailEvent>, UnexpectedDatabaseError>;
+
+    /// Makes the mailbox deliverable again, events are kept
+    async fn email_status_reset(&self, email: String) -> Result<(), UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl EmailEventRepo for crate::contracts::MockDb {
+    async fn email_event_record(&self, event: EmailEvent) -> Result<bool, UnexpectedDatabaseError> {
+        self.email_event.email_event_record(event).await
+    }
+
+    async fn email_status_get(
+        &self,
+        email: String,
+    ) -> Result<EmailStatus, UnexpectedDatabaseError> {
+        self.email_event.email_status_get(email).await
+    }
+
+    async fn email_events_list(
+        &self,
+        email: String,
+    ) -> Result<Vec<EmailEvent>, UnexpectedDatabaseError> {
+        self.email_event.email_events_list(email).await
+    }
+
+    async fn email_status_reset(&self, email: String) -> Result<(), UnexpectedDatabaseError> {
+        self.email_event.email_status_reset(email).await
+    }
+}
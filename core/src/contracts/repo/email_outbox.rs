@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+use crate::models::{OutboxEmail, OutboxEmailForm};
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait EmailOutboxRepo {
+    /// Returns false if email with the same idempotency key is already queued
+    async fn email_outbox_enqueue(
+        &self,
+        email: OutboxEmailForm,
+    ) -> Result<bool, UnexpectedDatabaseError>;
+
+    /// Emails which attempt time has come. Next attempt of each is moved to
+    /// `locked_until`, so parallel dispatchers do not send them again
+    async fn email_outbox_claim_pending(
+        &self,
+        limit: i64,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<OutboxEmail>, UnexpectedDatabaseError>;
+
+    async fn email_outbox_mark_sent(&self, id: uuid::Uuid) -> Result<(), UnexpectedDatabaseError>;
+
+    /// Increments attempts counter and postpones the next attempt.
+    /// Without next attempt the email is moved to dead letters
+    async fn email_outbox_mark_failed(
+        &self,
+        id: uuid::Uuid,
+        error: String,
+        next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), UnexpectedDatabaseError>;
+
+    /// Deletes at most `limit` emails sent before `before`
+    async fn email_outbox_delete_sent(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl EmailOutboxRepo for crate::contracts::MockDb {
+    async fn email_outbox_enqueue(
+        &self,
+        email: OutboxEmailForm,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        self.email_outbox.email_outbox_enqueue(email).await
+    }
+
+    async fn email_outbox_claim_pending(
+        &self,
+        limit: i64,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<OutboxEmail>, UnexpectedDatabaseError> {
+        self.email_outbox
+            .email_outbox_claim_pending(limit, locked_until)
+            .await
+    }
+
+    async fn email_outbox_mark_sent(&self, id: uuid::Uuid) -> Result<(), UnexpectedDatabaseError> {
+        self.email_outbox.email_outbox_mark_sent(id).await
+    }
+
+    async fn email_outbox_mark_failed(
+        &self,
+        id: uuid::Uuid,
+        error: String,
+        next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.email_outbox
+            .email_outbox_mark_failed(id, error, next_attempt_at)
+            .await
+    }
+
+    async fn email_outbox_delete_sent(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.email_outbox
+            .email_outbox_delete_sent(before, limit)
+            .await
+    }
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+use crate::models::ApplicationInvite;
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait InviteRepo {
+    /// Returns `None` if such invite already exists
+    async fn invite_create(
+        &self,
+        invite: String,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Option<ApplicationInvite>, UnexpectedDatabaseError>;
+
+    async fn invite_get(
+        &self,
+        invite: String,
+    ) -> Result<Option<ApplicationInvite>, UnexpectedDatabaseError>;
+
+    /// Lists all invites if `application_id` is `None`
+    async fn invites_list(
+        &self,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<ApplicationInvite>, UnexpectedDatabaseError>;
+
+    async fn invite_delete(
+        &self,
+        invite: String,
+    ) -> Result<Option<ApplicationInvite>, UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl InviteRepo for crate::contracts::MockDb {
+    async fn invite_create(
+        &self,
+        invite: String,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Option<ApplicationInvite>, UnexpectedDatabaseError> {
+        self.invite.invite_create(invite, application_id).await
+    }
+
+    async fn invite_get(
+        &self,
+        invite: String,
+    ) -> Result<Option<ApplicationInvite>, UnexpectedDatabaseError> {
+        self.invite.invite_get(invite).await
+    }
+
+    async fn invites_list(
+        &self,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<ApplicationInvite>, UnexpectedDatabaseError> {
+        self.invite.invites_list(application_id).await
+    }
+
+    async fn invite_delete(
+        &self,
+        invite: String,
+    ) -> Result<Option<ApplicationInvite>, UnexpectedDatabaseError> {
+        self.invite.invite_delete(invite).await
+    }
+}
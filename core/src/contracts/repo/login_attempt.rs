@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+use crate::models::{LoginAttemptKind, LoginAttempts};
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait LoginAttemptRepo {
+    async fn login_attempts_get(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+    ) -> Result<Option<LoginAttempts>, UnexpectedDatabaseError>;
+
+    /// Increments failures counter.
+    /// Counter starts from scratch if last failure was before `reset_before` or lockout is over
+    async fn login_attempt_register_failure(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+        reset_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<LoginAttempts, UnexpectedDatabaseError>;
+
+    async fn login_attempt_lock(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+        locked_until: chrono::DateTime<chrono::Utc>,
+        unlock_code: Option<String>,
+    ) -> Result<(), UnexpectedDatabaseError>;
+
+    async fn login_attempts_clear(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+    ) -> Result<(), UnexpectedDatabaseError>;
+
+    /// Returns `false` if no locked account has such code
+    async fn login_attempts_unlock_by_code(
+        &self,
+        unlock_code: String,
+    ) -> Result<bool, UnexpectedDatabaseError>;
+
+    /// Deletes counters without failures and lockout since `before`
+    async fn login_attempts_delete_stale(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl LoginAttemptRepo for crate::contracts::MockDb {
+    async fn login_attempts_get(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+    ) -> Result<Option<LoginAttempts>, UnexpectedDatabaseError> {
+        self.login_attempt.login_attempts_get(kind, key).await
+    }
+
+    async fn login_attempt_register_failure(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+        reset_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<LoginAttempts, UnexpectedDatabaseError> {
+        self.login_attempt
+            .login_attempt_register_failure(kind, key, reset_before)
+            .await
+    }
+
+    async fn login_attempt_lock(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+        locked_until: chrono::DateTime<chrono::Utc>,
+        unlock_code: Option<String>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.login_attempt
+            .login_attempt_lock(kind, key, locked_until, unlock_code)
+            .await
+    }
+
+    async fn login_attempts_clear(
+        &self,
+        kind: LoginAttemptKind,
+        key: String,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.login_attempt.login_attempts_clear(kind, key).await
+    }
+
+    async fn login_attempts_unlock_by_code(
+        &self,
+        unlock_code: String,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        self.login_attempt
+            .login_attempts_unlock_by_code(unlock_code)
+            .await
+    }
+
+    async fn login_attempts_delete_stale(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.login_attempt
+            .login_attempts_delete_stale(before, limit)
+            .await
+    }
+}
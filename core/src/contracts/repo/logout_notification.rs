@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::UnexpectedDatabaseError;
+use crate::models::LogoutNotification;
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait LogoutNotificationRepo {
+    /// Enqueue notification for each application with back-channel logout
+    /// where user is registered in
+    async fn logout_notifications_create_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+
+    /// Enqueue notification with `sid` for each application with back-channel logout
+    /// authorized within the session. Must be called before the session is deleted
+    async fn logout_notifications_create_for_session(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+
+    /// Not delivered notifications which attempt time has come.
+    /// Claimed ones are postponed to `locked_until`, so concurrent workers skip them
+    async fn logout_notifications_claim_pending(
+        &self,
+        limit: i64,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<LogoutNotification>, UnexpectedDatabaseError>;
+
+    async fn logout_notification_mark_delivered(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError>;
+
+    /// Increments attempts counter and postpones the next attempt
+    async fn logout_notification_mark_failed(
+        &self,
+        id: uuid::Uuid,
+        next_attempt_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), UnexpectedDatabaseError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl LogoutNotificationRepo for crate::contracts::MockDb {
+    async fn logout_notifications_create_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.logout_notification
+            .logout_notifications_create_for_user(user_id)
+            .await
+    }
+
+    async fn logout_notifications_create_for_session(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.logout_notification
+            .logout_notifications_create_for_session(user_id, session_id)
+            .await
+    }
+
+    async fn logout_notifications_claim_pending(
+        &self,
+        limit: i64,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<LogoutNotification>, UnexpectedDatabaseError> {
+        self.logout_notification
+            .logout_notifications_claim_pending(limit, locked_until)
+            .await
+    }
+
+    async fn logout_notification_mark_delivered(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.logout_notification
+            .logout_notification_mark_delivered(id)
+            .await
+    }
+
+    async fn logout_notification_mark_failed(
+        &self,
+        id: uuid::Uuid,
+        next_attempt_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.logout_notification
+            .logout_notification_mark_failed(id, next_attempt_at)
+            .await
+    }
+}
@@ -1,6 +1,14 @@
 pub use access_token::*;
 pub use application::*;
 pub use auth_code::*;
+pub use dpop_proof::*;
+pub use email_domain_rule::*;
+pub use email_event::*;
+pub use email_outbox::*;
+pub use invite::*;
+pub use login_attempt::*;
+pub use logout_notification::*;
+pub use personal_access_token::*;
 pub use requests::*;
 pub use session::*;
 pub use user::*;
@@ -9,6 +17,14 @@ pub use user_registration::*;
 mod access_token;
 mod application;
 mod auth_code;
+mod dpop_proof;
+mod email_domain_rule;
+mod email_event;
+mod email_outbox;
+mod invite;
+mod login_attempt;
+mod logout_notification;
+mod personal_access_token;
 mod requests;
 mod session;
 mod user;
@@ -26,9 +42,17 @@ pub struct MockDb {
     pub requests: MockRequestsRepo,
     pub session: MockSessionRepo,
     pub auth_code: MockAuthCodeRepo,
+    pub dpop_proof: MockDPoPProofRepo,
     pub application: MockApplicationRepo,
     pub access_token: MockAccessTokenRepo,
     pub user_registrations: MockUserRegistrationsRepo,
+    pub logout_notification: MockLogoutNotificationRepo,
+    pub personal_access_token: MockPersonalAccessTokenRepo,
+    pub login_attempt: MockLoginAttemptRepo,
+    pub invite: MockInviteRepo,
+    pub email_domain_rule: MockEmailDomainRuleRepo,
+    pub email_outbox: MockEmailOutboxRepo,
+    pub email_event: MockEmailEventRepo,
 }
 
 #[cfg(feature = "testing")]
@@ -47,8 +71,16 @@ impl MockDb {
             session: MockSessionRepo::new(),
             access_token: MockAccessTokenRepo::new(),
             auth_code: MockAuthCodeRepo::new(),
+            dpop_proof: MockDPoPProofRepo::new(),
             application: MockApplicationRepo::new(),
             user_registrations: MockUserRegistrationsRepo::new(),
+            logout_notification: MockLogoutNotificationRepo::new(),
+            personal_access_token: MockPersonalAccessTokenRepo::new(),
+            login_attempt: MockLoginAttemptRepo::new(),
+            invite: MockInviteRepo::new(),
+            email_domain_rule: MockEmailDomainRuleRepo::new(),
+            email_outbox: MockEmailOutboxRepo::new(),
+            email_event: MockEmailEventRepo::new(),
         }
     }
 }
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+#[cfg(feature = "testing")]
+use mockall::*;
+
+use crate::contracts::{GetUserBySessionError, UnexpectedDatabaseError};
+use crate::models::{PersonalAccessToken, User};
+
+#[cfg_attr(feature = "testing", automock)]
+#[async_trait]
+pub trait PersonalAccessTokenRepo {
+    async fn personal_access_token_create(
+        &self,
+        token: PersonalAccessToken,
+    ) -> Result<PersonalAccessToken, UnexpectedDatabaseError>;
+
+    async fn personal_access_tokens_list_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<PersonalAccessToken>, UnexpectedDatabaseError>;
+
+    /// Returns `false` if user has no token with such id
+    async fn personal_access_token_delete(
+        &self,
+        user_id: uuid::Uuid,
+        token_id: uuid::Uuid,
+    ) -> Result<bool, UnexpectedDatabaseError>;
+
+    /// Finds not expired token and marks it as used, unless recently marked already
+    async fn get_user_by_personal_access_token(
+        &self,
+        token: String,
+    ) -> Result<(PersonalAccessToken, User), GetUserBySessionError>;
+}
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl PersonalAccessTokenRepo for crate::contracts::MockDb {
+    async fn personal_access_token_create(
+        &self,
+        token: PersonalAccessToken,
+    ) -> Result<PersonalAccessToken, UnexpectedDatabaseError> {
+        self.personal_access_token
+            .personal_access_token_create(token)
+            .await
+    }
+
+    async fn personal_access_tokens_list_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<PersonalAccessToken>, UnexpectedDatabaseError> {
+        self.personal_access_token
+            .personal_access_tokens_list_for_user(user_id)
+            .await
+    }
+
+    async fn personal_access_token_delete(
+        &self,
+        user_id: uuid::Uuid,
+        token_id: uuid::Uuid,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        self.personal_access_token
+            .personal_access_token_delete(user_id, token_id)
+            .await
+    }
+
+    async fn get_user_by_personal_access_token(
+        &self,
+        token: String,
+    ) -> Result<(PersonalAccessToken, User), GetUserBySessionError> {
+        self.personal_access_token
+            .get_user_by_personal_access_token(token)
+            .await
+    }
+}
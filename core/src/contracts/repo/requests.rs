@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use mockall::*;
 
 use crate::contracts::UnexpectedDatabaseError;
-use crate::models::RegisterRequest;
+use crate::models::{OutboxEmailForm, RegisterRequest};
 
 #[derive(Debug, thiserror::Error)]
 pub enum SaveRegisterRequestError {
@@ -16,23 +16,59 @@ pub enum SaveRegisterRequestError {
 #[cfg_attr(feature = "testing", automock)]
 #[async_trait]
 pub trait RequestsRepo {
+    /// Email is queued in the same transaction
     async fn register_request_save(
         &self,
         request: RegisterRequest,
+        email: Option<OutboxEmailForm>,
     ) -> Result<RegisterRequest, SaveRegisterRequestError>;
 
-    /// Find actual register request by its code
-    async fn register_request_get_by_code(
+    /// Find actual register request by email and its code
+    async fn register_request_get_by_email_and_code(
         &self,
+        email: String,
         code: String,
     ) -> Result<Option<RegisterRequest>, UnexpectedDatabaseError>;
 
+    /// Latest requests of the mailbox first
     async fn register_requests_get_by_email(
         &self,
         email: String,
         count: u16,
     ) -> Result<Vec<RegisterRequest>, UnexpectedDatabaseError>;
 
+    /// Wrong codes entered for the mailbox since `since`
+    async fn register_failures_count(
+        &self,
+        email: String,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<i32, UnexpectedDatabaseError>;
+
+    /// Counts wrong code for the mailbox, failures before `since` are forgotten.
+    /// Nothing is counted while the mailbox has no pending requests.
+    /// With `max_attempts` failures requests of the mailbox are deleted, returns count of them
+    async fn register_requests_register_failure(
+        &self,
+        email: String,
+        max_attempts: i32,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+
+    /// Deletes requests of the mailbox except `count` latest ones
+    async fn register_requests_keep_latest(
+        &self,
+        email: String,
+        count: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+
+    /// Email is queued in the same transaction
+    async fn register_request_mark_sent(
+        &self,
+        code: String,
+        email: OutboxEmailForm,
+    ) -> Result<(), UnexpectedDatabaseError>;
+
+    /// Deletes requests of the mailbox, including other spellings of the email
     async fn register_requests_delete_all_for_email(
         &self,
         email: String,
@@ -50,6 +86,20 @@ pub trait RequestsRepo {
         &self,
         code: String,
     ) -> Result<Option<RegisterRequest>, UnexpectedDatabaseError>;
+
+    /// Deletes at most `limit` register requests expired before `before`
+    async fn register_requests_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+
+    /// Deletes at most `limit` failure counters which window started before `before`
+    async fn register_failures_delete_stale(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
 }
 
 #[cfg(feature = "testing")]
@@ -58,16 +108,19 @@ impl RequestsRepo for crate::contracts::MockDb {
     async fn register_request_save(
         &self,
         request: RegisterRequest,
+        email: Option<OutboxEmailForm>,
     ) -> Result<RegisterRequest, SaveRegisterRequestError> {
-        self.requests.register_request_save(request).await
+        self.requests.register_request_save(request, email).await
     }
 
-    /// Find actual register request by its code
-    async fn register_request_get_by_code(
+    async fn register_request_get_by_email_and_code(
         &self,
+        email: String,
         code: String,
     ) -> Result<Option<RegisterRequest>, UnexpectedDatabaseError> {
-        self.requests.register_request_get_by_code(code).await
+        self.requests
+            .register_request_get_by_email_and_code(email, code)
+            .await
     }
 
     async fn register_requests_get_by_email(
@@ -80,6 +133,43 @@ impl RequestsRepo for crate::contracts::MockDb {
             .await
     }
 
+    async fn register_failures_count(
+        &self,
+        email: String,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<i32, UnexpectedDatabaseError> {
+        self.requests.register_failures_count(email, since).await
+    }
+
+    async fn register_requests_register_failure(
+        &self,
+        email: String,
+        max_attempts: i32,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.requests
+            .register_requests_register_failure(email, max_attempts, since)
+            .await
+    }
+
+    async fn register_requests_keep_latest(
+        &self,
+        email: String,
+        count: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.requests
+            .register_requests_keep_latest(email, count)
+            .await
+    }
+
+    async fn register_request_mark_sent(
+        &self,
+        code: String,
+        email: OutboxEmailForm,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.requests.register_request_mark_sent(code, email).await
+    }
+
     async fn register_requests_delete_all_for_email(
         &self,
         email: String,
@@ -107,4 +197,24 @@ impl RequestsRepo for crate::contracts::MockDb {
     ) -> Result<Option<RegisterRequest>, UnexpectedDatabaseError> {
         self.requests.register_request_delete_by_code(code).await
     }
+
+    async fn register_requests_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.requests
+            .register_requests_delete_expired(before, limit)
+            .await
+    }
+
+    async fn register_failures_delete_stale(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.requests
+            .register_failures_delete_stale(before, limit)
+            .await
+    }
 }
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use mockall::*;
 
 use crate::contracts::UnexpectedDatabaseError;
-use crate::models::{SessionToken, User};
+use crate::models::{AccessToken, SessionToken, User};
 
 #[derive(Debug, thiserror::Error)]
 pub enum GetUserBySessionError {
@@ -26,9 +26,14 @@ pub enum SessionCreateError {
 #[cfg_attr(feature = "testing", automock)]
 #[async_trait]
 pub trait SessionRepo {
-    async fn get_user_by_session_token(&self, token: String)
-        -> Result<User, GetUserBySessionError>;
-    async fn get_user_by_access_token(&self, token: String) -> Result<User, GetUserBySessionError>;
+    async fn get_user_by_session_token(
+        &self,
+        token: String,
+    ) -> Result<(SessionToken, User), GetUserBySessionError>;
+    async fn get_user_by_access_token(
+        &self,
+        token: String,
+    ) -> Result<(AccessToken, User), GetUserBySessionError>;
     async fn session_create(
         &self,
         session: SessionToken,
@@ -41,6 +46,53 @@ pub trait SessionRepo {
         &self,
         user_id: uuid::Uuid,
     ) -> Result<(), UnexpectedDatabaseError>;
+    /// Returns `false` if user has no session with such id
+    async fn session_delete_by_id(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+    ) -> Result<bool, UnexpectedDatabaseError>;
+    /// Not expired sessions of the user, recently used first
+    async fn sessions_list_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<SessionToken>, UnexpectedDatabaseError>;
+    async fn session_mark_seen(
+        &self,
+        session_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError>;
+    /// Moves expiration forward and marks session as seen
+    async fn session_extend(
+        &self,
+        session_id: uuid::Uuid,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), UnexpectedDatabaseError>;
+    /// Deletes at most `limit` sessions expired before `before`
+    async fn sessions_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError>;
+    /// Remembers that the application requires the user to sign in again
+    async fn session_reauthentication_request(
+        &self,
+        user_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError>;
+    /// Deletes the pending reauthentication, returns when it was requested if not before `since`
+    async fn session_reauthentication_take(
+        &self,
+        user_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, UnexpectedDatabaseError>;
+    /// Remembers that the application was authorized within the session,
+    /// so it is notified when the session ends
+    async fn session_client_add(
+        &self,
+        session_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError>;
 }
 
 #[cfg(feature = "testing")]
@@ -49,10 +101,13 @@ impl SessionRepo for crate::contracts::MockDb {
     async fn get_user_by_session_token(
         &self,
         token: String,
-    ) -> Result<User, GetUserBySessionError> {
+    ) -> Result<(SessionToken, User), GetUserBySessionError> {
         self.session.get_user_by_session_token(token).await
     }
-    async fn get_user_by_access_token(&self, token: String) -> Result<User, GetUserBySessionError> {
+    async fn get_user_by_access_token(
+        &self,
+        token: String,
+    ) -> Result<(AccessToken, User), GetUserBySessionError> {
         self.session.get_user_by_access_token(token).await
     }
     async fn session_create(
@@ -74,4 +129,66 @@ impl SessionRepo for crate::contracts::MockDb {
     ) -> Result<(), UnexpectedDatabaseError> {
         self.session.session_delete_by_user_id(user_id).await
     }
+    async fn session_delete_by_id(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        self.session.session_delete_by_id(user_id, session_id).await
+    }
+    async fn sessions_list_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<SessionToken>, UnexpectedDatabaseError> {
+        self.session.sessions_list_for_user(user_id).await
+    }
+    async fn session_mark_seen(
+        &self,
+        session_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.session.session_mark_seen(session_id).await
+    }
+    async fn session_extend(
+        &self,
+        session_id: uuid::Uuid,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.session.session_extend(session_id, expires_at).await
+    }
+    async fn sessions_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        self.session.sessions_delete_expired(before, limit).await
+    }
+
+    async fn session_reauthentication_request(
+        &self,
+        user_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.session
+            .session_reauthentication_request(user_id, client_id)
+            .await
+    }
+
+    async fn session_reauthentication_take(
+        &self,
+        user_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, UnexpectedDatabaseError> {
+        self.session
+            .session_reauthentication_take(user_id, client_id, since)
+            .await
+    }
+
+    async fn session_client_add(
+        &self,
+        session_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.session.session_client_add(session_id, client_id).await
+    }
 }
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use mockall::*;
 
 use crate::contracts::UnexpectedDatabaseError;
-use crate::models::User;
+use crate::models::{OutboxEmailForm, User};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UserRegisterForm {
@@ -12,12 +12,17 @@ pub struct UserRegisterForm {
     pub password_hash: String,
     pub first_name: String,
     pub last_name: String,
+    pub locale: String,
+    /// Confirmation code was received on the email
+    pub email_verified: bool,
+    /// Claimed for the user in the same transaction, registration fails if it is already used
+    pub invite: Option<String>,
 }
 
+/// Password is verified by the caller against the hash of the found user
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UserCredentials {
     pub email: String,
-    pub password_hash: String,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -26,6 +31,8 @@ pub enum RegisterUserError {
     Unexpected(#[from] eyre::Report),
     #[error("Email already exists")]
     EmailAlreadyExists,
+    #[error("Invite not found or already used")]
+    InviteAlreadyUsed,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +40,7 @@ pub struct UserEditForm {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub email: Option<String>,
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -40,6 +48,9 @@ pub enum UserEditError {
     #[error("User not found")]
     UserNotFound,
 
+    #[error("Email already exists")]
+    EmailAlreadyExists,
+
     #[error(transparent)]
     Unexpected(#[from] eyre::Report),
 }
@@ -56,7 +67,12 @@ impl From<UnexpectedDatabaseError> for UserEditError {
 #[async_trait]
 pub trait UserRepo {
     async fn user_has_with_email(&self, email: String) -> Result<bool, UnexpectedDatabaseError>;
-    async fn user_register(&self, form: UserRegisterForm) -> Result<User, RegisterUserError>;
+    /// Email is queued in the same transaction
+    async fn user_register(
+        &self,
+        form: UserRegisterForm,
+        email: Option<OutboxEmailForm>,
+    ) -> Result<User, RegisterUserError>;
     async fn user_find_by_credentials(
         &self,
         creds: UserCredentials,
@@ -82,6 +98,12 @@ pub trait UserRepo {
         user_id: uuid::Uuid,
         new_password: String,
     ) -> Result<Option<User>, UnexpectedDatabaseError>;
+
+    async fn user_set_canonical_email(
+        &self,
+        user_id: uuid::Uuid,
+        canonical_email: String,
+    ) -> Result<(), UnexpectedDatabaseError>;
 }
 
 #[cfg(feature = "testing")]
@@ -90,8 +112,12 @@ impl UserRepo for crate::contracts::MockDb {
     async fn user_has_with_email(&self, email: String) -> Result<bool, UnexpectedDatabaseError> {
         self.users.user_has_with_email(email).await
     }
-    async fn user_register(&self, form: UserRegisterForm) -> Result<User, RegisterUserError> {
-        self.users.user_register(form).await
+    async fn user_register(
+        &self,
+        form: UserRegisterForm,
+        email: Option<OutboxEmailForm>,
+    ) -> Result<User, RegisterUserError> {
+        self.users.user_register(form, email).await
     }
     async fn user_find_by_credentials(
         &self,
@@ -137,4 +163,14 @@ impl UserRepo for crate::contracts::MockDb {
     ) -> Result<Option<User>, UnexpectedDatabaseError> {
         self.users.user_password_reset(user_id, new_password).await
     }
+
+    async fn user_set_canonical_email(
+        &self,
+        user_id: uuid::Uuid,
+        canonical_email: String,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        self.users
+            .user_set_canonical_email(user_id, canonical_email)
+            .await
+    }
 }
@@ -1,7 +1,15 @@
+use async_trait::async_trait;
 #[cfg(feature = "testing")]
 use mockall::*;
 
+#[derive(Debug, thiserror::Error)]
+pub enum HashingError {
+    #[error(transparent)]
+    Unexpected(#[from] eyre::Report),
+}
+
 #[cfg_attr(feature = "testing", automock)]
+#[async_trait]
 pub trait SecureGenerator: Send + Sync {
     fn secure_words(&self, length: u8) -> String;
     fn confirmation_code(&self) -> String {
@@ -11,6 +19,19 @@ pub trait SecureGenerator: Send + Sync {
     fn generate_token(&self) -> String;
     fn generate_token_long(&self) -> String;
 
-    fn password_hash(&self, password: String) -> (String, Vec<u8>);
-    fn verify_hash(&self, hash: &[u8], password: &str) -> bool;
+    /// Hashing runs on blocking threads, not on the async executor.
+    /// Count of passwords hashed at the same time is limited, others wait in the queue.
+    /// Hash is a PHC string: `$argon2id$v=19$m=65536,t=2,p=1$<salt>$<hash>`
+    async fn password_hash(&self, password: String) -> Result<String, HashingError>;
+    async fn verify_hash(&self, hash: &str, password: &str) -> Result<bool, HashingError>;
+
+    /// Hash is made with other algorithm or cost than new hashes are
+    fn hash_needs_upgrade(&self, hash: &str) -> bool;
+
+    /// Hash can be verified: PHC string of argon2, scrypt, PBKDF2 or bcrypt hash
+    fn hash_is_supported(&self, hash: &str) -> bool;
+
+    /// Hash of a random password, verified when user is not found.
+    /// Login of unknown email takes the same time as login of existing one
+    fn dummy_hash(&self) -> &str;
 }
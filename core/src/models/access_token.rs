@@ -6,11 +6,60 @@ pub struct AccessToken {
     pub scopes: Vec<String>,
     pub expires_at: chrono::DateTime<Utc>,
     pub registration_id: uuid::Uuid,
+    /// JWK thumbprint of the DPoP key, token can be used only with proof signed by this key
+    pub dpop_jkt: Option<String>,
 }
 
+/// Allows to read first and last name of the user
+pub const SCOPE_PROFILE: &str = "profile";
+
+/// Allows to read email of the user
+pub const SCOPE_EMAIL: &str = "email";
+
+/// All scopes a token can be granted
+pub const SCOPES: &[&str] = &[SCOPE_PROFILE, SCOPE_EMAIL];
+
 impl AccessToken {
     /// https://www.oauth.com/oauth2-servers/access-tokens/access-token-lifetime/
     pub fn lifetime() -> chrono::Duration {
         chrono::Duration::days(1)
     }
+
+    /// Token requested without any scope, as all tokens issued before scopes were,
+    /// reads the profile like `/viewer.get` always allowed
+    pub fn granted_scopes(&self) -> Vec<String> {
+        if self.scopes.is_empty() {
+            vec![SCOPE_PROFILE.to_owned()]
+        } else {
+            self.scopes.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(scopes: &[&str]) -> AccessToken {
+        AccessToken {
+            token: "token".to_owned(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_at: Utc::now() + AccessToken::lifetime(),
+            registration_id: uuid::Uuid::new_v4(),
+            dpop_jkt: None,
+        }
+    }
+
+    #[test]
+    fn token_without_scopes_reads_profile() {
+        assert_eq!(token(&[]).granted_scopes(), vec![SCOPE_PROFILE.to_owned()]);
+    }
+
+    #[test]
+    fn token_with_scopes_gets_only_them() {
+        assert_eq!(
+            token(&[SCOPE_EMAIL]).granted_scopes(),
+            vec![SCOPE_EMAIL.to_owned()]
+        );
+    }
 }
@@ -1,4 +1,15 @@
 use chrono::Utc;
+use url::{Host, Url};
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RedirectUriError {
+    #[error("Redirect URI is not a valid absolute URL")]
+    Malformed,
+    #[error("Redirect URI must not contain a fragment")]
+    HasFragment,
+    #[error("Redirect URI must use https, loopback http or a private-use scheme")]
+    InsecureScheme,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Application {
@@ -8,6 +19,12 @@ pub struct Application {
     pub title: String,
     pub secret_key: String,
     pub allowed_registrations: bool,
+    /// Where user can be redirected after logout initiated by the application
+    pub post_logout_redirect_uri: Vec<String>,
+    /// Receives logout tokens when user logs out from accesso
+    pub backchannel_logout_uri: Option<String>,
+    /// Access tokens can be issued only bound to DPoP key
+    pub require_dpop: bool,
 }
 
 impl Application {
@@ -18,9 +35,68 @@ impl Application {
 
     /// https://www.oauth.com/oauth2-servers/redirect-uris/redirect-uri-registration/
     /// https://www.oauth.com/oauth2-servers/redirect-uris/redirect-uri-validation/
-    /// The server should reject any authorization requests with redirect URLs that are not an exact match of a registered URL.
+    /// The server should reject any authorization requests with redirect URLs that are not a match of a registered URL.
+    /// Loopback redirects of native apps match on any port (RFC 8252, section 7.3).
     pub fn is_allowed_redirect(&self, redirect_uri: &str) -> bool {
-        self.redirect_uri.iter().any(|uri| uri == redirect_uri)
+        let requested = match Self::check_redirect_uri(redirect_uri, self.is_dev) {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+
+        self.redirect_uri
+            .iter()
+            .filter_map(|uri| Url::parse(uri).ok())
+            .any(|registered| is_redirect_match(&registered, &requested))
+    }
+
+    /// Validates redirect URI against the policy:
+    /// - `https` is always allowed
+    /// - `http` is allowed only for loopback IP literals (RFC 8252, section 7.3), or for dev applications
+    /// - private-use schemes in reverse domain notation, like `com.example.app:/callback` (RFC 8252, section 7.1)
+    pub fn check_redirect_uri(redirect_uri: &str, is_dev: bool) -> Result<Url, RedirectUriError> {
+        let url = Url::parse(redirect_uri).map_err(|_| RedirectUriError::Malformed)?;
+
+        if url.fragment().is_some() {
+            return Err(RedirectUriError::HasFragment);
+        }
+
+        match url.scheme() {
+            "https" if url.has_host() => Ok(url),
+            "http" if is_dev || is_loopback(&url) => Ok(url),
+            "http" | "https" => Err(RedirectUriError::InsecureScheme),
+            scheme if scheme.contains('.') => Ok(url),
+            _ => Err(RedirectUriError::InsecureScheme),
+        }
+    }
+
+    /// Back-channel logout receiver is a server endpoint, so only `https` allowed,
+    /// and `http` for dev applications
+    pub fn check_backchannel_logout_uri(uri: &str, is_dev: bool) -> Result<Url, RedirectUriError> {
+        let url = Url::parse(uri).map_err(|_| RedirectUriError::Malformed)?;
+
+        if url.fragment().is_some() {
+            return Err(RedirectUriError::HasFragment);
+        }
+
+        match url.scheme() {
+            "https" if url.has_host() => Ok(url),
+            "http" if url.has_host() && (is_dev || is_loopback(&url)) => Ok(url),
+            _ => Err(RedirectUriError::InsecureScheme),
+        }
+    }
+
+    /// https://openid.net/specs/openid-connect-rpinitiated-1_0.html#RedirectionAfterLogout
+    /// Uses the same policy as redirect after authorization.
+    pub fn is_allowed_post_logout_redirect(&self, redirect_uri: &str) -> bool {
+        let requested = match Self::check_redirect_uri(redirect_uri, self.is_dev) {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+
+        self.post_logout_redirect_uri
+            .iter()
+            .filter_map(|uri| Url::parse(uri).ok())
+            .any(|registered| is_redirect_match(&registered, &requested))
     }
 
     /// https://www.oauth.com/oauth2-servers/access-tokens/authorization-code-request/
@@ -46,17 +122,105 @@ pub struct AuthorizationCode {
 impl AuthorizationCode {
     /// https://www.oauth.com/oauth2-servers/access-tokens/authorization-code-request/
     pub fn is_redirect_same(&self, redirect_uri: &str) -> bool {
-        self.redirect_uri == redirect_uri
+        match (Url::parse(&self.redirect_uri), Url::parse(redirect_uri)) {
+            (Ok(saved), Ok(requested)) => saved == requested,
+            _ => false,
+        }
     }
 
     pub fn is_code_correct(&self, code: &str) -> bool {
         self.code == code
     }
 
+    pub fn lifetime() -> chrono::Duration {
+        chrono::Duration::minutes(15)
+    }
+
     pub fn is_expired(&self) -> bool {
-        let lifetime = chrono::Duration::minutes(15);
         let now = Utc::now();
 
-        (self.created_at + lifetime) < now
+        (self.created_at + Self::lifetime()) < now
+    }
+}
+
+fn is_loopback(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        _ => false,
+    }
+}
+
+fn is_redirect_match(registered: &Url, requested: &Url) -> bool {
+    if requested.scheme() == "http" && is_loopback(requested) {
+        registered.scheme() == requested.scheme()
+            && registered.host() == requested.host()
+            && registered.path() == requested.path()
+            && registered.query() == requested.query()
+    } else {
+        registered == requested
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn application(is_dev: bool, redirect_uri: &[&str]) -> Application {
+        Application {
+            id: uuid::Uuid::new_v4(),
+            is_dev,
+            redirect_uri: redirect_uri.iter().map(ToString::to_string).collect(),
+            title: "Test".to_owned(),
+            secret_key: "secret".to_owned(),
+            allowed_registrations: true,
+            post_logout_redirect_uri: vec![],
+            backchannel_logout_uri: None,
+            require_dpop: false,
+        }
+    }
+
+    #[test]
+    fn loopback_redirect_matches_any_port() {
+        let app = application(false, &["http://127.0.0.1/callback"]);
+
+        assert!(app.is_allowed_redirect("http://127.0.0.1:51004/callback"));
+        assert!(app.is_allowed_redirect("http://127.0.0.1/callback"));
+        assert!(!app.is_allowed_redirect("http://127.0.0.1:51004/other"));
+        assert!(!app.is_allowed_redirect("http://localhost:51004/callback"));
+    }
+
+    #[test]
+    fn private_use_scheme_allowed() {
+        let app = application(false, &["com.example.app:/oauth2redirect"]);
+
+        assert!(app.is_allowed_redirect("com.example.app:/oauth2redirect"));
+        assert!(!app.is_allowed_redirect("com.example.other:/oauth2redirect"));
+    }
+
+    #[test]
+    fn non_dev_requires_https() {
+        assert_eq!(
+            Application::check_redirect_uri("http://example.com/callback", false),
+            Err(RedirectUriError::InsecureScheme)
+        );
+        assert!(Application::check_redirect_uri("http://example.com/callback", true).is_ok());
+        assert!(Application::check_redirect_uri("https://example.com/callback", false).is_ok());
+        assert_eq!(
+            Application::check_redirect_uri("https://example.com/callback#token", false),
+            Err(RedirectUriError::HasFragment)
+        );
+        assert_eq!(
+            Application::check_redirect_uri("myapp:/callback", false),
+            Err(RedirectUriError::InsecureScheme)
+        );
+    }
+
+    #[test]
+    fn https_redirect_compared_as_url() {
+        let app = application(false, &["https://EXAMPLE.com/callback"]);
+
+        assert!(app.is_allowed_redirect("https://example.com:443/callback"));
+        assert!(!app.is_allowed_redirect("https://example.com/callback/"));
     }
 }
@@ -0,0 +1,66 @@
+use chrono::Utc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmailDomainRuleKind {
+    Allow,
+    Deny,
+}
+
+impl EmailDomainRuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+/// Allows or denies emails of the domain and its subdomains.
+/// Rule without application is global
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmailDomainRule {
+    pub id: uuid::Uuid,
+    /// Lowercased, without `@`
+    pub domain: String,
+    pub kind: EmailDomainRuleKind,
+    pub application_id: Option<uuid::Uuid>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl EmailDomainRule {
+    pub fn new(
+        domain: &str,
+        kind: EmailDomainRuleKind,
+        application_id: Option<uuid::Uuid>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            domain: normalize_domain(domain),
+            kind,
+            application_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// `example.com` rule matches `example.com` and `mail.example.com`
+    pub fn matches(&self, domain: &str) -> bool {
+        domain == self.domain
+            || matches!(domain.strip_suffix(&self.domain), Some(subdomain) if subdomain.ends_with('.'))
+    }
+}
+
+/// Lowercased domain part of the email, without trailing dot
+pub fn email_domain(email: &str) -> Option<String> {
+    email
+        .rsplit_once('@')
+        .map(|(_, domain)| normalize_domain(domain))
+        .filter(|domain| !domain.is_empty())
+}
+
+fn normalize_domain(domain: &str) -> String {
+    domain
+        .trim()
+        .trim_start_matches('@')
+        .trim_end_matches('.')
+        .to_lowercase()
+}
@@ -0,0 +1,49 @@
+use chrono::Utc;
+
+/// Whether emails can be sent to the address, as reported by the email provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmailStatus {
+    Deliverable,
+    /// Mailbox does not exist or rejects emails permanently
+    Bounced,
+    /// Recipient marked an email as spam
+    Complained,
+}
+
+impl EmailStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deliverable => "deliverable",
+            Self::Bounced => "bounced",
+            Self::Complained => "complained",
+        }
+    }
+
+    /// Unknown value is deliverable, so a broken row does not stop emails
+    pub fn parse(status: &str) -> Self {
+        match status {
+            "bounced" => Self::Bounced,
+            "complained" => Self::Complained,
+            _ => Self::Deliverable,
+        }
+    }
+
+    /// Emails to the address are not sent anymore
+    pub fn is_suppressed(&self) -> bool {
+        !matches!(self, Self::Deliverable)
+    }
+}
+
+/// Bounce or complaint received with the provider webhook
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmailEvent {
+    /// `sendgrid`
+    pub provider: String,
+    /// The same event delivered twice is recorded once
+    pub provider_event_id: String,
+    pub email: String,
+    pub status: EmailStatus,
+    /// Bounce reason from the recipient server, if the provider has it
+    pub reason: Option<String>,
+    pub occurred_at: chrono::DateTime<Utc>,
+}
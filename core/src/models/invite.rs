@@ -0,0 +1,20 @@
+use chrono::Utc;
+
+/// Allows to register when registration is invite-only.
+/// Each invite can be used once
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApplicationInvite {
+    pub invite: String,
+    pub created_at: chrono::DateTime<Utc>,
+    /// Application the invite was generated for, if any
+    pub application_id: Option<uuid::Uuid>,
+    /// User registered with the invite
+    pub user_id: Option<uuid::Uuid>,
+    pub registered_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl ApplicationInvite {
+    pub fn is_used(&self) -> bool {
+        self.registered_at.is_some()
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::Utc;
+
+/// What failed login attempts are counted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoginAttemptKind {
+    /// Email from the login form, lowercased. Counted even if user does not exist
+    Account,
+    /// Client IP address
+    Ip,
+}
+
+impl LoginAttemptKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Account => "account",
+            Self::Ip => "ip",
+        }
+    }
+}
+
+/// Failed login attempts for one account or from one client IP
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoginAttempts {
+    pub kind: LoginAttemptKind,
+    pub key: String,
+    /// Count of failures in a row, reset on successful login or after a quiet period
+    pub failures: i32,
+    pub last_failure_at: chrono::DateTime<Utc>,
+    pub locked_until: Option<chrono::DateTime<Utc>>,
+    /// Sent to the owner of the locked account to unlock it before lockout ends
+    pub unlock_code: Option<String>,
+}
+
+impl LoginAttempts {
+    /// Login is not allowed before this time: lockout or progressive delay is not over.
+    /// Delay is base, 2×base, 4×base… after `delay_after` failures, up to `max_delay`
+    pub fn next_attempt_at(
+        &self,
+        delay_after: i32,
+        base_delay: chrono::Duration,
+        max_delay: chrono::Duration,
+    ) -> chrono::DateTime<Utc> {
+        let delayed = if self.failures < delay_after {
+            self.last_failure_at
+        } else {
+            let exponent = (self.failures - delay_after).clamp(0, 30) as u32;
+            let delay = base_delay
+                .checked_mul(2i32.saturating_pow(exponent))
+                .unwrap_or(max_delay)
+                .min(max_delay);
+            self.last_failure_at + delay
+        };
+
+        match self.locked_until {
+            Some(locked_until) => locked_until.max(delayed),
+            None => delayed,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::Utc;
+
+/// Back-channel logout notification waiting for delivery to the application
+/// https://openid.net/specs/openid-connect-backchannel-1_0.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogoutNotification {
+    pub id: uuid::Uuid,
+    pub client_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    /// Ended session, `None` when all sessions of the user are ended
+    pub session_id: Option<uuid::Uuid>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::DateTime<Utc>,
+    pub delivered_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl LogoutNotification {
+    /// After this count of failed attempts notification is not delivered anymore
+    pub const MAX_ATTEMPTS: i32 = 8;
+
+    /// Claimed notification is hidden from other workers for this long, in seconds
+    pub const CLAIM_LEASE_SECONDS: i64 = 5 * 60;
+
+    /// Exponential backoff: 1, 2, 4, 8… minutes after each failed attempt
+    pub fn next_attempt_after_failure(&self) -> chrono::DateTime<Utc> {
+        let delay = 2i64.pow(self.attempts.clamp(0, Self::MAX_ATTEMPTS) as u32);
+        Utc::now() + chrono::Duration::minutes(delay)
+    }
+}
@@ -2,10 +2,24 @@ use chrono::Utc;
 
 pub use access_token::*;
 pub use client::*;
+pub use email_domain_rule::*;
+pub use email_event::*;
+pub use invite::*;
+pub use login_attempt::*;
+pub use logout_notification::*;
+pub use outbox_email::*;
+pub use personal_access_token::*;
 pub use user_registration::*;
 
 mod access_token;
 mod client;
+mod email_domain_rule;
+mod email_event;
+mod invite;
+mod login_attempt;
+mod logout_notification;
+mod outbox_email;
+mod personal_access_token;
 mod user_registration;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -13,16 +27,41 @@ pub struct RegisterRequest {
     pub email: String,
     pub code: String,
     pub expires_at: chrono::DateTime<Utc>,
+    pub created_at: chrono::DateTime<Utc>,
+    /// When the code was sent to email last time
+    pub last_sent_at: chrono::DateTime<Utc>,
+    /// Negotiated when the request is created, becomes the locale of the user
+    pub locale: String,
+    /// Confirmation emails are not sent to bounced or complained email
+    pub email_status: EmailStatus,
+    /// Checked when the request is created, claimed when it is confirmed
+    pub invite: Option<String>,
 }
 
 impl RegisterRequest {
-    pub fn new(email: String, code: String) -> Self {
+    pub fn new(email: String, code: String, locale: String) -> Self {
+        let now = chrono::Utc::now();
+
         Self {
             email,
             code,
-            expires_at: chrono::Utc::now() + chrono::Duration::days(1),
+            expires_at: now + Self::lifetime(),
+            created_at: now,
+            last_sent_at: now,
+            locale,
+            email_status: EmailStatus::Deliverable,
+            invite: None,
         }
     }
+
+    pub fn lifetime() -> chrono::Duration {
+        chrono::Duration::days(1)
+    }
+
+    /// Wrong codes are counted for the mailbox during the lifetime of a request
+    pub fn failures_window_start(now: chrono::DateTime<Utc>) -> chrono::DateTime<Utc> {
+        now - Self::lifetime()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -33,11 +72,102 @@ pub struct User {
     pub password_hash: String,
     pub first_name: String,
     pub last_name: String,
+    /// Emails and messages for the user are in this locale
+    pub locale: String,
+    /// Reported by the email provider, emails are not sent unless deliverable
+    pub email_status: EmailStatus,
+    /// Confirmation code was received on the email, reset when the email is changed
+    pub email_verified: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SessionToken {
+    /// Identifies the session without exposing the token
+    pub id: uuid::Uuid,
     pub user_id: uuid::Uuid,
     pub token: String,
+    /// Moves forward while session is used, see [`SessionToken::needs_renewal`]
     pub expires_at: chrono::DateTime<Utc>,
+    /// Session cannot be extended after this time
+    pub absolute_expires_at: chrono::DateTime<Utc>,
+    /// When user entered credentials to create this session
+    pub authenticated_at: chrono::DateTime<Utc>,
+    /// Device which created the session
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub last_seen_at: chrono::DateTime<Utc>,
+}
+
+impl SessionToken {
+    /// `last_seen_at` is not updated more often to avoid write on each request
+    pub const LAST_SEEN_PRECISION_SECONDS: i64 = 60;
+
+    /// How long ago user has been authenticated
+    pub fn auth_age(&self) -> chrono::Duration {
+        Utc::now() - self.authenticated_at
+    }
+
+    pub fn is_last_seen_outdated(&self) -> bool {
+        Utc::now() - self.last_seen_at
+            > chrono::Duration::seconds(Self::LAST_SEEN_PRECISION_SECONDS)
+    }
+
+    /// Session is renewed when less than half of idle lifetime left,
+    /// so it is not updated on each request
+    pub fn needs_renewal(&self, idle_lifetime: chrono::Duration) -> bool {
+        self.expires_at < self.absolute_expires_at
+            && self.expires_at - Utc::now() < idle_lifetime / 2
+    }
+
+    pub fn renewed_expires_at(&self, idle_lifetime: chrono::Duration) -> chrono::DateTime<Utc> {
+        (Utc::now() + idle_lifetime).min(self.absolute_expires_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(expires_in: chrono::Duration, absolute_in: chrono::Duration) -> SessionToken {
+        let now = Utc::now();
+        SessionToken {
+            id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            token: "token".to_owned(),
+            expires_at: now + expires_in,
+            absolute_expires_at: now + absolute_in,
+            authenticated_at: now,
+            user_agent: None,
+            ip: None,
+            created_at: now,
+            last_seen_at: now,
+        }
+    }
+
+    #[test]
+    fn session_is_renewed_when_less_than_half_of_idle_lifetime_left() {
+        let idle = chrono::Duration::hours(24);
+
+        assert!(
+            !session(chrono::Duration::hours(13), chrono::Duration::days(14)).needs_renewal(idle)
+        );
+        assert!(
+            session(chrono::Duration::hours(11), chrono::Duration::days(14)).needs_renewal(idle)
+        );
+    }
+
+    #[test]
+    fn session_is_not_renewed_past_absolute_expiration() {
+        let idle = chrono::Duration::hours(24);
+        let capped = session(chrono::Duration::hours(2), chrono::Duration::hours(2));
+        assert!(!capped.needs_renewal(idle));
+
+        let near_absolute = session(chrono::Duration::hours(2), chrono::Duration::hours(5));
+        assert!(near_absolute.needs_renewal(idle));
+        assert_eq!(
+            near_absolute.renewed_expires_at(idle),
+            near_absolute.absolute_expires_at
+        );
+    }
 }
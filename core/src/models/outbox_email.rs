@@ -0,0 +1,67 @@
+use chrono::Utc;
+
+use crate::contracts::EmailMessage;
+
+/// Email queued in the same transaction as the change it is about.
+/// Delivered by the dispatcher, so requests do not wait for the email provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEmail {
+    pub id: uuid::Uuid,
+    pub recipient: String,
+    /// Locale of the recipient the email is rendered in
+    pub locale: String,
+    pub message: EmailMessage,
+    pub idempotency_key: String,
+    pub created_at: chrono::DateTime<Utc>,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::DateTime<Utc>,
+    pub sent_at: Option<chrono::DateTime<Utc>>,
+    /// Set when all attempts are failed, email is kept for investigation
+    pub failed_at: Option<chrono::DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl OutboxEmail {
+    /// After this count of failed attempts email is moved to dead letters
+    pub const MAX_ATTEMPTS: i32 = 8;
+
+    /// Claimed email is hidden from other dispatchers for this long, in seconds
+    pub const CLAIM_LEASE_SECONDS: i64 = 5 * 60;
+
+    /// Exponential backoff: 30 seconds, 1, 2, 4… minutes after each failed attempt.
+    /// None when attempts are exhausted
+    pub fn next_attempt_after_failure(&self) -> Option<chrono::DateTime<Utc>> {
+        if self.attempts + 1 >= Self::MAX_ATTEMPTS {
+            return None;
+        }
+
+        let delay = 30 * 2i64.pow(self.attempts.clamp(0, Self::MAX_ATTEMPTS) as u32);
+        Some(Utc::now() + chrono::Duration::seconds(delay))
+    }
+}
+
+/// The same idempotency key is queued only once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEmailForm {
+    pub recipient: String,
+    /// Locale of the recipient the email is rendered in
+    pub locale: String,
+    pub message: EmailMessage,
+    pub idempotency_key: String,
+}
+
+impl OutboxEmailForm {
+    pub fn new(
+        recipient: String,
+        locale: String,
+        message: EmailMessage,
+        idempotency_key: String,
+    ) -> Self {
+        Self {
+            recipient,
+            locale,
+            message,
+            idempotency_key,
+        }
+    }
+}
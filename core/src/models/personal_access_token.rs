@@ -0,0 +1,24 @@
+use chrono::Utc;
+
+/// Token created by the user to access own resources without OAuth flow
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PersonalAccessToken {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    /// Given by the user to recognize the token in the list
+    pub name: String,
+    pub token: String,
+    pub scopes: Vec<String>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub expires_at: chrono::DateTime<Utc>,
+    pub last_used_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    /// Token cannot live longer than a year, user should create a new one
+    pub const MAX_LIFETIME_DAYS: i64 = 365;
+
+    /// `last_used_at` is updated only when the previous use is older than this,
+    /// so authenticated requests don't write the row each time
+    pub const LAST_USED_PRECISION_MINUTES: i64 = 5;
+}
@@ -0,0 +1,148 @@
+/// Mailbox provider with known address rules
+struct Provider {
+    /// Domains delivering to the same mailboxes, the first one is canonical
+    domains: &'static [&'static str],
+    /// Dots in the local part are ignored by the provider
+    ignore_dots: bool,
+    /// Part of the local part after the separator is ignored by the provider
+    tag_separator: Option<char>,
+}
+
+const PROVIDERS: &[Provider] = &[
+    Provider {
+        domains: &["gmail.com", "googlemail.com"],
+        ignore_dots: true,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &["icloud.com", "me.com", "mac.com"],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &["proton.me", "protonmail.com", "protonmail.ch", "pm.me"],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &[
+            "yandex.ru",
+            "yandex.com",
+            "ya.ru",
+            "yandex.by",
+            "yandex.kz",
+            "yandex.ua",
+        ],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &["outlook.com"],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &["hotmail.com"],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &["live.com"],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+    Provider {
+        domains: &["fastmail.com"],
+        ignore_dots: false,
+        tag_separator: Some('+'),
+    },
+];
+
+/// Address which is the same for all spellings of one mailbox.
+/// Domain is lowercased and converted to punycode, provider rules are applied
+/// only for known providers: custom domains may treat dots and tags as significant
+pub fn canonical_email(email: &str) -> String {
+    let email = email.trim();
+
+    let (local, domain) = match email.rsplit_once('@') {
+        Some((local, domain)) => (local.to_lowercase(), ascii_domain(domain)),
+        None => return email.to_lowercase(),
+    };
+
+    let provider = PROVIDERS
+        .iter()
+        .find(|provider| provider.domains.contains(&domain.as_str()));
+
+    match provider {
+        Some(provider) => {
+            let mut local = local.as_str();
+            if let Some(separator) = provider.tag_separator {
+                // Address consisting of a tag only is left as is
+                if let Some((name, _)) = local
+                    .split_once(separator)
+                    .filter(|(name, _)| !name.is_empty())
+                {
+                    local = name;
+                }
+            }
+
+            let local = if provider.ignore_dots {
+                local.replace('.', "")
+            } else {
+                local.to_owned()
+            };
+
+            format!("{}@{}", local, provider.domains[0])
+        }
+        None => format!("{}@{}", local, domain),
+    }
+}
+
+fn ascii_domain(domain: &str) -> String {
+    let domain = domain.trim_end_matches('.');
+
+    // Invalid domain cannot receive emails, lowercase is enough to compare it
+    idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gmail_ignores_dots_tags_and_alias_domain() {
+        assert_eq!(
+            canonical_email("John.Doe+news@GoogleMail.com"),
+            "johndoe@gmail.com"
+        );
+        assert_eq!(
+            canonical_email("j.o.h.n.doe@gmail.com."),
+            "johndoe@gmail.com"
+        );
+        assert_eq!(canonical_email("+tag@gmail.com"), "+tag@gmail.com");
+    }
+
+    #[test]
+    fn other_providers_keep_dots() {
+        assert_eq!(
+            canonical_email("John.Doe+news@me.com"),
+            "john.doe@icloud.com"
+        );
+        assert_eq!(
+            canonical_email("john.doe+news@outlook.com"),
+            "john.doe@outlook.com"
+        );
+    }
+
+    #[test]
+    fn unknown_domain_is_only_lowercased() {
+        assert_eq!(
+            canonical_email("John.Doe+news@Corp.com"),
+            "john.doe+news@corp.com"
+        );
+        assert_eq!(
+            canonical_email("Demo@Bücher.example"),
+            "demo@xn--bcher-kva.example"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use accesso_settings::Email as EmailSettings;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::{EmailMessage, EmailNotification, SendEmailError};
+use crate::services::email_templates::{EmailTemplates, EmailTemplatesError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("Capture transport requires email.capture_dir")]
+    DirectoryRequired,
+    #[error(transparent)]
+    Templates(#[from] EmailTemplatesError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Message as it would be delivered to the recipient
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapturedEmail {
+    pub id: uuid::Uuid,
+    pub recipient: String,
+    pub locale: String,
+    pub message: EmailMessage,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Renders messages and keeps them instead of sending, for development only.
+/// Messages are stored as one json file each, so the email worker and every api process
+/// using the directory see the same messages
+#[derive(Debug, Clone)]
+pub struct Capture {
+    templates: Arc<EmailTemplates>,
+    dir: PathBuf,
+}
+
+impl Capture {
+    pub fn new(email: EmailSettings) -> Result<Self, CaptureError> {
+        let dir = PathBuf::from(
+            email
+                .capture_dir
+                .as_ref()
+                .ok_or(CaptureError::DirectoryRequired)?,
+        );
+        std::fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            templates: Arc::new(EmailTemplates::new(&email)?),
+            dir,
+        })
+    }
+
+    /// Captured messages for the recipient, oldest first
+    pub fn list(&self, recipient: &str) -> Result<Vec<CapturedEmail>, CaptureError> {
+        let mut emails = vec![];
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if matches!(path.extension(), Some(ext) if ext == "json") {
+                emails.push(serde_json::from_slice(&std::fs::read(path)?)?);
+            }
+        }
+
+        emails.retain(|email: &CapturedEmail| email.recipient.eq_ignore_ascii_case(recipient));
+        emails.sort_by_key(|email| email.captured_at);
+
+        Ok(emails)
+    }
+
+    fn store(&self, email: CapturedEmail) -> Result<(), CaptureError> {
+        // Written under temporary name, so readers never see a partial file
+        let path = self.dir.join(format!("{}.json", email.id));
+        let temporary = path.with_extension("tmp");
+        std::fs::write(&temporary, serde_json::to_vec(&email)?)?;
+        std::fs::rename(temporary, path)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EmailNotification for Capture {
+    #[tracing::instrument(skip(self))]
+    async fn send(
+        &self,
+        email: String,
+        locale: String,
+        message: EmailMessage,
+    ) -> Result<(), SendEmailError> {
+        let rendered = self
+            .templates
+            .render(&email, &locale, &message)
+            .map_err(CaptureError::from)?;
+
+        self.store(CapturedEmail {
+            id: uuid::Uuid::new_v4(),
+            recipient: email,
+            locale,
+            message,
+            subject: rendered.subject,
+            text: rendered.text,
+            html: rendered.html,
+            captured_at: chrono::Utc::now(),
+        })?;
+
+        tracing::info!("Email captured");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(capture_dir: Option<String>) -> EmailSettings {
+        EmailSettings {
+            sender_email: "no-reply@accesso.sova.dev".to_owned(),
+            sender_name: "Accesso".to_owned(),
+            application_host: "accesso.sova.dev".to_owned(),
+            email_confirm_url_prefix: "/register/confirm-".to_owned(),
+            account_unlock_url_prefix: "/login/unlock-".to_owned(),
+            templates_dir: None,
+            transport: accesso_settings::EmailTransport::Capture,
+            outbox_interval: 5,
+            capture_dir,
+        }
+    }
+
+    fn capture(capture_dir: Option<String>) -> Capture {
+        Capture::new(settings(capture_dir)).unwrap()
+    }
+
+    async fn lists_by_recipient(capture: Capture) {
+        let confirmation = EmailMessage::RegisterConfirmation {
+            code: "demo-code".to_owned(),
+        };
+
+        capture
+            .send(
+                "John@gmail.com".to_owned(),
+                "en".to_owned(),
+                confirmation.clone(),
+            )
+            .await
+            .unwrap();
+        capture
+            .send(
+                "jane@gmail.com".to_owned(),
+                "ru".to_owned(),
+                EmailMessage::AccountAlreadyExists,
+            )
+            .await
+            .unwrap();
+
+        let emails = capture.list("john@gmail.com").unwrap();
+
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].message, confirmation);
+        assert_eq!(emails[0].subject, "Confirm registration at Accesso");
+        assert!(emails[0].text.contains("/register/confirm-demo-code"));
+    }
+
+    #[test]
+    fn requires_directory() {
+        assert!(matches!(
+            Capture::new(settings(None)),
+            Err(CaptureError::DirectoryRequired)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn captures_to_directory() {
+        let dir = std::env::temp_dir().join(format!("accesso-capture-{}", uuid::Uuid::new_v4()));
+
+        lists_by_recipient(capture(Some(dir.display().to_string()))).await;
+
+        // Another process reads the same messages
+        assert_eq!(
+            capture(Some(dir.display().to_string()))
+                .list("john@gmail.com")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
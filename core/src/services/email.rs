@@ -1,8 +1,12 @@
+use std::sync::Arc;
+
 use crate::contracts::{EmailMessage, EmailNotification, SendEmailError};
-use accesso_settings::SendGrid;
+use crate::services::email_templates::{EmailTemplates, EmailTemplatesError};
+use accesso_settings::{Email as Settings, SendGrid};
 use async_trait::async_trait;
 use reqwest::{Client, StatusCode};
 
+/// Renders messages locally and sends them with SendGrid
 #[derive(Clone, Debug)]
 pub struct Email {
     /// SendGrid api_key
@@ -10,77 +14,78 @@ pub struct Email {
 
     /// Who sends email: no-reply@domain.com
     pub sender_email: String,
+    pub sender_name: String,
 
-    /// domain of the application, without https:// and any path
-    pub application_host: String,
-
-    /// Confirmation url prefix. Should be concatenated with https:// and application_host
-    pub email_confirm_url_prefix: String,
-    pub email_confirm_template: String,
     pub enabled: bool,
+    templates: Arc<EmailTemplates>,
     client: Client,
 }
 
-impl From<SendGrid> for Email {
-    fn from(s: SendGrid) -> Self {
-        Self {
-            api_key: s.api_key,
-            sender_email: s.sender_email,
-            application_host: s.application_host,
-            email_confirm_template: s.email_confirm_template,
-            email_confirm_url_prefix: s.email_confirm_url_prefix,
-            enabled: s.enabled,
+impl Email {
+    pub fn new(email: Settings, sendgrid: SendGrid) -> Result<Self, EmailTemplatesError> {
+        Ok(Self {
+            api_key: sendgrid.api_key,
+            sender_email: email.sender_email.clone(),
+            sender_name: email.sender_name.clone(),
+            enabled: sendgrid.enabled,
+            templates: Arc::new(EmailTemplates::new(&email)?),
             client: Client::new(),
-        }
+        })
     }
 }
 
 #[async_trait]
 impl EmailNotification for Email {
     #[tracing::instrument]
-    async fn send(&self, email: String, message: EmailMessage) -> Result<(), SendEmailError> {
+    async fn send(
+        &self,
+        email: String,
+        locale: String,
+        message: EmailMessage,
+    ) -> Result<(), SendEmailError> {
         if !self.enabled {
             tracing::warn!("Email service is disabled!");
             tracing::debug!(?message);
             return Ok(());
         }
 
-        if let EmailMessage::RegisterConfirmation { code } = message {
-            let request = self
-                .client
-                .post("https://api.sendgrid.com/v3/mail/send")
-                .header("Authorization", format!("Bearer {}", self.api_key.clone()))
-                .json(&sg::MailSend {
-                    subject: "Confirm registration at Accesso".to_owned(),
-                    template_id: self.email_confirm_template.clone(),
-                    from: sg::Sender {
-                        email: self.sender_email.clone(),
-                        name: "Accesso".to_owned(),
+        let rendered = self.templates.render(&email, &locale, &message)?;
+
+        let request = self
+            .client
+            .post("https://api.sendgrid.com/v3/mail/send")
+            .header("Authorization", format!("Bearer {}", self.api_key.clone()))
+            .json(&sg::MailSend {
+                subject: rendered.subject,
+                from: sg::Sender {
+                    email: self.sender_email.clone(),
+                    name: self.sender_name.clone(),
+                },
+                personalizations: vec![sg::Personalization {
+                    to: vec![sg::Target { email }],
+                }],
+                // SendGrid requires plain text to go first
+                content: vec![
+                    sg::Content {
+                        r#type: "text/plain",
+                        value: rendered.text,
+                    },
+                    sg::Content {
+                        r#type: "text/html",
+                        value: rendered.html,
                     },
-                    personalizations: vec![sg::Personalization {
-                        dynamic_template_data: sg::TemplateData {
-                            application_host: self.application_host.clone(),
-                            confirm_registration_url: format!(
-                                "https://{host}{prefix}{code}",
-                                host = self.application_host,
-                                prefix = self.email_confirm_url_prefix,
-                                code = code
-                            ),
-                        },
-                        to: vec![sg::Target { email }],
-                    }],
-                });
-
-            let resp = request.send().await?;
-
-            tracing::info!("resp: {:?}", resp);
-
-            if resp.status() != StatusCode::ACCEPTED {
-                return Err(SendEmailError::Unexpected(eyre::eyre!(
-                    "Could not send email!, status: {}",
-                    resp.status()
-                )));
-            }
+                ],
+            });
+
+        let resp = request.send().await?;
+
+        tracing::info!("resp: {:?}", resp);
+
+        if resp.status() != StatusCode::ACCEPTED {
+            return Err(SendEmailError::Unexpected(eyre::eyre!(
+                "Could not send email!, status: {}",
+                resp.status()
+            )));
         }
 
         Ok(())
@@ -95,13 +100,12 @@ mod sg {
         pub personalizations: Vec<Personalization>,
         pub from: Sender,
         pub subject: String,
-        pub template_id: String,
+        pub content: Vec<Content>,
     }
 
     #[derive(Debug, Serialize)]
     pub struct Personalization {
         pub to: Vec<Target>,
-        pub dynamic_template_data: TemplateData,
     }
 
     #[derive(Debug, Serialize)]
@@ -110,12 +114,9 @@ mod sg {
     }
 
     #[derive(Debug, Serialize)]
-    pub struct TemplateData {
-        #[serde(rename = "applicationHost")]
-        pub application_host: String,
-
-        #[serde(rename = "confirmRegistrationUrl")]
-        pub confirm_registration_url: String,
+    pub struct Content {
+        pub r#type: &'static str,
+        pub value: String,
     }
 
     #[derive(Debug, Serialize)]
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use crate::models::{email_domain, EmailDomainRule, EmailDomainRuleKind};
+
+lazy_static::lazy_static! {
+    static ref DISPOSABLE_DOMAINS: HashSet<&'static str> = {
+        let str = include_str!("../../../resources/disposable_domains.txt");
+        str.lines().map(str::trim).filter(|line| !line.is_empty()).collect()
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum EmailDomainRejection {
+    #[error("Email domain is denied")]
+    Denied,
+    #[error("Email domain is not in the allowlist")]
+    NotAllowed,
+    #[error("Disposable email addresses are not accepted")]
+    Disposable,
+}
+
+/// Domain or one of its parent domains is in the bundled list of throwaway email services
+pub fn is_disposable_domain(domain: &str) -> bool {
+    let mut domain = domain;
+
+    loop {
+        if DISPOSABLE_DOMAINS.contains(domain) {
+            return true;
+        }
+
+        match domain.split_once('.') {
+            Some((_, parent)) => domain = parent,
+            None => return false,
+        }
+    }
+}
+
+/// Deny rule wins over allow rule. If global rules or rules of the application
+/// have an allowlist, the domain should match it. Disposable domain passes only
+/// if an allow rule matches it explicitly
+pub fn check_email_domain(
+    email: &str,
+    rules: &[EmailDomainRule],
+    block_disposable: bool,
+) -> Result<(), EmailDomainRejection> {
+    let domain = email_domain(email).ok_or(EmailDomainRejection::NotAllowed)?;
+    let matching = |kind: EmailDomainRuleKind| {
+        let domain = &domain;
+        rules
+            .iter()
+            .filter(move |rule| rule.kind == kind && rule.matches(domain))
+    };
+
+    if matching(EmailDomainRuleKind::Deny).next().is_some() {
+        return Err(EmailDomainRejection::Denied);
+    }
+
+    let has_allowlist = |global: bool| {
+        rules.iter().any(|rule| {
+            rule.kind == EmailDomainRuleKind::Allow && rule.application_id.is_none() == global
+        })
+    };
+    let allowed_by = |global: bool| {
+        matching(EmailDomainRuleKind::Allow).any(|rule| rule.application_id.is_none() == global)
+    };
+
+    for global in [true, false] {
+        if has_allowlist(global) && !allowed_by(global) {
+            return Err(EmailDomainRejection::NotAllowed);
+        }
+    }
+
+    if block_disposable
+        && is_disposable_domain(&domain)
+        && matching(EmailDomainRuleKind::Allow).next().is_none()
+    {
+        return Err(EmailDomainRejection::Disposable);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(domain: &str, kind: EmailDomainRuleKind, application: bool) -> EmailDomainRule {
+        EmailDomainRule::new(domain, kind, application.then(uuid::Uuid::new_v4))
+    }
+
+    #[test]
+    fn deny_rule_wins_over_allow_rule() {
+        let rules = [
+            rule("corp.com", EmailDomainRuleKind::Allow, false),
+            rule("Staging.Corp.com", EmailDomainRuleKind::Deny, true),
+        ];
+
+        assert_eq!(
+            check_email_domain("demo@mail.corp.com", &rules, true),
+            Ok(())
+        );
+        assert_eq!(
+            check_email_domain("demo@api.staging.corp.com", &rules, true),
+            Err(EmailDomainRejection::Denied)
+        );
+        assert_eq!(
+            check_email_domain("demo@notcorp.com", &rules, true),
+            Err(EmailDomainRejection::NotAllowed)
+        );
+    }
+
+    #[test]
+    fn application_allowlist_applies_with_global_rules() {
+        let rules = [rule("corp.com", EmailDomainRuleKind::Allow, true)];
+
+        assert_eq!(check_email_domain("demo@corp.com", &rules, true), Ok(()));
+        assert_eq!(
+            check_email_domain("demo@gmail.com", &rules, true),
+            Err(EmailDomainRejection::NotAllowed)
+        );
+    }
+
+    #[test]
+    fn disposable_domain_is_blocked_unless_allowed() {
+        assert_eq!(
+            check_email_domain("demo@mail.yopmail.com", &[], true),
+            Err(EmailDomainRejection::Disposable)
+        );
+        assert_eq!(check_email_domain("demo@yopmail.com", &[], false), Ok(()));
+
+        let rules = [rule("yopmail.com", EmailDomainRuleKind::Allow, false)];
+        assert_eq!(check_email_domain("demo@yopmail.com", &rules, true), Ok(()));
+    }
+}
@@ -0,0 +1,339 @@
+use std::path::Path;
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::contracts::EmailMessage;
+
+/// Templates are embedded in the binary, files from `templates_dir` replace them.
+/// Translations are in locale directories, missing ones fall back to the root templates
+const TEMPLATES: &[(&str, &str)] = &[
+    (
+        "layout.html",
+        include_str!("../../../resources/emails/layout.html"),
+    ),
+    (
+        "register_confirmation/subject.txt",
+        include_str!("../../../resources/emails/register_confirmation/subject.txt"),
+    ),
+    (
+        "register_confirmation/body.html",
+        include_str!("../../../resources/emails/register_confirmation/body.html"),
+    ),
+    (
+        "register_confirmation/body.txt",
+        include_str!("../../../resources/emails/register_confirmation/body.txt"),
+    ),
+    (
+        "register_finished/subject.txt",
+        include_str!("../../../resources/emails/register_finished/subject.txt"),
+    ),
+    (
+        "register_finished/body.html",
+        include_str!("../../../resources/emails/register_finished/body.html"),
+    ),
+    (
+        "register_finished/body.txt",
+        include_str!("../../../resources/emails/register_finished/body.txt"),
+    ),
+    (
+        "account_already_exists/subject.txt",
+        include_str!("../../../resources/emails/account_already_exists/subject.txt"),
+    ),
+    (
+        "account_already_exists/body.html",
+        include_str!("../../../resources/emails/account_already_exists/body.html"),
+    ),
+    (
+        "account_already_exists/body.txt",
+        include_str!("../../../resources/emails/account_already_exists/body.txt"),
+    ),
+    (
+        "account_locked/subject.txt",
+        include_str!("../../../resources/emails/account_locked/subject.txt"),
+    ),
+    (
+        "account_locked/body.html",
+        include_str!("../../../resources/emails/account_locked/body.html"),
+    ),
+    (
+        "account_locked/body.txt",
+        include_str!("../../../resources/emails/account_locked/body.txt"),
+    ),
+    (
+        "ru/layout.html",
+        include_str!("../../../resources/emails/ru/layout.html"),
+    ),
+    (
+        "ru/register_confirmation/subject.txt",
+        include_str!("../../../resources/emails/ru/register_confirmation/subject.txt"),
+    ),
+    (
+        "ru/register_confirmation/body.html",
+        include_str!("../../../resources/emails/ru/register_confirmation/body.html"),
+    ),
+    (
+        "ru/register_confirmation/body.txt",
+        include_str!("../../../resources/emails/ru/register_confirmation/body.txt"),
+    ),
+    (
+        "ru/register_finished/subject.txt",
+        include_str!("../../../resources/emails/ru/register_finished/subject.txt"),
+    ),
+    (
+        "ru/register_finished/body.html",
+        include_str!("../../../resources/emails/ru/register_finished/body.html"),
+    ),
+    (
+        "ru/register_finished/body.txt",
+        include_str!("../../../resources/emails/ru/register_finished/body.txt"),
+    ),
+    (
+        "ru/account_already_exists/subject.txt",
+        include_str!("../../../resources/emails/ru/account_already_exists/subject.txt"),
+    ),
+    (
+        "ru/account_already_exists/body.html",
+        include_str!("../../../resources/emails/ru/account_already_exists/body.html"),
+    ),
+    (
+        "ru/account_already_exists/body.txt",
+        include_str!("../../../resources/emails/ru/account_already_exists/body.txt"),
+    ),
+    (
+        "ru/account_locked/subject.txt",
+        include_str!("../../../resources/emails/ru/account_locked/subject.txt"),
+    ),
+    (
+        "ru/account_locked/body.html",
+        include_str!("../../../resources/emails/ru/account_locked/body.html"),
+    ),
+    (
+        "ru/account_locked/body.txt",
+        include_str!("../../../resources/emails/ru/account_locked/body.txt"),
+    ),
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailTemplatesError {
+    #[error("Could not read template {0}: {1}")]
+    Io(String, #[source] std::io::Error),
+    #[error(transparent)]
+    Template(#[from] tera::Error),
+}
+
+/// Email ready to be passed to a transport
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct EmailTemplates {
+    tera: Tera,
+    application_host: String,
+    email_confirm_url_prefix: String,
+    account_unlock_url_prefix: String,
+}
+
+#[derive(Serialize)]
+struct Variables<'a> {
+    application_host: &'a str,
+    application_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confirm_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unlock_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_name: Option<&'a str>,
+}
+
+impl EmailTemplates {
+    pub fn new(settings: &accesso_settings::Email) -> Result<Self, EmailTemplatesError> {
+        let mut templates = TEMPLATES
+            .iter()
+            .map(|(name, content)| (name.to_string(), content.to_string()))
+            .collect::<Vec<_>>();
+
+        if let Some(dir) = &settings.templates_dir {
+            for (name, content) in templates.iter_mut() {
+                let path = Path::new(dir).join(name.as_str());
+                if path.exists() {
+                    *content = std::fs::read_to_string(&path)
+                        .map_err(|e| EmailTemplatesError::Io(path.display().to_string(), e))?;
+                }
+            }
+        }
+
+        let mut tera = Tera::default();
+        // Templates with .html suffix are escaped, text parts are not
+        tera.add_raw_templates(templates)?;
+
+        Ok(Self {
+            tera,
+            application_host: settings.application_host.clone(),
+            email_confirm_url_prefix: settings.email_confirm_url_prefix.clone(),
+            account_unlock_url_prefix: settings.account_unlock_url_prefix.clone(),
+        })
+    }
+
+    pub fn render(
+        &self,
+        email: &str,
+        locale: &str,
+        message: &EmailMessage,
+    ) -> Result<RenderedEmail, EmailTemplatesError> {
+        let application_url = format!("https://{}", self.application_host);
+        let mut variables = Variables {
+            application_host: &self.application_host,
+            application_url: application_url.clone(),
+            confirm_url: None,
+            unlock_url: None,
+            first_name: None,
+            last_name: None,
+        };
+
+        let name = match message {
+            EmailMessage::RegisterConfirmation { code } => {
+                // Email is needed to count wrong codes for its requests
+                variables.confirm_url = Some(format!(
+                    "{url}{prefix}{code}?email={email}",
+                    url = application_url,
+                    prefix = self.email_confirm_url_prefix,
+                    code = code,
+                    email =
+                        url::form_urlencoded::byte_serialize(email.as_bytes()).collect::<String>()
+                ));
+                "register_confirmation"
+            }
+            EmailMessage::RegisterFinished {
+                first_name,
+                last_name,
+            } => {
+                variables.first_name = Some(first_name);
+                variables.last_name = Some(last_name);
+                "register_finished"
+            }
+            EmailMessage::AccountAlreadyExists => "account_already_exists",
+            EmailMessage::AccountLocked { code } => {
+                variables.unlock_url = Some(format!(
+                    "{url}{prefix}{code}",
+                    url = application_url,
+                    prefix = self.account_unlock_url_prefix,
+                    code = code
+                ));
+                "account_locked"
+            }
+        };
+
+        let context = Context::from_serialize(&variables)?;
+        let render = |part: &str| {
+            let localized = format!("{}/{}/{}", locale, name, part);
+            let translated = self.tera.get_template_names().any(|name| name == localized);
+            let template = match translated {
+                true => localized,
+                false => format!("{}/{}", name, part),
+            };
+
+            self.tera.render(&template, &context)
+        };
+
+        Ok(RenderedEmail {
+            subject: render("subject.txt")?.trim().to_owned(),
+            html: render("body.html")?,
+            text: render("body.txt")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(email: &str, locale: &str, message: EmailMessage) -> String {
+        let templates = EmailTemplates::new(&accesso_settings::Email {
+            sender_email: "no-reply@accesso.sova.dev".to_owned(),
+            sender_name: "Accesso".to_owned(),
+            application_host: "accesso.sova.dev".to_owned(),
+            email_confirm_url_prefix: "/register/confirm-".to_owned(),
+            account_unlock_url_prefix: "/login/unlock-".to_owned(),
+            templates_dir: None,
+            transport: accesso_settings::EmailTransport::SendGrid,
+            outbox_interval: 5,
+            capture_dir: None,
+        })
+        .unwrap();
+        let rendered = templates.render(email, locale, &message).unwrap();
+
+        format!(
+            "Subject: {}\n\n--- text ---\n{}\n--- html ---\n{}",
+            rendered.subject, rendered.text, rendered.html
+        )
+    }
+
+    #[test]
+    fn register_confirmation() {
+        insta::assert_snapshot!(render(
+            "john+demo@gmail.com",
+            "en",
+            EmailMessage::RegisterConfirmation {
+                code: "demo-code".to_owned(),
+            }
+        ));
+    }
+
+    #[test]
+    fn register_finished() {
+        insta::assert_snapshot!(render(
+            "john@gmail.com",
+            "en",
+            EmailMessage::RegisterFinished {
+                first_name: "John".to_owned(),
+                last_name: "<Doe>".to_owned(),
+            }
+        ));
+    }
+
+    #[test]
+    fn account_already_exists() {
+        insta::assert_snapshot!(render(
+            "john@gmail.com",
+            "en",
+            EmailMessage::AccountAlreadyExists
+        ));
+    }
+
+    #[test]
+    fn account_locked() {
+        insta::assert_snapshot!(render(
+            "john@gmail.com",
+            "en",
+            EmailMessage::AccountLocked {
+                code: "demo-code".to_owned(),
+            }
+        ));
+    }
+
+    #[test]
+    fn register_confirmation_ru() {
+        insta::assert_snapshot!(render(
+            "john@gmail.com",
+            "ru",
+            EmailMessage::RegisterConfirmation {
+                code: "demo-code".to_owned(),
+            }
+        ));
+    }
+
+    #[test]
+    fn unknown_locale_falls_back() {
+        assert_eq!(
+            render("john@gmail.com", "de", EmailMessage::AccountAlreadyExists),
+            render("john@gmail.com", "en", EmailMessage::AccountAlreadyExists)
+        );
+    }
+}
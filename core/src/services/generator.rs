@@ -1,4 +1,17 @@
-use crate::contracts::SecureGenerator;
+use std::sync::Arc;
+use std::time::Instant;
+
+use accesso_settings::{PasswordHashAlgorithm, PasswordHashing};
+use argon2::password_hash::{PasswordHash, PasswordHasher, SaltString};
+use argon2::Argon2;
+use async_trait::async_trait;
+use eyre::WrapErr;
+use once_cell::sync::OnceCell;
+use opentelemetry::metrics::ValueRecorder;
+use opentelemetry::KeyValue;
+use tokio::sync::Semaphore;
+
+use crate::contracts::{HashingError, SecureGenerator};
 
 lazy_static::lazy_static! {
     static ref WORDS: Vec<&'static str> = {
@@ -7,49 +20,170 @@ lazy_static::lazy_static! {
     };
 }
 
-#[derive(Clone, Default)]
-pub struct Generator {}
+/// Each actix and background worker builds its own generator,
+/// so the limit is made once by the first one and shared by the whole process
+static HASHING_PERMITS: OnceCell<Arc<Semaphore>> = OnceCell::new();
+
+#[derive(Clone)]
+pub struct Generator {
+    new_hash: NewHash,
+    dummy_hash: String,
+    /// Each hashing holds a permit while running, shared by all generators
+    hashing_permits: Arc<Semaphore>,
+    /// Time spent waiting for a permit, in seconds
+    hashing_queue_time: ValueRecorder<f64>,
+}
+
+/// Algorithm and cost of new hashes
+#[derive(Clone, Debug)]
+enum NewHash {
+    Argon2id(argon2::Params),
+    Scrypt(scrypt::Params),
+}
 
 const TOKEN_LENGTH: u8 = 28;
 const TOKEN_LONG_LENGTH: usize = 52;
+const SALT_LENGTH: usize = 16;
+const SCRYPT_OUTPUT_LENGTH: usize = 32;
+
+impl TryFrom<PasswordHashing> for Generator {
+    type Error = HashingError;
+
+    fn try_from(s: PasswordHashing) -> Result<Self, Self::Error> {
+        let new_hash = match s.algorithm {
+            PasswordHashAlgorithm::Argon2id => NewHash::Argon2id(
+                argon2::Params::new(
+                    s.argon2_memory_cost,
+                    s.argon2_time_cost,
+                    s.argon2_parallelism,
+                    None,
+                )
+                .map_err(|error| eyre::eyre!("Invalid argon2 params: {}", error))?,
+            ),
+            PasswordHashAlgorithm::Scrypt => NewHash::Scrypt(
+                scrypt::Params::new(s.scrypt_log_n, s.scrypt_r, s.scrypt_p)
+                    .map_err(|error| eyre::eyre!("Invalid scrypt params: {}", error))?,
+            ),
+        };
+        let dummy_hash = hash_password(&new_hash, random_string(TOKEN_LENGTH as usize))?;
+        let meter = opentelemetry::global::meter("accesso");
+
+        Ok(Self {
+            new_hash,
+            dummy_hash,
+            hashing_permits: HASHING_PERMITS
+                .get_or_init(|| Arc::new(Semaphore::new(s.max_concurrency.max(1))))
+                .clone(),
+            hashing_queue_time: meter
+                .f64_value_recorder("password_hashing.queue_time")
+                .with_description("Time password hashing waits for a free slot, in seconds")
+                .init(),
+        })
+    }
+}
 
 impl Generator {
-    pub fn new() -> Self {
-        Self {}
+    /// Runs hashing on a blocking thread when concurrency limit allows
+    async fn run_hashing<T, F>(
+        &self,
+        operation: &'static str,
+        hashing: F,
+    ) -> Result<T, HashingError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let queued_at = Instant::now();
+        let _permit = self
+            .hashing_permits
+            .acquire()
+            .await
+            .wrap_err("Password hashing queue is closed")?;
+
+        self.hashing_queue_time.record(
+            queued_at.elapsed().as_secs_f64(),
+            &[KeyValue::new("operation", operation)],
+        );
+
+        Ok(actix_rt::task::spawn_blocking(hashing)
+            .await
+            .wrap_err("Password hashing task failed")?)
     }
 }
 
+#[async_trait]
 impl SecureGenerator for Generator {
     fn secure_words(&self, length: u8) -> String {
         create_words_password(length, "-")
     }
 
-    fn password_hash(&self, password: String) -> (String, Vec<u8>) {
-        use sodiumoxide::crypto::pwhash::argon2id13;
-        sodiumoxide::init().unwrap();
+    async fn password_hash(&self, password: String) -> Result<String, HashingError> {
+        let new_hash = self.new_hash.clone();
+        self.run_hashing("hash", move || hash_password(&new_hash, password))
+            .await?
+    }
 
-        let hash = argon2id13::pwhash(
-            password.as_bytes(),
-            argon2id13::OPSLIMIT_INTERACTIVE,
-            argon2id13::MEMLIMIT_INTERACTIVE,
-        )
-        .unwrap();
+    async fn verify_hash(&self, hash: &str, password: &str) -> Result<bool, HashingError> {
+        let hash = hash.to_owned();
+        let password = password.to_owned();
+        self.run_hashing("verify", move || verify_password(&hash, &password))
+            .await
+    }
 
-        let texthash = std::str::from_utf8(&hash.0).unwrap().to_owned();
+    fn hash_needs_upgrade(&self, hash: &str) -> bool {
+        let hash = match PasswordHash::new(trim_hash(hash)) {
+            Ok(hash) => hash,
+            Err(_) => return true,
+        };
 
-        (texthash, hash.0.to_vec())
+        match &self.new_hash {
+            NewHash::Argon2id(params) => {
+                hash.algorithm != argon2::Algorithm::Argon2id.ident()
+                    || hash.version != Some(argon2::Version::V0x13.into())
+                    || argon2::Params::try_from(&hash).map_or(true, |current| {
+                        current.m_cost() != params.m_cost()
+                            || current.t_cost() != params.t_cost()
+                            || current.p_cost() != params.p_cost()
+                    })
+            }
+            NewHash::Scrypt(params) => {
+                hash.algorithm != scrypt::ALG_ID
+                    || scrypt::Params::try_from(&hash).map_or(true, |current| {
+                        current.log_n() != params.log_n()
+                            || current.r() != params.r()
+                            || current.p() != params.p()
+                    })
+            }
+        }
     }
 
-    fn verify_hash(&self, hash: &[u8], password: &str) -> bool {
-        use sodiumoxide::crypto::pwhash::argon2id13;
-        sodiumoxide::init().unwrap();
+    fn hash_is_supported(&self, hash: &str) -> bool {
+        let hash = trim_hash(hash);
+        if is_bcrypt(hash) {
+            return true;
+        }
 
-        match argon2id13::HashedPassword::from_slice(hash) {
-            Some(hp) => argon2id13::pwhash_verify(&hp, password.as_bytes()),
-            _ => false,
+        match PasswordHash::new(hash) {
+            // scrypt is verified only with 32 bytes output
+            Ok(hash) if hash.algorithm == scrypt::ALG_ID => {
+                hash.hash.map(|output| output.len()) == Some(SCRYPT_OUTPUT_LENGTH)
+            }
+            Ok(hash) => [
+                argon2::Algorithm::Argon2id.ident(),
+                argon2::Algorithm::Argon2i.ident(),
+                argon2::Algorithm::Argon2d.ident(),
+                pbkdf2::Algorithm::Pbkdf2Sha256.ident(),
+                pbkdf2::Algorithm::Pbkdf2Sha512.ident(),
+            ]
+            .contains(&hash.algorithm),
+            Err(_) => false,
         }
     }
 
+    fn dummy_hash(&self) -> &str {
+        &self.dummy_hash
+    }
+
     fn generate_token(&self) -> String {
         random_string(TOKEN_LENGTH as usize)
     }
@@ -59,6 +193,58 @@ impl SecureGenerator for Generator {
     }
 }
 
+/// PHC string of the hash with algorithm, cost and salt inside
+fn hash_password(new_hash: &NewHash, password: String) -> Result<String, HashingError> {
+    let salt = SaltString::b64_encode(&rand::random::<[u8; SALT_LENGTH]>())
+        .map_err(|error| eyre::eyre!("Could not encode salt: {}", error))?;
+
+    let hash = match new_hash {
+        NewHash::Argon2id(params) => Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            params.clone(),
+        )
+        .hash_password(password.as_bytes(), &salt),
+        NewHash::Scrypt(params) => {
+            scrypt::Scrypt.hash_password_customized(password.as_bytes(), None, None, *params, &salt)
+        }
+    };
+
+    Ok(hash
+        .map_err(|error| eyre::eyre!("Could not hash password: {}", error))?
+        .to_string())
+}
+
+/// Algorithm and cost are read from the hash, so old hashes are verified after settings change.
+/// Hashes imported from other applications are upgraded on login too
+fn verify_password(hash: &str, password: &str) -> bool {
+    let hash = trim_hash(hash);
+    if is_bcrypt(hash) {
+        return bcrypt::verify(password, hash).unwrap_or(false);
+    }
+
+    match PasswordHash::new(hash) {
+        Ok(hash) => hash
+            .verify_password(
+                &[&Argon2::default(), &scrypt::Scrypt, &pbkdf2::Pbkdf2],
+                password,
+            )
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// bcrypt hash is not a PHC string: `$2b$12$<salt><hash>`
+fn is_bcrypt(hash: &str) -> bool {
+    hash.parse::<bcrypt::HashParts>().is_ok()
+}
+
+/// Hashes made by sodiumoxide are PHC strings padded with NULs to 128 bytes.
+/// Stored ones are already trimmed, because Postgres does not allow NULs in text
+fn trim_hash(hash: &str) -> &str {
+    hash.trim_end_matches('\u{0}')
+}
+
 fn create_words_password(length: u8, separator: &str) -> String {
     use rand::prelude::*;
     let mut rng = rand::thread_rng();
@@ -83,3 +269,161 @@ fn random_string(length: usize) -> String {
         .map(char::from)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Made by libsodium `crypto_pwhash_str` with interactive limits, as sodiumoxide did
+    const SODIUM_HASH: &str = "$argon2id$v=19$m=65536,t=2,p=1$37C+l6dv3EKRZVLYgJur9g$X7paUV999LmcldLxSBMrEiNpNzmFf/4iFfXM/il5n3w";
+    const SODIUM_PASSWORD: &str = "correct horse battery staple";
+
+    /// crypt_blowfish test vector
+    const BCRYPT_HASH: &str = "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+    const BCRYPT_PASSWORD: &str = "U*U";
+
+    /// PBKDF2-HMAC-SHA256 test vector of RFC 7914: `passwd`, `salt`, c = 1, dkLen = 64
+    const PBKDF2_HASH: &str = "$pbkdf2-sha256$i=1,l=64$c2FsdA$VawEblbjCJ/sFpHCJUS2BflBhSFt3gRl5oudV8INrLxJypzM8Xm2RZkWZLOdd+8xfHG4RbHjC9UJESBB06GXgw";
+    const PBKDF2_PASSWORD: &str = "passwd";
+
+    /// scrypt test vector of RFC 7914: `password`, `NaCl`, N = 1024, r = 8, p = 16,
+    /// first 32 bytes of the output
+    const SCRYPT_HASH: &str =
+        "$scrypt$ln=10,r=8,p=16$TmFDbA$/bq+HJ00cgB4VucZDQHp/nxq18vII3gw53N2Y0s3MWI";
+    const SCRYPT_PASSWORD: &str = "password";
+
+    fn settings(algorithm: PasswordHashAlgorithm) -> PasswordHashing {
+        PasswordHashing {
+            max_concurrency: 1,
+            algorithm,
+            argon2_memory_cost: 4096,
+            argon2_time_cost: 2,
+            argon2_parallelism: 1,
+            scrypt_log_n: 10,
+            scrypt_r: 8,
+            scrypt_p: 1,
+        }
+    }
+
+    fn generator(settings: PasswordHashing) -> Generator {
+        Generator::try_from(settings).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn generators_share_hashing_limit() {
+        let first = generator(settings(PasswordHashAlgorithm::Argon2id));
+        let second = generator(settings(PasswordHashAlgorithm::Argon2id));
+
+        assert!(Arc::ptr_eq(&first.hashing_permits, &second.hashing_permits));
+
+        // Every test generator allows one hashing at a time
+        let _permit = first.hashing_permits.acquire().await.unwrap();
+        assert_eq!(second.hashing_permits.available_permits(), 0);
+    }
+
+    #[test]
+    fn verifies_legacy_sodium_hash() {
+        let padded = format!("{:\0<128}", SODIUM_HASH);
+
+        assert!(verify_password(SODIUM_HASH, SODIUM_PASSWORD));
+        assert!(verify_password(&padded, SODIUM_PASSWORD));
+        assert!(!verify_password(SODIUM_HASH, "wrong password"));
+    }
+
+    #[test]
+    fn argon2id_hash_round_trips() {
+        let generator = generator(settings(PasswordHashAlgorithm::Argon2id));
+        let hash = hash_password(&generator.new_hash, "password".to_owned()).unwrap();
+
+        assert!(hash.starts_with("$argon2id$v=19$m=4096,t=2,p=1$"));
+        assert!(verify_password(&hash, "password"));
+        assert!(!verify_password(&hash, "wrong password"));
+        assert!(!generator.hash_needs_upgrade(&hash));
+    }
+
+    #[test]
+    fn scrypt_hash_round_trips() {
+        let generator = generator(settings(PasswordHashAlgorithm::Scrypt));
+        let hash = hash_password(&generator.new_hash, "password".to_owned()).unwrap();
+
+        assert!(hash.starts_with("$scrypt$ln=10,r=8,p=1$"));
+        assert!(verify_password(&hash, "password"));
+        assert!(!verify_password(&hash, "wrong password"));
+        assert!(!generator.hash_needs_upgrade(&hash));
+    }
+
+    #[test]
+    fn legacy_sodium_hash_is_kept_with_same_cost() {
+        let generator = generator(PasswordHashing {
+            argon2_memory_cost: 65536,
+            ..settings(PasswordHashAlgorithm::Argon2id)
+        });
+
+        assert!(!generator.hash_needs_upgrade(SODIUM_HASH));
+        assert!(!generator.hash_needs_upgrade(&format!("{:\0<128}", SODIUM_HASH)));
+    }
+
+    #[test]
+    fn hash_is_upgraded_after_cost_change() {
+        let generator = generator(PasswordHashing {
+            argon2_memory_cost: 65536,
+            argon2_time_cost: 3,
+            ..settings(PasswordHashAlgorithm::Argon2id)
+        });
+
+        assert!(generator.hash_needs_upgrade(SODIUM_HASH));
+    }
+
+    #[test]
+    fn hash_is_upgraded_after_algorithm_change() {
+        let argon2id = generator(settings(PasswordHashAlgorithm::Argon2id));
+        let scrypt = generator(settings(PasswordHashAlgorithm::Scrypt));
+        let scrypt_hash = hash_password(&scrypt.new_hash, "password".to_owned()).unwrap();
+
+        assert!(scrypt.hash_needs_upgrade(SODIUM_HASH));
+        assert!(argon2id.hash_needs_upgrade(&scrypt_hash));
+        assert!(argon2id.hash_needs_upgrade("not a hash"));
+    }
+
+    #[test]
+    fn verifies_imported_hashes() {
+        for (hash, password) in [
+            (BCRYPT_HASH, BCRYPT_PASSWORD),
+            (PBKDF2_HASH, PBKDF2_PASSWORD),
+            (SCRYPT_HASH, SCRYPT_PASSWORD),
+        ] {
+            assert!(verify_password(hash, password), "{}", hash);
+            assert!(!verify_password(hash, "wrong password"), "{}", hash);
+        }
+    }
+
+    #[test]
+    fn imported_hashes_are_supported_and_upgraded() {
+        let generator = generator(settings(PasswordHashAlgorithm::Argon2id));
+
+        for hash in [BCRYPT_HASH, PBKDF2_HASH, SCRYPT_HASH, SODIUM_HASH] {
+            assert!(generator.hash_is_supported(hash), "{}", hash);
+        }
+        for hash in [BCRYPT_HASH, PBKDF2_HASH, SCRYPT_HASH] {
+            assert!(generator.hash_needs_upgrade(hash), "{}", hash);
+        }
+        assert!(is_bcrypt(BCRYPT_HASH));
+        assert!(!is_bcrypt(PBKDF2_HASH));
+    }
+
+    #[test]
+    fn unknown_hashes_are_not_supported() {
+        let generator = generator(settings(PasswordHashAlgorithm::Argon2id));
+
+        assert!(!generator.hash_is_supported("$1$saltsalt$2vnaRpHa6Jxjz5n83ok8Z0"));
+        assert!(!generator.hash_is_supported("5f4dcc3b5aa765d61d8327deb882cf99"));
+        assert!(!verify_password(
+            "5f4dcc3b5aa765d61d8327deb882cf99",
+            "password"
+        ));
+        // Full 64 bytes output of the RFC 7914 vector
+        assert!(!generator.hash_is_supported(
+            "$scrypt$ln=10,r=8,p=16$TmFDbA$/bq+HJ00cgB4VucZDQHp/nxq18vII3gw53N2Y0s3MWIurzDZLiKjiG/xCSedmDDaxyevuUqD7m2DYMvfoswGQA"
+        ));
+    }
+}
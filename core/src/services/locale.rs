@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use validator::{ValidationError, ValidationErrors};
+
+/// Locales with translated emails and messages
+pub const LOCALES: &[&str] = &["en", "ru"];
+
+/// Used when none of the preferred locales is supported
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Messages of `validator` codes, `{param}` is replaced with the error param
+const MESSAGES: &[(&str, &str, &str)] = &[
+    ("en", "email", "Must be a valid email address"),
+    ("en", "length_min", "Must be at least {min} characters long"),
+    ("en", "length_max", "Must be at most {max} characters long"),
+    ("en", "range", "Must be between {min} and {max}"),
+    ("en", "locale", "Language is not supported"),
+    ("en", "scope", "Scope is not supported"),
+    ("en", "invalid", "Invalid value"),
+    ("ru", "email", "Введите корректный адрес электронной почты"),
+    ("ru", "length_min", "Должно быть не короче {min} символов"),
+    ("ru", "length_max", "Должно быть не длиннее {max} символов"),
+    ("ru", "range", "Должно быть от {min} до {max}"),
+    ("ru", "locale", "Язык не поддерживается"),
+    ("ru", "scope", "Область доступа не поддерживается"),
+    ("ru", "invalid", "Некорректное значение"),
+];
+
+/// Supported locale for a language tag, region is ignored: `ru-RU` is `ru`
+pub fn supported_locale(tag: &str) -> Option<&'static str> {
+    let language = tag.trim().split(['-', '_']).next()?.to_lowercase();
+
+    LOCALES.iter().copied().find(|locale| *locale == language)
+}
+
+/// The most preferred supported locale from `Accept-Language` header value
+pub fn negotiate_locale(accept_language: &str) -> &'static str {
+    let mut tags = accept_language
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+            match quality > 0.0 {
+                true => Some((tag, quality)),
+                false => None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Stable sort keeps the header order for equal quality
+    tags.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    tags.into_iter()
+        .find_map(|(tag, _)| supported_locale(tag))
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Validator for optional locale fields of forms
+pub fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    match LOCALES.contains(&locale) {
+        true => Ok(()),
+        false => Err(ValidationError::new("locale")),
+    }
+}
+
+/// Messages of invalid fields in the locale, falls back to the default one
+pub fn validation_messages(
+    errors: &ValidationErrors,
+    locale: &str,
+) -> BTreeMap<String, Vec<String>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, errors)| {
+            let messages = errors
+                .iter()
+                .map(|error| validation_message(error, locale))
+                .collect();
+
+            (field.to_owned(), messages)
+        })
+        .collect()
+}
+
+fn validation_message(error: &ValidationError, locale: &str) -> String {
+    let key = match error.code.as_ref() {
+        "length" if !error.params.contains_key("min") => "length_max",
+        "length" => "length_min",
+        code => code,
+    };
+
+    let find = |locale: &str, key: &str| {
+        MESSAGES
+            .iter()
+            .find(|(l, k, _)| *l == locale && *k == key)
+            .map(|(_, _, message)| *message)
+    };
+
+    let template = find(locale, key)
+        .or_else(|| find(DEFAULT_LOCALE, key))
+        .map(ToOwned::to_owned)
+        .or_else(|| error.message.as_ref().map(|message| message.to_string()))
+        .or_else(|| {
+            find(locale, "invalid")
+                .or_else(|| find(DEFAULT_LOCALE, "invalid"))
+                .map(ToOwned::to_owned)
+        })
+        .unwrap_or_else(|| error.code.to_string());
+
+    error
+        .params
+        .iter()
+        .fold(template, |message, (name, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+
+            message.replace(&format!("{{{}}}", name), &value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_by_quality_and_region() {
+        assert_eq!(negotiate_locale("ru-RU,ru;q=0.9,en-US;q=0.8"), "ru");
+        assert_eq!(negotiate_locale("de-DE, en;q=0.5, ru;q=0.7"), "ru");
+        assert_eq!(negotiate_locale("de, fr;q=0.8"), DEFAULT_LOCALE);
+        assert_eq!(negotiate_locale("ru;q=0, en"), "en");
+        assert_eq!(negotiate_locale(""), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn messages_fall_back_to_default_locale() {
+        let mut errors = ValidationErrors::new();
+        let mut length = ValidationError::new("length");
+        length.add_param("min".into(), &8);
+        errors.add("password", length);
+        errors.add("email", ValidationError::new("email"));
+        errors.add("name", ValidationError::new("unknown"));
+
+        let messages = validation_messages(&errors, "ru");
+
+        assert_eq!(
+            messages["password"],
+            vec!["Должно быть не короче 8 символов"]
+        );
+        assert_eq!(
+            messages["email"],
+            vec!["Введите корректный адрес электронной почты"]
+        );
+        assert_eq!(messages["name"], vec!["Некорректное значение"]);
+        assert_eq!(
+            validation_messages(&errors, "de")["password"],
+            vec!["Must be at least 8 characters long"]
+        );
+    }
+}
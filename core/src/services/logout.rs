@@ -0,0 +1,170 @@
+use crate::contracts::{LogoutNotifier, LogoutNotifyError};
+use crate::models::Application;
+use accesso_settings::Logout;
+use async_trait::async_trait;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const BACKCHANNEL_LOGOUT_EVENT: &str = "http://schemas.openid.net/event/backchannel-logout";
+
+#[derive(Clone, Debug)]
+pub struct BackChannelLogout {
+    /// `iss` claim of the logout token
+    pub issuer: String,
+    client: Client,
+}
+
+impl TryFrom<Logout> for BackChannelLogout {
+    type Error = LogoutNotifyError;
+
+    fn try_from(s: Logout) -> Result<Self, Self::Error> {
+        Ok(Self {
+            issuer: s.issuer,
+            client: Client::builder()
+                .timeout(Duration::from_secs(s.request_timeout))
+                .build()?,
+        })
+    }
+}
+
+/// https://openid.net/specs/openid-connect-backchannel-1_0.html#LogoutToken
+#[derive(Debug, Serialize)]
+struct LogoutToken {
+    iss: String,
+    aud: String,
+    sub: String,
+    /// Ended session, omitted when the user is logged out of all sessions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sid: Option<String>,
+    iat: i64,
+    jti: String,
+    events: HashMap<&'static str, HashMap<String, String>>,
+}
+
+impl BackChannelLogout {
+    /// Logout token is signed with HS256 using client secret
+    fn logout_token(
+        &self,
+        application: &Application,
+        user_id: uuid::Uuid,
+        session_id: Option<uuid::Uuid>,
+    ) -> Result<String, LogoutNotifyError> {
+        let mut events = HashMap::new();
+        events.insert(BACKCHANNEL_LOGOUT_EVENT, HashMap::new());
+
+        let claims = LogoutToken {
+            iss: self.issuer.clone(),
+            aud: application.id.to_string(),
+            sub: user_id.to_string(),
+            sid: session_id.map(|id| id.to_string()),
+            iat: chrono::Utc::now().timestamp(),
+            jti: uuid::Uuid::new_v4().to_string(),
+            events,
+        };
+
+        Ok(encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(application.secret_key.as_bytes()),
+        )?)
+    }
+}
+
+#[async_trait]
+impl LogoutNotifier for BackChannelLogout {
+    #[tracing::instrument(skip(self, application), fields(application.id = %application.id))]
+    async fn notify(
+        &self,
+        application: &Application,
+        user_id: uuid::Uuid,
+        session_id: Option<uuid::Uuid>,
+    ) -> Result<(), LogoutNotifyError> {
+        let uri = application
+            .backchannel_logout_uri
+            .as_ref()
+            .ok_or(LogoutNotifyError::NotConfigured)?;
+
+        let logout_token = self.logout_token(application, user_id, session_id)?;
+
+        let response = self
+            .client
+            .post(uri)
+            .header("Cache-Control", "no-store")
+            .form(&[("logout_token", logout_token)])
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(LogoutNotifyError::Rejected(response.status().as_u16()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{decode, DecodingKey, Validation};
+
+    fn application() -> Application {
+        Application {
+            id: uuid::Uuid::new_v4(),
+            is_dev: false,
+            redirect_uri: vec![],
+            title: "Demo".to_owned(),
+            secret_key: "secret".to_owned(),
+            allowed_registrations: true,
+            post_logout_redirect_uri: vec![],
+            backchannel_logout_uri: Some("https://app.example.com/backchannel".to_owned()),
+            require_dpop: false,
+        }
+    }
+
+    fn claims(application: &Application, session_id: Option<uuid::Uuid>) -> serde_json::Value {
+        let logout = BackChannelLogout {
+            issuer: "https://accesso.example.com".to_owned(),
+            client: Client::new(),
+        };
+        let token = logout
+            .logout_token(application, uuid::Uuid::new_v4(), session_id)
+            .unwrap();
+
+        let mut validation = Validation::default();
+        validation.set_audience(&[application.id.to_string()]);
+        validation.set_required_spec_claims(&["aud"]);
+        validation.validate_exp = false;
+
+        decode::<serde_json::Value>(
+            &token,
+            &DecodingKey::from_secret(application.secret_key.as_bytes()),
+            &validation,
+        )
+        .unwrap()
+        .claims
+    }
+
+    #[test]
+    fn logout_token_names_ended_session() {
+        let application = application();
+        let session_id = uuid::Uuid::new_v4();
+
+        let claims = claims(&application, Some(session_id));
+
+        assert_eq!(claims["sid"], session_id.to_string());
+        assert!(claims["events"][BACKCHANNEL_LOGOUT_EVENT].is_object());
+    }
+
+    #[test]
+    fn logout_token_of_all_sessions_has_no_sid() {
+        let application = application();
+
+        let claims = claims(&application, None);
+
+        assert!(claims.get("sid").is_none());
+        assert!(claims["sub"].is_string());
+    }
+}
@@ -1,5 +1,20 @@
+pub mod canonical_email;
+pub mod capture;
+pub mod dpop;
 pub mod email;
+pub mod email_domain;
+pub mod email_templates;
 pub mod generator;
+pub mod locale;
+pub mod logout;
+pub mod sendgrid_webhook;
+pub mod smtp;
 
+pub use canonical_email::canonical_email;
+pub use capture::Capture;
 pub use email::Email;
 pub use generator::Generator;
+pub use locale::{negotiate_locale, DEFAULT_LOCALE};
+pub use logout::BackChannelLogout;
+pub use sendgrid_webhook::SendGridWebhook;
+pub use smtp::Smtp;
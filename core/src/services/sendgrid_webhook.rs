@@ -0,0 +1,205 @@
+//! SendGrid Event Webhook with signature verification
+//! https://docs.sendgrid.com/for-developers/tracking-events/getting-started-event-webhook-security-features
+
+use chrono::TimeZone;
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+use serde::Deserialize;
+
+use crate::models::{EmailEvent, EmailStatus};
+
+pub const SIGNATURE_HEADER: &str = "X-Twilio-Email-Event-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Twilio-Email-Event-Webhook-Timestamp";
+
+const PROVIDER: &str = "sendgrid";
+
+/// DER prefix of `SubjectPublicKeyInfo` with uncompressed P-256 point, the point follows it
+const P256_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum SendGridWebhookError {
+    #[error("Verification key must be base64 encoded P-256 public key")]
+    InvalidPublicKey,
+    #[error("Webhook signature is invalid")]
+    InvalidSignature,
+    #[error(transparent)]
+    InvalidPayload(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Deserialize)]
+struct SendGridEvent {
+    email: String,
+    timestamp: i64,
+    event: String,
+    sg_event_id: String,
+    /// `bounce` or `blocked` for bounce events
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    reason: Option<String>,
+}
+
+/// Checks that events are sent by SendGrid and picks bounces and spam reports from them
+#[derive(Debug, Clone)]
+pub struct SendGridWebhook {
+    public_key: Vec<u8>,
+}
+
+impl SendGridWebhook {
+    /// `public_key` is the verification key from the Mail Settings of SendGrid
+    pub fn new(public_key: &str) -> Result<Self, SendGridWebhookError> {
+        let der = base64::decode(public_key.trim())
+            .map_err(|_| SendGridWebhookError::InvalidPublicKey)?;
+
+        match der.strip_prefix(P256_SPKI_PREFIX) {
+            Some(point) if point.len() == 65 && point[0] == 0x04 => Ok(Self {
+                public_key: point.to_vec(),
+            }),
+            _ => Err(SendGridWebhookError::InvalidPublicKey),
+        }
+    }
+
+    /// Signature covers the timestamp header followed by the raw body.
+    /// Timestamp age is not checked: replayed events are already recorded and ignored
+    pub fn verify(
+        &self,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+    ) -> Result<(), SendGridWebhookError> {
+        let signature =
+            base64::decode(signature.trim()).map_err(|_| SendGridWebhookError::InvalidSignature)?;
+
+        let mut payload = timestamp.as_bytes().to_vec();
+        payload.extend_from_slice(body);
+
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, &self.public_key)
+            .verify(&payload, &signature)
+            .map_err(|_| SendGridWebhookError::InvalidSignature)
+    }
+
+    /// Bounces and spam reports from the verified body, other events are skipped
+    pub fn parse_events(&self, body: &[u8]) -> Result<Vec<EmailEvent>, SendGridWebhookError> {
+        let events: Vec<SendGridEvent> = serde_json::from_slice(body)?;
+
+        Ok(events
+            .into_iter()
+            .filter_map(|event| {
+                let status = event_status(&event)?;
+                let occurred_at = chrono::Utc.timestamp_opt(event.timestamp, 0).single()?;
+
+                Some(EmailEvent {
+                    provider: PROVIDER.to_owned(),
+                    provider_event_id: event.sg_event_id,
+                    email: event.email,
+                    status,
+                    reason: event.reason,
+                    occurred_at,
+                })
+            })
+            .collect())
+    }
+}
+
+fn event_status(event: &SendGridEvent) -> Option<EmailStatus> {
+    match (
+        event.event.as_str(),
+        event.kind.as_deref(),
+        event.reason.as_deref(),
+    ) {
+        // Blocked is a temporary rejection of the server, the address may be fine
+        ("bounce", Some("blocked"), _) => None,
+        ("bounce", _, _) => Some(EmailStatus::Bounced),
+        ("spamreport", _, _) => Some(EmailStatus::Complained),
+        // SendGrid drops emails to addresses on its own suppression lists
+        ("dropped", _, Some("Bounced Address" | "Invalid")) => Some(EmailStatus::Bounced),
+        ("dropped", _, Some("Spam Reporting Address")) => Some(EmailStatus::Complained),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    const BODY: &str = r#"[
+        {"email": "bounced@example.com", "timestamp": 1636977600, "event": "bounce", "type": "bounce", "sg_event_id": "e1", "reason": "550 5.1.1 User unknown"},
+        {"email": "blocked@example.com", "timestamp": 1636977600, "event": "bounce", "type": "blocked", "sg_event_id": "e2"},
+        {"email": "spam@example.com", "timestamp": 1636977601, "event": "spamreport", "sg_event_id": "e3"},
+        {"email": "dropped@example.com", "timestamp": 1636977602, "event": "dropped", "reason": "Bounced Address", "sg_event_id": "e4"},
+        {"email": "opened@example.com", "timestamp": 1636977603, "event": "open", "sg_event_id": "e5"}
+    ]"#;
+
+    fn keys() -> (EcdsaKeyPair, String) {
+        let random = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &random).unwrap();
+        let pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref()).unwrap();
+        let spki = [P256_SPKI_PREFIX, pair.public_key().as_ref()].concat();
+
+        (pair, base64::encode(spki))
+    }
+
+    fn sign(pair: &EcdsaKeyPair, timestamp: &str, body: &str) -> String {
+        let payload = [timestamp.as_bytes(), body.as_bytes()].concat();
+        let signature = pair.sign(&SystemRandom::new(), &payload).unwrap();
+
+        base64::encode(signature.as_ref())
+    }
+
+    #[test]
+    fn verifies_signature_of_timestamp_and_body() {
+        let (pair, public_key) = keys();
+        let webhook = SendGridWebhook::new(&public_key).unwrap();
+        let signature = sign(&pair, "1636977600", BODY);
+
+        assert!(webhook
+            .verify("1636977600", &signature, BODY.as_bytes())
+            .is_ok());
+        assert!(webhook
+            .verify("1636977601", &signature, BODY.as_bytes())
+            .is_err());
+        assert!(webhook.verify("1636977600", &signature, b"[]").is_err());
+        assert!(webhook
+            .verify("1636977600", "garbage", BODY.as_bytes())
+            .is_err());
+        assert!(SendGridWebhook::new("bm90IGEga2V5").is_err());
+    }
+
+    #[test]
+    fn picks_bounces_and_complaints() {
+        let (_, public_key) = keys();
+        let webhook = SendGridWebhook::new(&public_key).unwrap();
+
+        let events = webhook
+            .parse_events(BODY.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|event| (event.provider_event_id, event.email, event.status))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                (
+                    "e1".to_owned(),
+                    "bounced@example.com".to_owned(),
+                    EmailStatus::Bounced
+                ),
+                (
+                    "e3".to_owned(),
+                    "spam@example.com".to_owned(),
+                    EmailStatus::Complained
+                ),
+                (
+                    "e4".to_owned(),
+                    "dropped@example.com".to_owned(),
+                    EmailStatus::Bounced
+                ),
+            ]
+        );
+    }
+}
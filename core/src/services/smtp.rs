@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use accesso_settings::{Email as EmailSettings, Smtp as SmtpSettings, SmtpTls};
+use async_trait::async_trait;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::{Tls, TlsParameters};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::contracts::{EmailMessage, EmailNotification, SendEmailError};
+use crate::services::email_templates::{EmailTemplates, EmailTemplatesError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SmtpError {
+    #[error(transparent)]
+    Templates(#[from] EmailTemplatesError),
+    #[error("Invalid sender: {0}")]
+    InvalidSender(#[from] lettre::address::AddressError),
+    #[error(transparent)]
+    Transport(#[from] lettre::transport::smtp::Error),
+}
+
+/// Renders messages locally and sends them to own SMTP server
+#[derive(Clone)]
+pub struct Smtp {
+    sender: Mailbox,
+    templates: Arc<EmailTemplates>,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl std::fmt::Debug for Smtp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Transport has credentials inside
+        f.debug_struct("Smtp")
+            .field("sender", &self.sender)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Smtp {
+    pub fn new(email: EmailSettings, smtp: SmtpSettings) -> Result<Self, SmtpError> {
+        let tls = match smtp.tls {
+            SmtpTls::None => Tls::None,
+            SmtpTls::StartTls => Tls::Required(TlsParameters::new(smtp.host.clone())?),
+            SmtpTls::Tls => Tls::Wrapper(TlsParameters::new(smtp.host.clone())?),
+        };
+
+        // Dangerous builder only skips default TLS, mode is taken from settings
+        let mut transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host)
+            .port(smtp.port)
+            .tls(tls)
+            .timeout(Some(Duration::from_secs(smtp.timeout)));
+
+        if let (Some(username), Some(password)) = (smtp.username, smtp.password) {
+            transport = transport.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            sender: Mailbox::new(Some(email.sender_name.clone()), email.sender_email.parse()?),
+            templates: Arc::new(EmailTemplates::new(&email)?),
+            transport: transport.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl EmailNotification for Smtp {
+    #[tracing::instrument]
+    async fn send(
+        &self,
+        email: String,
+        locale: String,
+        message: EmailMessage,
+    ) -> Result<(), SendEmailError> {
+        let rendered = self.templates.render(&email, &locale, &message)?;
+
+        let message = Message::builder()
+            .from(self.sender.clone())
+            .to(Mailbox::new(None, email.parse()?))
+            .subject(rendered.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                rendered.text,
+                rendered.html,
+            ))?;
+
+        let response = self.transport.send(message).await?;
+
+        tracing::info!(code = %response.code(), "Email sent");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    use super::*;
+
+    /// Accepts one connection and returns received commands and message lines
+    async fn smtp_stand_in() -> (u16, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut lines = BufReader::new(read).lines();
+            let mut transcript = vec![];
+            let mut data = false;
+
+            write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+            while let Some(line) = lines.next_line().await.unwrap() {
+                let reply: &[u8] = match line.split(' ').next().unwrap() {
+                    "." if data => {
+                        data = false;
+                        b"250 Queued\r\n"
+                    }
+                    _ if data => {
+                        transcript.push(line);
+                        continue;
+                    }
+                    "EHLO" => b"250-localhost\r\n250 AUTH PLAIN LOGIN\r\n",
+                    "AUTH" => b"235 Authenticated\r\n",
+                    "DATA" => {
+                        data = true;
+                        b"354 Go ahead\r\n"
+                    }
+                    "QUIT" => b"221 Bye\r\n",
+                    _ => b"250 OK\r\n",
+                };
+
+                let quit = line == "QUIT";
+                transcript.push(line);
+                write.write_all(reply).await.unwrap();
+
+                if quit {
+                    break;
+                }
+            }
+
+            transcript
+        });
+
+        (port, handle)
+    }
+
+    fn smtp(port: u16, credentials: bool) -> Smtp {
+        Smtp::new(
+            EmailSettings {
+                sender_email: "no-reply@accesso.sova.dev".to_owned(),
+                sender_name: "Accesso".to_owned(),
+                application_host: "accesso.sova.dev".to_owned(),
+                email_confirm_url_prefix: "/register/confirm-".to_owned(),
+                account_unlock_url_prefix: "/login/unlock-".to_owned(),
+                templates_dir: None,
+                transport: accesso_settings::EmailTransport::Smtp,
+                outbox_interval: 5,
+                capture_dir: None,
+            },
+            SmtpSettings {
+                host: "127.0.0.1".to_owned(),
+                port,
+                tls: SmtpTls::None,
+                username: credentials.then(|| "demo".to_owned()),
+                password: credentials.then(|| "secret".to_owned()),
+                timeout: 5,
+            },
+        )
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn sends_rendered_message_with_auth() {
+        let (port, server) = smtp_stand_in().await;
+
+        smtp(port, true)
+            .send(
+                "john@gmail.com".to_owned(),
+                "en".to_owned(),
+                EmailMessage::RegisterConfirmation {
+                    code: "demo-code".to_owned(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let transcript = server.await.unwrap();
+        let has = |expected: &str| transcript.iter().any(|line| line == expected);
+
+        assert!(has(&format!(
+            "AUTH PLAIN {}",
+            base64::encode("\0demo\0secret")
+        )));
+        assert!(has("MAIL FROM:<no-reply@accesso.sova.dev>"));
+        assert!(has("RCPT TO:<john@gmail.com>"));
+        assert!(has("Subject: Confirm registration at Accesso"));
+        assert!(transcript
+            .iter()
+            .any(|line| line.starts_with("Content-Type: multipart/alternative")));
+    }
+
+    #[actix_rt::test]
+    async fn does_not_authenticate_without_credentials() {
+        let (port, server) = smtp_stand_in().await;
+
+        smtp(port, false)
+            .send(
+                "john@gmail.com".to_owned(),
+                "en".to_owned(),
+                EmailMessage::AccountAlreadyExists,
+            )
+            .await
+            .unwrap();
+
+        let transcript = server.await.unwrap();
+
+        assert!(!transcript.iter().any(|line| line.starts_with("AUTH")));
+        assert!(transcript
+            .iter()
+            .any(|line| line == "RCPT TO:<john@gmail.com>"));
+    }
+}
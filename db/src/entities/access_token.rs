@@ -2,12 +2,15 @@ use crate::chrono::Utc;
 use accesso_core::models;
 use sqlx::FromRow;
 
+use super::User;
+
 #[derive(Debug, FromRow)]
 pub(crate) struct AccessToken {
     pub(crate) token: String,
     pub(crate) scopes: Vec<String>,
     pub(crate) expires_at: chrono::DateTime<Utc>,
     pub(crate) registration_id: uuid::Uuid,
+    pub(crate) dpop_jkt: Option<String>,
 }
 
 impl From<models::AccessToken> for AccessToken {
@@ -17,6 +20,7 @@ impl From<models::AccessToken> for AccessToken {
             scopes: token.scopes,
             expires_at: token.expires_at,
             registration_id: token.registration_id,
+            dpop_jkt: token.dpop_jkt,
         }
     }
 }
@@ -28,6 +32,51 @@ impl Into<models::AccessToken> for AccessToken {
             scopes: self.scopes,
             expires_at: self.expires_at,
             registration_id: self.registration_id,
+            dpop_jkt: self.dpop_jkt,
         }
     }
 }
+
+/// Access token joined with the user it is issued for
+#[derive(Debug, FromRow)]
+pub(crate) struct AccessTokenUser {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) email: String,
+    pub(crate) password_hash: String,
+    pub(crate) first_name: String,
+    pub(crate) last_name: String,
+    pub(crate) canonical_email: String,
+    pub(crate) locale: String,
+    pub(crate) email_status: String,
+    pub(crate) email_verified: bool,
+    pub(crate) token: String,
+    pub(crate) scopes: Vec<String>,
+    pub(crate) expires_at: chrono::DateTime<Utc>,
+    pub(crate) registration_id: uuid::Uuid,
+    pub(crate) dpop_jkt: Option<String>,
+}
+
+impl Into<(models::AccessToken, models::User)> for AccessTokenUser {
+    fn into(self) -> (models::AccessToken, models::User) {
+        let token = AccessToken {
+            token: self.token,
+            scopes: self.scopes,
+            expires_at: self.expires_at,
+            registration_id: self.registration_id,
+            dpop_jkt: self.dpop_jkt,
+        };
+        let user = User {
+            id: self.id,
+            email: self.email,
+            password_hash: self.password_hash,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            canonical_email: self.canonical_email,
+            locale: self.locale,
+            email_status: self.email_status,
+            email_verified: self.email_verified,
+        };
+
+        (token.into(), user.into())
+    }
+}
@@ -10,6 +10,9 @@ pub(crate) struct Client {
     pub(crate) secret_key: String,
     pub(crate) title: String,
     pub(crate) allowed_registrations: bool,
+    pub(crate) post_logout_redirect_uri: Vec<String>,
+    pub(crate) backchannel_logout_uri: Option<String>,
+    pub(crate) require_dpop: bool,
 }
 
 impl Into<models::Application> for Client {
@@ -21,6 +24,9 @@ impl Into<models::Application> for Client {
             secret_key: self.secret_key,
             title: self.title,
             allowed_registrations: self.allowed_registrations,
+            post_logout_redirect_uri: self.post_logout_redirect_uri,
+            backchannel_logout_uri: self.backchannel_logout_uri,
+            require_dpop: self.require_dpop,
         }
     }
 }
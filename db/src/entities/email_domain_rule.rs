@@ -0,0 +1,27 @@
+use crate::chrono::Utc;
+use accesso_core::models;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct EmailDomainRule {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) domain: String,
+    pub(crate) kind: String,
+    pub(crate) application_id: Option<uuid::Uuid>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+}
+
+impl Into<models::EmailDomainRule> for EmailDomainRule {
+    fn into(self) -> models::EmailDomainRule {
+        models::EmailDomainRule {
+            id: self.id,
+            domain: self.domain,
+            kind: match self.kind.as_str() {
+                "allow" => models::EmailDomainRuleKind::Allow,
+                _ => models::EmailDomainRuleKind::Deny,
+            },
+            application_id: self.application_id,
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::chrono::Utc;
+use accesso_core::models;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct EmailEvent {
+    pub(crate) provider: String,
+    pub(crate) provider_event_id: String,
+    pub(crate) email: String,
+    pub(crate) status: String,
+    pub(crate) reason: Option<String>,
+    pub(crate) occurred_at: chrono::DateTime<Utc>,
+}
+
+impl Into<models::EmailEvent> for EmailEvent {
+    fn into(self) -> models::EmailEvent {
+        models::EmailEvent {
+            provider: self.provider,
+            provider_event_id: self.provider_event_id,
+            email: self.email,
+            status: models::EmailStatus::parse(&self.status),
+            reason: self.reason,
+            occurred_at: self.occurred_at,
+        }
+    }
+}
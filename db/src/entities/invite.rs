@@ -0,0 +1,24 @@
+use crate::chrono::Utc;
+use accesso_core::models;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct ApplicationInvite {
+    pub(crate) invite: String,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) application_id: Option<uuid::Uuid>,
+    pub(crate) user_id: Option<uuid::Uuid>,
+    pub(crate) registered_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl Into<models::ApplicationInvite> for ApplicationInvite {
+    fn into(self) -> models::ApplicationInvite {
+        models::ApplicationInvite {
+            invite: self.invite,
+            created_at: self.created_at,
+            application_id: self.application_id,
+            user_id: self.user_id,
+            registered_at: self.registered_at,
+        }
+    }
+}
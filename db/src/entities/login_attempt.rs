@@ -0,0 +1,29 @@
+use crate::chrono::Utc;
+use accesso_core::models;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct LoginAttempts {
+    pub(crate) kind: String,
+    pub(crate) key: String,
+    pub(crate) failures: i32,
+    pub(crate) last_failure_at: chrono::DateTime<Utc>,
+    pub(crate) locked_until: Option<chrono::DateTime<Utc>>,
+    pub(crate) unlock_code: Option<String>,
+}
+
+impl Into<models::LoginAttempts> for LoginAttempts {
+    fn into(self) -> models::LoginAttempts {
+        models::LoginAttempts {
+            kind: match self.kind.as_str() {
+                "ip" => models::LoginAttemptKind::Ip,
+                _ => models::LoginAttemptKind::Account,
+            },
+            key: self.key,
+            failures: self.failures,
+            last_failure_at: self.last_failure_at,
+            locked_until: self.locked_until,
+            unlock_code: self.unlock_code,
+        }
+    }
+}
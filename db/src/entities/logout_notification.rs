@@ -0,0 +1,30 @@
+use crate::chrono::Utc;
+use accesso_core::models;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct LogoutNotification {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) client_id: uuid::Uuid,
+    pub(crate) user_id: uuid::Uuid,
+    pub(crate) session_id: Option<uuid::Uuid>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) attempts: i32,
+    pub(crate) next_attempt_at: chrono::DateTime<Utc>,
+    pub(crate) delivered_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl Into<models::LogoutNotification> for LogoutNotification {
+    fn into(self) -> models::LogoutNotification {
+        models::LogoutNotification {
+            id: self.id,
+            client_id: self.client_id,
+            user_id: self.user_id,
+            session_id: self.session_id,
+            created_at: self.created_at,
+            attempts: self.attempts,
+            next_attempt_at: self.next_attempt_at,
+            delivered_at: self.delivered_at,
+        }
+    }
+}
@@ -1,15 +1,29 @@
 mod access_token;
 mod authorization_code;
 mod client;
+mod email_domain_rule;
+mod email_event;
+mod invite;
+mod login_attempt;
+mod logout_notification;
+mod outbox_email;
+mod personal_access_token;
 mod requests;
 mod session_token;
 mod user;
 mod user_registration;
 
-pub(crate) use access_token::AccessToken;
+pub(crate) use access_token::{AccessToken, AccessTokenUser};
 pub(crate) use authorization_code::AuthorizationCode;
 pub(crate) use client::Client;
+pub(crate) use email_domain_rule::EmailDomainRule;
+pub(crate) use email_event::EmailEvent;
+pub(crate) use invite::ApplicationInvite;
+pub(crate) use login_attempt::LoginAttempts;
+pub(crate) use logout_notification::LogoutNotification;
+pub(crate) use outbox_email::OutboxEmail;
+pub(crate) use personal_access_token::{PersonalAccessToken, PersonalAccessTokenUser};
 pub(crate) use requests::RegistrationRequest;
-pub(crate) use session_token::SessionToken;
+pub(crate) use session_token::{SessionToken, SessionUser};
 pub(crate) use user::User;
 pub(crate) use user_registration::UserRegistration;
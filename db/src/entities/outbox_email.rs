@@ -0,0 +1,38 @@
+use crate::chrono::Utc;
+use accesso_core::contracts::EmailMessage;
+use accesso_core::models;
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct OutboxEmail {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) recipient: String,
+    pub(crate) locale: String,
+    pub(crate) message: Json<EmailMessage>,
+    pub(crate) idempotency_key: String,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) attempts: i32,
+    pub(crate) next_attempt_at: chrono::DateTime<Utc>,
+    pub(crate) sent_at: Option<chrono::DateTime<Utc>>,
+    pub(crate) failed_at: Option<chrono::DateTime<Utc>>,
+    pub(crate) last_error: Option<String>,
+}
+
+impl Into<models::OutboxEmail> for OutboxEmail {
+    fn into(self) -> models::OutboxEmail {
+        models::OutboxEmail {
+            id: self.id,
+            recipient: self.recipient,
+            locale: self.locale,
+            message: self.message.0,
+            idempotency_key: self.idempotency_key,
+            created_at: self.created_at,
+            attempts: self.attempts,
+            next_attempt_at: self.next_attempt_at,
+            sent_at: self.sent_at,
+            failed_at: self.failed_at,
+            last_error: self.last_error,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::chrono::Utc;
+use accesso_core::models;
+use sqlx::FromRow;
+
+use super::User;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct PersonalAccessToken {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) user_id: uuid::Uuid,
+    pub(crate) name: String,
+    pub(crate) token: String,
+    pub(crate) scopes: Vec<String>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) expires_at: chrono::DateTime<Utc>,
+    pub(crate) last_used_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl From<models::PersonalAccessToken> for PersonalAccessToken {
+    fn from(token: models::PersonalAccessToken) -> Self {
+        Self {
+            id: token.id,
+            user_id: token.user_id,
+            name: token.name,
+            token: token.token,
+            scopes: token.scopes,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+        }
+    }
+}
+
+impl Into<models::PersonalAccessToken> for PersonalAccessToken {
+    fn into(self) -> models::PersonalAccessToken {
+        models::PersonalAccessToken {
+            id: self.id,
+            user_id: self.user_id,
+            name: self.name,
+            token: self.token,
+            scopes: self.scopes,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            last_used_at: self.last_used_at,
+        }
+    }
+}
+
+/// Personal access token joined with its owner
+#[derive(Debug, FromRow)]
+pub(crate) struct PersonalAccessTokenUser {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) email: String,
+    pub(crate) password_hash: String,
+    pub(crate) first_name: String,
+    pub(crate) last_name: String,
+    pub(crate) canonical_email: String,
+    pub(crate) locale: String,
+    pub(crate) email_status: String,
+    pub(crate) email_verified: bool,
+    pub(crate) token_id: uuid::Uuid,
+    pub(crate) name: String,
+    pub(crate) token: String,
+    pub(crate) scopes: Vec<String>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) expires_at: chrono::DateTime<Utc>,
+    pub(crate) last_used_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl Into<(models::PersonalAccessToken, models::User)> for PersonalAccessTokenUser {
+    fn into(self) -> (models::PersonalAccessToken, models::User) {
+        let token = PersonalAccessToken {
+            id: self.token_id,
+            user_id: self.id,
+            name: self.name,
+            token: self.token,
+            scopes: self.scopes,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            last_used_at: self.last_used_at,
+        };
+        let user = User {
+            id: self.id,
+            email: self.email,
+            password_hash: self.password_hash,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            canonical_email: self.canonical_email,
+            locale: self.locale,
+            email_status: self.email_status,
+            email_verified: self.email_verified,
+        };
+
+        (token.into(), user.into())
+    }
+}
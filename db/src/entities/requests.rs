@@ -1,20 +1,33 @@
 use crate::chrono::Utc;
 use accesso_core::models;
+use accesso_core::services::canonical_email;
 use sqlx::FromRow;
 
 #[derive(Debug, FromRow)]
 pub(crate) struct RegistrationRequest {
     pub(crate) confirmation_code: String,
     pub(crate) email: String,
+    pub(crate) canonical_email: String,
     pub(crate) expires_at: chrono::DateTime<Utc>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) last_sent_at: chrono::DateTime<Utc>,
+    pub(crate) locale: String,
+    pub(crate) email_status: String,
+    pub(crate) invite: Option<String>,
 }
 
 impl From<models::RegisterRequest> for RegistrationRequest {
     fn from(model: models::RegisterRequest) -> Self {
         Self {
             confirmation_code: model.code,
+            canonical_email: canonical_email(&model.email),
             email: model.email,
             expires_at: model.expires_at,
+            created_at: model.created_at,
+            last_sent_at: model.last_sent_at,
+            locale: model.locale,
+            email_status: model.email_status.as_str().to_owned(),
+            invite: model.invite,
         }
     }
 }
@@ -25,6 +38,11 @@ impl Into<models::RegisterRequest> for RegistrationRequest {
             code: self.confirmation_code,
             email: self.email,
             expires_at: self.expires_at,
+            created_at: self.created_at,
+            last_sent_at: self.last_sent_at,
+            locale: self.locale,
+            email_status: models::EmailStatus::parse(&self.email_status),
+            invite: self.invite,
         }
     }
 }
@@ -2,19 +2,35 @@ use crate::chrono::Utc;
 use accesso_core::models;
 use sqlx::FromRow;
 
+use super::User;
+
 #[derive(Debug, FromRow)]
 pub(crate) struct SessionToken {
+    pub(crate) id: uuid::Uuid,
     pub(crate) user_id: uuid::Uuid,
     pub(crate) token: String,
     pub(crate) expires_at: chrono::DateTime<Utc>,
+    pub(crate) absolute_expires_at: chrono::DateTime<Utc>,
+    pub(crate) authenticated_at: chrono::DateTime<Utc>,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) ip: Option<String>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) last_seen_at: chrono::DateTime<Utc>,
 }
 
 impl From<models::SessionToken> for SessionToken {
     fn from(session: models::SessionToken) -> Self {
         Self {
+            id: session.id,
             user_id: session.user_id,
             token: session.token,
             expires_at: session.expires_at,
+            absolute_expires_at: session.absolute_expires_at,
+            authenticated_at: session.authenticated_at,
+            user_agent: session.user_agent,
+            ip: session.ip,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
         }
     }
 }
@@ -22,9 +38,69 @@ impl From<models::SessionToken> for SessionToken {
 impl Into<models::SessionToken> for SessionToken {
     fn into(self) -> models::SessionToken {
         models::SessionToken {
+            id: self.id,
             user_id: self.user_id,
             token: self.token,
             expires_at: self.expires_at,
+            absolute_expires_at: self.absolute_expires_at,
+            authenticated_at: self.authenticated_at,
+            user_agent: self.user_agent,
+            ip: self.ip,
+            created_at: self.created_at,
+            last_seen_at: self.last_seen_at,
         }
     }
 }
+
+/// Session token joined with its owner
+#[derive(Debug, FromRow)]
+pub(crate) struct SessionUser {
+    pub(crate) id: uuid::Uuid,
+    pub(crate) email: String,
+    pub(crate) password_hash: String,
+    pub(crate) first_name: String,
+    pub(crate) last_name: String,
+    pub(crate) canonical_email: String,
+    pub(crate) locale: String,
+    pub(crate) email_status: String,
+    pub(crate) email_verified: bool,
+    pub(crate) session_id: uuid::Uuid,
+    pub(crate) token: String,
+    pub(crate) expires_at: chrono::DateTime<Utc>,
+    pub(crate) absolute_expires_at: chrono::DateTime<Utc>,
+    pub(crate) authenticated_at: chrono::DateTime<Utc>,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) ip: Option<String>,
+    pub(crate) created_at: chrono::DateTime<Utc>,
+    pub(crate) last_seen_at: chrono::DateTime<Utc>,
+}
+
+impl Into<(models::SessionToken, models::User)> for SessionUser {
+    fn into(self) -> (models::SessionToken, models::User) {
+        let session = SessionToken {
+            id: self.session_id,
+            user_id: self.id,
+            token: self.token,
+            expires_at: self.expires_at,
+            absolute_expires_at: self.absolute_expires_at,
+            authenticated_at: self.authenticated_at,
+            user_agent: self.user_agent,
+            ip: self.ip,
+            created_at: self.created_at,
+            last_seen_at: self.last_seen_at,
+        };
+        let user = User {
+            id: self.id,
+            email: self.email,
+            password_hash: self.password_hash,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            canonical_email: self.canonical_email,
+            locale: self.locale,
+            email_status: self.email_status,
+            email_verified: self.email_verified,
+        };
+
+        (session.into(), user.into())
+    }
+}
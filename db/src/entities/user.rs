@@ -9,6 +9,9 @@ pub(crate) struct User {
     pub(crate) first_name: String,
     pub(crate) last_name: String,
     pub(crate) canonical_email: String,
+    pub(crate) locale: String,
+    pub(crate) email_status: String,
+    pub(crate) email_verified: bool,
 }
 
 impl Into<models::User> for User {
@@ -31,6 +34,9 @@ impl Into<models::User> for User {
             password_hash: String::from_utf8(padded.to_vec()).unwrap(),
             first_name: self.first_name,
             last_name: self.last_name,
+            locale: self.locale,
+            email_status: models::EmailStatus::parse(&self.email_status),
+            email_verified: self.email_verified,
         }
     }
 }
@@ -36,6 +36,15 @@ pub fn sqlx_error_to_register_user_error(err: sqlx::Error) -> RegisterUserError
 }
 
 pub fn sqlx_error_to_account_edit_error(err: sqlx::Error) -> UserEditError {
+    use sqlx::error::Error as SqlxError;
+
+    if let SqlxError::Database(ref e) = err {
+        let pg_err = e.downcast_ref::<PgDatabaseError>();
+        if pg_err.code() == SqlState::UNIQUE_VIOLATION.code() {
+            return UserEditError::EmailAlreadyExists;
+        }
+    }
+
     println!("{:#?}", err);
     UserEditError::Unexpected(err.into())
 }
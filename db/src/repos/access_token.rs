@@ -18,14 +18,15 @@ impl AccessTokenRepo for Database {
             // language=PostgreSQL
             r#"
             INSERT INTO access_tokens
-                (token, scopes, expires_at, registration_id)
-            VALUES ($1, $2, $3, $4)
-            RETURNING access_tokens.*
+                (token, scopes, expires_at, registration_id, dpop_jkt)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING token, scopes, expires_at, registration_id, dpop_jkt
             "#,
             token.token,
             &token.scopes,
             token.expires_at,
-            token.registration_id
+            token.registration_id,
+            token.dpop_jkt
         )
         .fetch_one(&self.pool)
         .await?
@@ -39,7 +40,7 @@ impl AccessTokenRepo for Database {
             AccessToken,
             // language=PostgreSQL
             r#"
-            SELECT token, scopes, expires_at, registration_id
+            SELECT token, scopes, expires_at, registration_id, dpop_jkt
             FROM access_tokens
             "#
         )
@@ -58,7 +59,7 @@ impl AccessTokenRepo for Database {
             AccessToken,
             // language=PostgreSQL
             r#"
-            SELECT token, scopes, expires_at, registration_id
+            SELECT token, scopes, expires_at, registration_id, dpop_jkt
             FROM access_tokens
             WHERE registration_id = $1
             "#,
@@ -89,4 +90,24 @@ impl AccessTokenRepo for Database {
         .await?
         .rows_affected())
     }
+
+    async fn access_tokens_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM access_tokens
+            WHERE ctid IN (SELECT ctid FROM access_tokens WHERE expires_at < $1 LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
 }
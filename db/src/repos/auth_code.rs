@@ -51,4 +51,24 @@ impl AuthCodeRepo for Database {
         .await?
         .map(Into::into))
     }
+
+    async fn auth_codes_delete_created_before(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM authorization_codes
+            WHERE ctid IN (SELECT ctid FROM authorization_codes WHERE created_at < $1 LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
 }
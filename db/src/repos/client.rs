@@ -24,7 +24,10 @@ impl ApplicationRepo for Database {
                    redirect_uri,
                    secret_key,
                    title,
-                   allowed_registrations
+                   allowed_registrations,
+                   post_logout_redirect_uri,
+                   backchannel_logout_uri,
+                   require_dpop
             FROM clients
             WHERE id = $1
             "#,
@@ -40,7 +43,8 @@ impl ApplicationRepo for Database {
             entities::Client,
             // language=PostgreSQL
             r#"
-            SELECT id, is_dev, redirect_uri, secret_key, title, allowed_registrations
+            SELECT id, is_dev, redirect_uri, secret_key, title, allowed_registrations,
+                   post_logout_redirect_uri, backchannel_logout_uri, require_dpop
             FROM clients
             "#,
         )
@@ -58,7 +62,8 @@ impl ApplicationRepo for Database {
             entities::Client,
             // language=PostgreSQL
             r#"
-            SELECT id, is_dev, redirect_uri, secret_key, title, allowed_registrations
+            SELECT id, is_dev, redirect_uri, secret_key, title, allowed_registrations,
+                   post_logout_redirect_uri, backchannel_logout_uri, require_dpop
             FROM clients
             WHERE allowed_registrations = true AND is_dev = false
             "#
@@ -78,7 +83,8 @@ impl ApplicationRepo for Database {
             entities::Client,
             // language=PostgreSQL
             r#"
-            SELECT clients.id, is_dev, redirect_uri, secret_key, title, allowed_registrations
+            SELECT clients.id, is_dev, redirect_uri, secret_key, title, allowed_registrations,
+                   post_logout_redirect_uri, backchannel_logout_uri, require_dpop
             FROM clients
             LEFT JOIN user_registrations ON clients.id = user_registrations.client_id
             WHERE user_registrations.user_id = $1
@@ -100,15 +106,20 @@ impl ApplicationRepo for Database {
             entities::Client,
             // language=PostgreSQL
             r#"
-            INSERT INTO clients (is_dev, redirect_uri, title, secret_key, allowed_registrations)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, is_dev, redirect_uri, title, secret_key, allowed_registrations
+            INSERT INTO clients (is_dev, redirect_uri, title, secret_key, allowed_registrations,
+                                 post_logout_redirect_uri, backchannel_logout_uri, require_dpop)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, is_dev, redirect_uri, title, secret_key, allowed_registrations,
+                      post_logout_redirect_uri, backchannel_logout_uri, require_dpop
             "#,
             application.is_dev,
             &application.redirect_uri,
             application.title,
             application.secret_key,
             application.allowed_registrations,
+            &application.post_logout_redirect_uri,
+            application.backchannel_logout_uri,
+            application.require_dpop,
         )
         .fetch_one(&self.pool)
         .await
@@ -125,16 +136,21 @@ impl ApplicationRepo for Database {
             entities::Client,
             // language=PostgreSQL
             r#"
-            UPDATE clients SET (is_dev, redirect_uri, title, secret_key, allowed_registrations)
-            = ($1, $2, $3, $4, $5)
-            WHERE id = $6
-            RETURNING id, is_dev, redirect_uri, title, secret_key, allowed_registrations
+            UPDATE clients SET (is_dev, redirect_uri, title, secret_key, allowed_registrations,
+                                post_logout_redirect_uri, backchannel_logout_uri, require_dpop)
+            = ($1, $2, $3, $4, $5, $6, $7, $8)
+            WHERE id = $9
+            RETURNING id, is_dev, redirect_uri, title, secret_key, allowed_registrations,
+                      post_logout_redirect_uri, backchannel_logout_uri, require_dpop
             "#,
             form.is_dev,
             &form.redirect_uri,
             form.title,
             form.secret_key,
             form.allowed_registrations,
+            &form.post_logout_redirect_uri,
+            form.backchannel_logout_uri,
+            form.require_dpop,
             id,
         )
         .fetch_optional(&self.pool)
@@ -0,0 +1,55 @@
+use accesso_core::contracts::repo::DPoPProofRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+
+use crate::Database;
+
+#[async_trait]
+impl DPoPProofRepo for Database {
+    async fn dpop_proof_use(
+        &self,
+        jkt: String,
+        jti: String,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        let inserted = sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO dpop_proofs
+                (jkt, jti, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (jkt, jti) DO NOTHING
+            "#,
+            jkt,
+            jti,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        Ok(inserted > 0)
+    }
+
+    async fn dpop_proofs_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM dpop_proofs
+            WHERE ctid IN (SELECT ctid
+                           FROM dpop_proofs
+                           WHERE expires_at < $1
+                           LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+}
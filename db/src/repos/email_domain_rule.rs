@@ -0,0 +1,94 @@
+use accesso_core::contracts::repo::EmailDomainRuleRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+use accesso_core::models;
+
+use crate::entities::EmailDomainRule;
+use crate::Database;
+
+#[async_trait]
+impl EmailDomainRuleRepo for Database {
+    async fn email_domain_rules_list(
+        &self,
+    ) -> Result<Vec<models::EmailDomainRule>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            EmailDomainRule,
+            // language=PostgreSQL
+            r#"
+            SELECT id, domain, kind, application_id, created_at
+            FROM email_domain_rules
+            ORDER BY application_id NULLS FIRST, domain
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn email_domain_rules_for(
+        &self,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<models::EmailDomainRule>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            EmailDomainRule,
+            // language=PostgreSQL
+            r#"
+            SELECT id, domain, kind, application_id, created_at
+            FROM email_domain_rules
+            WHERE application_id IS NULL
+               OR application_id = $1
+            "#,
+            application_id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn email_domain_rule_create(
+        &self,
+        rule: models::EmailDomainRule,
+    ) -> Result<Option<models::EmailDomainRule>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            EmailDomainRule,
+            // language=PostgreSQL
+            r#"
+            INSERT INTO email_domain_rules (id, domain, kind, application_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING
+            RETURNING id, domain, kind, application_id, created_at
+            "#,
+            rule.id,
+            rule.domain,
+            rule.kind.as_str(),
+            rule.application_id,
+            rule.created_at
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Into::into))
+    }
+
+    async fn email_domain_rule_delete(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<models::EmailDomainRule>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            EmailDomainRule,
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM email_domain_rules
+            WHERE id = $1
+            RETURNING id, domain, kind, application_id, created_at
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Into::into))
+    }
+}
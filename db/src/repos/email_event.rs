@@ -0,0 +1,174 @@
+use accesso_core::contracts::repo::EmailEventRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+use accesso_core::models;
+use accesso_core::services::canonical_email;
+
+use crate::entities::EmailEvent;
+use crate::Database;
+
+#[async_trait]
+impl EmailEventRepo for Database {
+    async fn email_event_record(
+        &self,
+        event: models::EmailEvent,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        let canonical = canonical_email(&event.email);
+        let mut transaction = self.pool.begin().await?;
+
+        let inserted = sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO email_events
+                (provider, provider_event_id, email, canonical_email, status, reason, occurred_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (provider, provider_event_id) DO NOTHING
+            "#,
+            event.provider,
+            event.provider_event_id,
+            event.email,
+            canonical,
+            event.status.as_str(),
+            event.reason,
+            event.occurred_at
+        )
+        .execute(&mut transaction)
+        .await?
+        .rows_affected()
+            > 0;
+
+        if inserted {
+            sqlx::query!(
+                // language=PostgreSQL
+                r#"
+                INSERT INTO email_suppressions (canonical_email, status)
+                VALUES ($1, $2)
+                ON CONFLICT (canonical_email) DO UPDATE
+                    SET status     = excluded.status,
+                        updated_at = now()
+                "#,
+                canonical,
+                event.status.as_str()
+            )
+            .execute(&mut transaction)
+            .await?;
+
+            sqlx::query!(
+                // language=PostgreSQL
+                r#"
+                UPDATE users
+                SET email_status = $2
+                WHERE canonical_email = $1
+                "#,
+                canonical,
+                event.status.as_str()
+            )
+            .execute(&mut transaction)
+            .await?;
+
+            sqlx::query!(
+                // language=PostgreSQL
+                r#"
+                UPDATE registration_requests
+                SET email_status = $2
+                WHERE canonical_email = $1
+                "#,
+                canonical,
+                event.status.as_str()
+            )
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(inserted)
+    }
+
+    async fn email_status_get(
+        &self,
+        email: String,
+    ) -> Result<models::EmailStatus, UnexpectedDatabaseError> {
+        let status = sqlx::query_scalar!(
+            // language=PostgreSQL
+            r#"
+            SELECT status
+            FROM email_suppressions
+            WHERE canonical_email = $1
+            "#,
+            canonical_email(&email)
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(status
+            .map(|status| models::EmailStatus::parse(&status))
+            .unwrap_or(models::EmailStatus::Deliverable))
+    }
+
+    async fn email_events_list(
+        &self,
+        email: String,
+    ) -> Result<Vec<models::EmailEvent>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            EmailEvent,
+            // language=PostgreSQL
+            r#"
+            SELECT provider, provider_event_id, email, status, reason, occurred_at
+            FROM email_events
+            WHERE canonical_email = $1
+            ORDER BY occurred_at DESC
+            "#,
+            canonical_email(&email)
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn email_status_reset(&self, email: String) -> Result<(), UnexpectedDatabaseError> {
+        let canonical = canonical_email(&email);
+        let mut transaction = self.pool.begin().await?;
+
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM email_suppressions
+            WHERE canonical_email = $1
+            "#,
+            canonical
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE users
+            SET email_status = 'deliverable'
+            WHERE canonical_email = $1
+            "#,
+            canonical
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE registration_requests
+            SET email_status = 'deliverable'
+            WHERE canonical_email = $1
+            "#,
+            canonical
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+}
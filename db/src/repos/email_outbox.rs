@@ -0,0 +1,147 @@
+use accesso_core::contracts::repo::EmailOutboxRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+use accesso_core::models;
+use sqlx::types::Json;
+use sqlx::{Executor, Postgres};
+
+use crate::entities::OutboxEmail;
+use crate::Database;
+
+/// Queues email with the executor of the business change, usually a transaction
+pub(crate) async fn enqueue<'e, E>(
+    executor: E,
+    email: &models::OutboxEmailForm,
+) -> Result<bool, sqlx::Error>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    Ok(sqlx::query!(
+        // language=PostgreSQL
+        r#"
+        INSERT INTO email_outbox (recipient, locale, message, idempotency_key)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (idempotency_key) DO NOTHING
+        "#,
+        email.recipient,
+        email.locale,
+        Json(&email.message) as _,
+        email.idempotency_key
+    )
+    .execute(executor)
+    .await?
+    .rows_affected()
+        > 0)
+}
+
+#[async_trait]
+impl EmailOutboxRepo for Database {
+    async fn email_outbox_enqueue(
+        &self,
+        email: models::OutboxEmailForm,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        Ok(enqueue(&self.pool, &email).await?)
+    }
+
+    async fn email_outbox_claim_pending(
+        &self,
+        limit: i64,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<models::OutboxEmail>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            OutboxEmail,
+            // language=PostgreSQL
+            r#"
+            UPDATE email_outbox
+            SET next_attempt_at = $2
+            WHERE id IN (SELECT id
+                         FROM email_outbox
+                         WHERE sent_at IS NULL
+                           AND failed_at IS NULL
+                           AND next_attempt_at <= now()
+                         ORDER BY next_attempt_at
+                         LIMIT $1 FOR UPDATE SKIP LOCKED)
+            RETURNING id,
+                      recipient,
+                      locale,
+                      message as "message: Json<accesso_core::contracts::EmailMessage>",
+                      idempotency_key,
+                      created_at,
+                      attempts,
+                      next_attempt_at,
+                      sent_at,
+                      failed_at,
+                      last_error
+            "#,
+            limit,
+            locked_until
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn email_outbox_mark_sent(&self, id: uuid::Uuid) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE email_outbox
+            SET sent_at  = now(),
+                attempts = attempts + 1
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn email_outbox_mark_failed(
+        &self,
+        id: uuid::Uuid,
+        error: String,
+        next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE email_outbox
+            SET attempts        = attempts + 1,
+                last_error      = $2,
+                next_attempt_at = coalesce($3, next_attempt_at),
+                failed_at       = CASE WHEN $3::timestamptz IS NULL THEN now() END
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            next_attempt_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn email_outbox_delete_sent(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM email_outbox
+            WHERE id IN (SELECT id FROM email_outbox WHERE sent_at < $1 LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+}
@@ -0,0 +1,93 @@
+use accesso_core::contracts::repo::InviteRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+use accesso_core::models;
+
+use crate::entities::ApplicationInvite;
+use crate::Database;
+
+#[async_trait]
+impl InviteRepo for Database {
+    async fn invite_create(
+        &self,
+        invite: String,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Option<models::ApplicationInvite>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            ApplicationInvite,
+            // language=PostgreSQL
+            r#"
+            INSERT INTO application_invites (invite, application_id)
+            VALUES ($1, $2)
+            ON CONFLICT (invite) DO NOTHING
+            RETURNING invite, created_at, application_id, user_id, registered_at
+            "#,
+            invite,
+            application_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Into::into))
+    }
+
+    async fn invite_get(
+        &self,
+        invite: String,
+    ) -> Result<Option<models::ApplicationInvite>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            ApplicationInvite,
+            // language=PostgreSQL
+            r#"
+            SELECT invite, created_at, application_id, user_id, registered_at
+            FROM application_invites
+            WHERE invite = $1
+            "#,
+            invite
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Into::into))
+    }
+
+    async fn invites_list(
+        &self,
+        application_id: Option<uuid::Uuid>,
+    ) -> Result<Vec<models::ApplicationInvite>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            ApplicationInvite,
+            // language=PostgreSQL
+            r#"
+            SELECT invite, created_at, application_id, user_id, registered_at
+            FROM application_invites
+            WHERE $1::uuid IS NULL
+               OR application_id = $1
+            ORDER BY created_at DESC
+            "#,
+            application_id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn invite_delete(
+        &self,
+        invite: String,
+    ) -> Result<Option<models::ApplicationInvite>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            ApplicationInvite,
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM application_invites
+            WHERE invite = $1
+            RETURNING invite, created_at, application_id, user_id, registered_at
+            "#,
+            invite
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Into::into))
+    }
+}
@@ -0,0 +1,159 @@
+use accesso_core::contracts::repo::LoginAttemptRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+use accesso_core::models;
+
+use crate::entities::LoginAttempts;
+use crate::Database;
+
+#[async_trait]
+impl LoginAttemptRepo for Database {
+    async fn login_attempts_get(
+        &self,
+        kind: models::LoginAttemptKind,
+        key: String,
+    ) -> Result<Option<models::LoginAttempts>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            LoginAttempts,
+            // language=PostgreSQL
+            r#"
+            SELECT kind, key, failures, last_failure_at, locked_until, unlock_code
+            FROM login_attempts
+            WHERE kind = $1
+              AND key = $2
+            "#,
+            kind.as_str(),
+            key
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Into::into))
+    }
+
+    async fn login_attempt_register_failure(
+        &self,
+        kind: models::LoginAttemptKind,
+        key: String,
+        reset_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<models::LoginAttempts, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            LoginAttempts,
+            // language=PostgreSQL
+            r#"
+            INSERT INTO login_attempts (kind, key, failures, last_failure_at)
+            VALUES ($1, $2, 1, now())
+            ON CONFLICT (kind, key) DO UPDATE
+                SET failures        = CASE
+                                          WHEN login_attempts.last_failure_at < $3
+                                              OR login_attempts.locked_until < now() THEN 1
+                                          ELSE login_attempts.failures + 1 END,
+                    locked_until    = CASE
+                                          WHEN login_attempts.locked_until < now() THEN NULL
+                                          ELSE login_attempts.locked_until END,
+                    unlock_code     = CASE
+                                          WHEN login_attempts.locked_until < now() THEN NULL
+                                          ELSE login_attempts.unlock_code END,
+                    last_failure_at = now()
+            RETURNING kind, key, failures, last_failure_at, locked_until, unlock_code
+            "#,
+            kind.as_str(),
+            key,
+            reset_before
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .into())
+    }
+
+    async fn login_attempt_lock(
+        &self,
+        kind: models::LoginAttemptKind,
+        key: String,
+        locked_until: chrono::DateTime<chrono::Utc>,
+        unlock_code: Option<String>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE login_attempts
+            SET locked_until = $3,
+                unlock_code  = $4
+            WHERE kind = $1
+              AND key = $2
+            "#,
+            kind.as_str(),
+            key,
+            locked_until,
+            unlock_code
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn login_attempts_clear(
+        &self,
+        kind: models::LoginAttemptKind,
+        key: String,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM login_attempts
+            WHERE kind = $1
+              AND key = $2
+            "#,
+            kind.as_str(),
+            key
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn login_attempts_unlock_by_code(
+        &self,
+        unlock_code: String,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        let result = sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM login_attempts
+            WHERE unlock_code = $1
+              AND locked_until > now()
+            "#,
+            unlock_code
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn login_attempts_delete_stale(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM login_attempts
+            WHERE ctid IN (SELECT ctid
+                           FROM login_attempts
+                           WHERE last_failure_at < $1
+                             AND (locked_until IS NULL OR locked_until < $1)
+                           LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+}
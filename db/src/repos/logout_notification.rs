@@ -0,0 +1,128 @@
+use accesso_core::contracts::repo::LogoutNotificationRepo;
+use accesso_core::contracts::UnexpectedDatabaseError;
+use accesso_core::models;
+
+use crate::entities::LogoutNotification;
+use crate::Database;
+
+#[async_trait]
+impl LogoutNotificationRepo for Database {
+    async fn logout_notifications_create_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO logout_notifications (client_id, user_id)
+            SELECT clients.id, user_registrations.user_id
+            FROM user_registrations
+                     INNER JOIN clients ON clients.id = user_registrations.client_id
+            WHERE user_registrations.user_id = $1
+              AND clients.backchannel_logout_uri IS NOT NULL
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn logout_notifications_create_for_session(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO logout_notifications (client_id, user_id, session_id)
+            SELECT clients.id, session_tokens.user_id, session_tokens.id
+            FROM session_clients
+                     INNER JOIN session_tokens ON session_tokens.id = session_clients.session_id
+                     INNER JOIN clients ON clients.id = session_clients.client_id
+            WHERE session_tokens.id = $2
+              AND session_tokens.user_id = $1
+              AND clients.backchannel_logout_uri IS NOT NULL
+            "#,
+            user_id,
+            session_id
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn logout_notifications_claim_pending(
+        &self,
+        limit: i64,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<models::LogoutNotification>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            LogoutNotification,
+            // language=PostgreSQL
+            r#"
+            UPDATE logout_notifications
+            SET next_attempt_at = $3
+            WHERE id IN (SELECT id
+                         FROM logout_notifications
+                         WHERE delivered_at IS NULL
+                           AND attempts < $1
+                           AND next_attempt_at <= now()
+                         ORDER BY next_attempt_at
+                         LIMIT $2 FOR UPDATE SKIP LOCKED)
+            RETURNING id, client_id, user_id, session_id, created_at, attempts, next_attempt_at, delivered_at
+            "#,
+            models::LogoutNotification::MAX_ATTEMPTS,
+            limit,
+            locked_until
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn logout_notification_mark_delivered(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE logout_notifications
+            SET delivered_at = now(),
+                attempts     = attempts + 1
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn logout_notification_mark_failed(
+        &self,
+        id: uuid::Uuid,
+        next_attempt_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE logout_notifications
+            SET attempts        = attempts + 1,
+                next_attempt_at = $2
+            WHERE id = $1
+            "#,
+            id,
+            next_attempt_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,14 @@
 mod access_token;
 mod auth_code;
 mod client;
+mod dpop_proof;
+mod email_domain_rule;
+mod email_event;
+mod email_outbox;
+mod invite;
+mod login_attempt;
+mod logout_notification;
+mod personal_access_token;
 mod requests;
 mod session;
 mod user;
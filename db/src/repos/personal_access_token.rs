@@ -0,0 +1,132 @@
+use accesso_core::contracts::repo::PersonalAccessTokenRepo;
+use accesso_core::contracts::{GetUserBySessionError, UnexpectedDatabaseError};
+use accesso_core::models;
+
+use crate::entities::{PersonalAccessToken, PersonalAccessTokenUser};
+use crate::mappers::sqlx_error_to_get_user_by_session_error;
+use crate::Database;
+
+#[async_trait]
+impl PersonalAccessTokenRepo for Database {
+    async fn personal_access_token_create(
+        &self,
+        token: models::PersonalAccessToken,
+    ) -> Result<models::PersonalAccessToken, UnexpectedDatabaseError> {
+        let token = PersonalAccessToken::from(token);
+        Ok(sqlx::query_as!(
+            PersonalAccessToken,
+            // language=PostgreSQL
+            r#"
+            INSERT INTO personal_access_tokens
+                (id, user_id, name, token, scopes, created_at, expires_at, last_used_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, user_id, name, token, scopes, created_at, expires_at, last_used_at
+            "#,
+            token.id,
+            token.user_id,
+            token.name,
+            token.token,
+            &token.scopes,
+            token.created_at,
+            token.expires_at,
+            token.last_used_at
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .into())
+    }
+
+    async fn personal_access_tokens_list_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<models::PersonalAccessToken>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            PersonalAccessToken,
+            // language=PostgreSQL
+            r#"
+            SELECT id, user_id, name, token, scopes, created_at, expires_at, last_used_at
+            FROM personal_access_tokens
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn personal_access_token_delete(
+        &self,
+        user_id: uuid::Uuid,
+        token_id: uuid::Uuid,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        let deleted = sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM personal_access_tokens
+            WHERE id = $1
+              AND user_id = $2
+            "#,
+            token_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        Ok(deleted > 0)
+    }
+
+    async fn get_user_by_personal_access_token(
+        &self,
+        token: String,
+    ) -> Result<(models::PersonalAccessToken, models::User), GetUserBySessionError> {
+        sqlx::query_as!(
+            PersonalAccessTokenUser,
+            // language=PostgreSQL
+            r#"
+            WITH touched AS (
+                -- Not every request writes the row, last use is tracked with this precision
+                UPDATE personal_access_tokens
+                SET last_used_at = now()
+                WHERE token = $1
+                  AND expires_at > now()
+                  AND (last_used_at IS NULL OR last_used_at < $2)
+            )
+            SELECT users.id,
+                   users.email,
+                   users.password_hash,
+                   users.first_name,
+                   users.last_name,
+                   users.canonical_email,
+                   users.locale,
+                   users.email_status,
+                   users.email_verified,
+                   personal_access_tokens.id AS token_id,
+                   personal_access_tokens.name,
+                   personal_access_tokens.token,
+                   personal_access_tokens.scopes,
+                   personal_access_tokens.created_at,
+                   personal_access_tokens.expires_at,
+                   personal_access_tokens.last_used_at
+            FROM personal_access_tokens
+                     INNER JOIN users ON users.id = personal_access_tokens.user_id
+            WHERE personal_access_tokens.token = $1
+              AND personal_access_tokens.expires_at > now()
+            "#,
+            token,
+            chrono::Utc::now()
+                - chrono::Duration::minutes(
+                    models::PersonalAccessToken::LAST_USED_PRECISION_MINUTES
+                )
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map(Into::into)
+        .map_err(sqlx_error_to_get_user_by_session_error)
+    }
+}
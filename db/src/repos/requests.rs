@@ -3,8 +3,11 @@ use accesso_core::contracts::{SaveRegisterRequestError, UnexpectedDatabaseError}
 use accesso_core::models;
 use accesso_core::models::RegisterRequest;
 
+use accesso_core::services::canonical_email;
+
 use crate::entities::RegistrationRequest;
 use crate::mappers::sqlx_error_to_save_register_request_error;
+use crate::repos::email_outbox;
 use crate::Database;
 
 #[async_trait]
@@ -12,42 +15,74 @@ impl RequestsRepo for Database {
     async fn register_request_save(
         &self,
         request: models::RegisterRequest,
+        email: Option<models::OutboxEmailForm>,
     ) -> Result<models::RegisterRequest, SaveRegisterRequestError> {
         let request = RegistrationRequest::from(request);
+        let mut transaction = self
+            .pool
+            .begin()
+            .await
+            .map_err(sqlx_error_to_save_register_request_error)?;
 
-        Ok(sqlx::query_as!(
+        let saved: models::RegisterRequest = sqlx::query_as!(
             RegistrationRequest,
             // language=PostgreSQL
             r#"
             INSERT INTO registration_requests
-                (confirmation_code, email, expires_at)
-            VALUES ($1, $2, $3)
-            RETURNING confirmation_code, email, expires_at
+                (confirmation_code, email, canonical_email, expires_at, created_at, last_sent_at, locale, invite, email_status)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $9,
+                    -- Bounced email stays bounced for new requests until reset
+                    coalesce((SELECT status
+                              FROM email_suppressions
+                              WHERE canonical_email = $3::varchar), $8))
+            RETURNING confirmation_code, email, canonical_email, expires_at, created_at, last_sent_at, locale, email_status, invite
             "#,
             request.confirmation_code,
             request.email,
-            request.expires_at
+            request.canonical_email,
+            request.expires_at,
+            request.created_at,
+            request.last_sent_at,
+            request.locale,
+            request.email_status,
+            request.invite
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut transaction)
         .await
-        .map_err(sqlx_error_to_save_register_request_error)
-        .map(Into::into)?)
+        .map_err(sqlx_error_to_save_register_request_error)?
+        .into();
+
+        if let Some(email) = email {
+            email_outbox::enqueue(&mut transaction, &email)
+                .await
+                .map_err(sqlx_error_to_save_register_request_error)?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(sqlx_error_to_save_register_request_error)?;
+
+        Ok(saved)
     }
 
-    async fn register_request_get_by_code(
+    async fn register_request_get_by_email_and_code(
         &self,
+        email: String,
         code: String,
     ) -> Result<Option<models::RegisterRequest>, UnexpectedDatabaseError> {
         Ok(sqlx::query_as!(
             RegistrationRequest,
             // language=PostgreSQL
             r#"
-            SELECT confirmation_code, email, expires_at
+            SELECT confirmation_code, email, canonical_email, expires_at, created_at, last_sent_at, locale, email_status, invite
             FROM registration_requests
             WHERE confirmation_code = $1
-              AND expires_at > $2
+              AND canonical_email = $2
+              AND expires_at > $3
             "#,
             code,
+            canonical_email(&email),
             chrono::Utc::now()
         )
         .fetch_optional(&self.pool)
@@ -64,12 +99,13 @@ impl RequestsRepo for Database {
             RegistrationRequest,
             // language=PostgreSQL
             r#"
-            SELECT confirmation_code, email, expires_at
+            SELECT confirmation_code, email, canonical_email, expires_at, created_at, last_sent_at, locale, email_status, invite
             FROM registration_requests
-            WHERE email = $1
+            WHERE canonical_email = $1
+            ORDER BY created_at DESC
             LIMIT $2
             "#,
-            email,
+            canonical_email(&email),
             count as i16
         )
         .fetch_all(&self.pool)
@@ -79,6 +115,135 @@ impl RequestsRepo for Database {
         .collect())
     }
 
+    async fn register_failures_count(
+        &self,
+        email: String,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<i32, UnexpectedDatabaseError> {
+        Ok(sqlx::query_scalar!(
+            // language=PostgreSQL
+            r#"
+            SELECT failed_attempts
+            FROM registration_failures
+            WHERE canonical_email = $1
+              AND window_started_at >= $2
+            "#,
+            canonical_email(&email),
+            since
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .unwrap_or(0))
+    }
+
+    async fn register_requests_register_failure(
+        &self,
+        email: String,
+        max_attempts: i32,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        let canonical_email = canonical_email(&email);
+        let mut transaction = self.pool.begin().await?;
+
+        // Without a pending request there is nothing to guess,
+        // so anyone can't block registration of a mailbox by sending wrong codes
+        let failed_attempts = sqlx::query_scalar!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO registration_failures AS failures
+                (canonical_email, failed_attempts, window_started_at)
+            SELECT $1::varchar, 1, now()
+            WHERE EXISTS(SELECT 1
+                         FROM registration_requests
+                         WHERE canonical_email = $1
+                           AND expires_at > now())
+            ON CONFLICT (canonical_email) DO UPDATE
+                SET failed_attempts   = CASE
+                                            WHEN failures.window_started_at < $2 THEN 1
+                                            ELSE failures.failed_attempts + 1 END,
+                    window_started_at = CASE
+                                            WHEN failures.window_started_at < $2 THEN now()
+                                            ELSE failures.window_started_at END
+            RETURNING failed_attempts
+            "#,
+            canonical_email,
+            since
+        )
+        .fetch_optional(&mut transaction)
+        .await?;
+
+        let deleted = if failed_attempts >= Some(max_attempts) {
+            sqlx::query!(
+                // language=PostgreSQL
+                r#"
+                DELETE
+                FROM registration_requests
+                WHERE canonical_email = $1
+                "#,
+                canonical_email
+            )
+            .execute(&mut transaction)
+            .await?
+            .rows_affected()
+        } else {
+            0
+        };
+
+        transaction.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn register_requests_keep_latest(
+        &self,
+        email: String,
+        count: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM registration_requests
+            WHERE canonical_email = $1
+              AND confirmation_code NOT IN (SELECT confirmation_code
+                                            FROM registration_requests
+                                            WHERE canonical_email = $1
+                                            ORDER BY created_at DESC
+                                            LIMIT $2)
+            "#,
+            canonical_email(&email),
+            count
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn register_request_mark_sent(
+        &self,
+        code: String,
+        email: models::OutboxEmailForm,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        let mut transaction = self.pool.begin().await?;
+
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE registration_requests
+            SET last_sent_at = now()
+            WHERE confirmation_code = $1
+            "#,
+            code
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        email_outbox::enqueue(&mut transaction, &email).await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
     async fn register_requests_delete_all_for_email(
         &self,
         email: String,
@@ -88,9 +253,9 @@ impl RequestsRepo for Database {
             r#"
             DELETE
             FROM registration_requests
-            WHERE email = $1
+            WHERE canonical_email = $1
             "#,
-            email
+            canonical_email(&email)
         )
         .execute(&self.pool)
         .await?
@@ -159,4 +324,44 @@ impl RequestsRepo for Database {
         .await?
         .map(Into::into))
     }
+
+    async fn register_requests_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM registration_requests
+            WHERE ctid IN (SELECT ctid FROM registration_requests WHERE expires_at < $1 LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn register_failures_delete_stale(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM registration_failures
+            WHERE ctid IN (SELECT ctid FROM registration_failures WHERE window_started_at < $1 LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
 }
@@ -2,7 +2,7 @@ use accesso_core::contracts::repo::SessionRepo;
 use accesso_core::contracts::{GetUserBySessionError, SessionCreateError, UnexpectedDatabaseError};
 use accesso_core::models;
 
-use crate::entities::{SessionToken, User};
+use crate::entities::{AccessTokenUser, SessionToken, SessionUser};
 use crate::mappers::{sqlx_error_to_get_user_by_session_error, sqlx_error_to_session_create_error};
 use crate::Database;
 
@@ -11,12 +11,29 @@ impl SessionRepo for Database {
     async fn get_user_by_session_token(
         &self,
         token: String,
-    ) -> Result<models::User, GetUserBySessionError> {
+    ) -> Result<(models::SessionToken, models::User), GetUserBySessionError> {
         sqlx::query_as!(
-            User,
+            SessionUser,
             // language=PostgreSQL
             r#"
-            SELECT users.*
+            SELECT users.id,
+                   users.email,
+                   users.password_hash,
+                   users.first_name,
+                   users.last_name,
+                   users.canonical_email,
+                   users.locale,
+                   users.email_status,
+                   users.email_verified,
+                   st.id AS session_id,
+                   st.token,
+                   st.expires_at,
+                   st.absolute_expires_at,
+                   st.authenticated_at,
+                   st.user_agent,
+                   st.ip,
+                   st.created_at,
+                   st.last_seen_at
                 FROM users
                          INNER JOIN session_tokens st ON users.id = st.user_id
                 WHERE st.token = $1
@@ -34,12 +51,25 @@ impl SessionRepo for Database {
     async fn get_user_by_access_token(
         &self,
         token: String,
-    ) -> Result<models::User, GetUserBySessionError> {
+    ) -> Result<(models::AccessToken, models::User), GetUserBySessionError> {
         sqlx::query_as!(
-            User,
+            AccessTokenUser,
             // language=PostgreSQL
             r#"
-            SELECT users.*
+            SELECT users.id,
+                   users.email,
+                   users.password_hash,
+                   users.first_name,
+                   users.last_name,
+                   users.canonical_email,
+                   users.locale,
+                   users.email_status,
+                   users.email_verified,
+                   access_tokens.token,
+                   access_tokens.scopes,
+                   access_tokens.expires_at,
+                   access_tokens.registration_id,
+                   access_tokens.dpop_jkt
                 FROM users
                          INNER JOIN user_registrations ON users.id = user_registrations.user_id
                          INNER JOIN access_tokens ON user_registrations.id = access_tokens.registration_id
@@ -66,13 +96,20 @@ impl SessionRepo for Database {
             // language=PostgreSQL
             r#"
             INSERT INTO session_tokens
-                (user_id, token, expires_at)
-                VALUES ($1, $2, $3)
-                RETURNING user_id, token, expires_at
+                (id, user_id, token, expires_at, absolute_expires_at, authenticated_at, user_agent, ip, created_at, last_seen_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                RETURNING id, user_id, token, expires_at, absolute_expires_at, authenticated_at, user_agent, ip, created_at, last_seen_at
             "#,
+            session.id,
             session.user_id,
             session.token,
-            session.expires_at
+            session.expires_at,
+            session.absolute_expires_at,
+            session.authenticated_at,
+            session.user_agent,
+            session.ip,
+            session.created_at,
+            session.last_seen_at
         )
         .fetch_one(&self.pool)
         .await
@@ -117,4 +154,176 @@ impl SessionRepo for Database {
 
         Ok(())
     }
+
+    async fn session_delete_by_id(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+    ) -> Result<bool, UnexpectedDatabaseError> {
+        let deleted = sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM session_tokens
+            WHERE id = $1
+              AND user_id = $2
+            "#,
+            session_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        Ok(deleted > 0)
+    }
+
+    async fn sessions_list_for_user(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<models::SessionToken>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_as!(
+            SessionToken,
+            // language=PostgreSQL
+            r#"
+            SELECT id, user_id, token, expires_at, absolute_expires_at, authenticated_at, user_agent, ip, created_at, last_seen_at
+            FROM session_tokens
+            WHERE user_id = $1
+              AND expires_at > now()
+            ORDER BY last_seen_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    async fn session_mark_seen(
+        &self,
+        session_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE session_tokens
+            SET last_seen_at = now()
+            WHERE id = $1
+            "#,
+            session_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn session_extend(
+        &self,
+        session_id: uuid::Uuid,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE session_tokens
+            SET expires_at   = $2,
+                last_seen_at = now()
+            WHERE id = $1
+            "#,
+            session_id,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn sessions_delete_expired(
+        &self,
+        before: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<u64, UnexpectedDatabaseError> {
+        Ok(sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM session_tokens
+            WHERE ctid IN (SELECT ctid FROM session_tokens WHERE expires_at < $1 LIMIT $2)
+            "#,
+            before,
+            limit
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn session_reauthentication_request(
+        &self,
+        user_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO reauthentication_requests (user_id, client_id, requested_at)
+            VALUES ($1, $2, now())
+            ON CONFLICT (user_id, client_id) DO UPDATE SET requested_at = excluded.requested_at
+            "#,
+            user_id,
+            client_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn session_reauthentication_take(
+        &self,
+        user_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, UnexpectedDatabaseError> {
+        Ok(sqlx::query_scalar!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM reauthentication_requests
+            WHERE user_id = $1
+              AND client_id = $2
+            RETURNING requested_at
+            "#,
+            user_id,
+            client_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .filter(|requested_at| *requested_at >= since))
+    }
+
+    async fn session_client_add(
+        &self,
+        session_id: uuid::Uuid,
+        client_id: uuid::Uuid,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            INSERT INTO session_clients (session_id, client_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            session_id,
+            client_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -4,9 +4,11 @@ use accesso_core::contracts::{
     UserRegisterForm,
 };
 use accesso_core::models;
+use accesso_core::services::canonical_email;
 
 use crate::entities::User;
 use crate::mappers::{sqlx_error_to_account_edit_error, sqlx_error_to_register_user_error};
+use crate::repos::email_outbox;
 use crate::Database;
 
 #[async_trait]
@@ -15,9 +17,10 @@ impl UserRepo for Database {
         Ok(sqlx::query_scalar!(
             // language=PostgreSQL
             r#"
-            SELECT EXISTS(SELECT 1 FROM users WHERE canonical_email = $1) AS "exists!"
+            SELECT EXISTS(SELECT 1 FROM users WHERE canonical_email IN ($1, $2)) AS "exists!"
             "#,
-            email.to_lowercase()
+            canonical_email(&email),
+            legacy_canonical_email(&email)
         )
         .fetch_one(&self.pool)
         .await?)
@@ -26,34 +29,95 @@ impl UserRepo for Database {
     async fn user_register(
         &self,
         form: UserRegisterForm,
+        email: Option<models::OutboxEmailForm>,
     ) -> Result<models::User, RegisterUserError> {
         let user = User {
             id: uuid::Uuid::new_v4(),
             email: form.email.clone(),
-            canonical_email: form.email.to_lowercase(),
+            canonical_email: canonical_email(&form.email),
             first_name: form.first_name,
             last_name: form.last_name,
-            password_hash: form.password_hash.trim_end_matches('\u{0}').to_owned(),
+            password_hash: form.password_hash,
+            locale: form.locale,
+            // Confirmation code is received, so the email is deliverable
+            email_status: models::EmailStatus::Deliverable.as_str().to_owned(),
+            email_verified: form.email_verified,
         };
+        let mut transaction = self
+            .pool
+            .begin()
+            .await
+            .map_err(sqlx_error_to_register_user_error)?;
 
         sqlx::query!(
             // language=PostgreSQL
             r#"
             INSERT INTO users
-                (id, email, canonical_email, first_name, last_name, password_hash)
-            VALUES ($1, $2, $3, $4, $5, $6)
+                (id, email, canonical_email, first_name, last_name, password_hash, locale, email_verified)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
             user.id,
             user.email,
             user.canonical_email,
             user.first_name,
             user.last_name,
-            user.password_hash
+            user.password_hash,
+            user.locale,
+            user.email_verified
         )
-        .execute(&self.pool)
+        .execute(&mut transaction)
+        .await
+        .map_err(sqlx_error_to_register_user_error)?;
+
+        // Confirmation code is received, earlier bounces of the mailbox are outdated
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            DELETE
+            FROM email_suppressions
+            WHERE canonical_email = $1
+            "#,
+            user.canonical_email
+        )
+        .execute(&mut transaction)
         .await
         .map_err(sqlx_error_to_register_user_error)?;
 
+        if let Some(invite) = form.invite {
+            // Concurrent confirmations with the same invite wait for each other here,
+            // only the first one finds it unused
+            let claimed = sqlx::query!(
+                // language=PostgreSQL
+                r#"
+                UPDATE application_invites
+                SET user_id       = $2,
+                    registered_at = now()
+                WHERE invite = $1
+                  AND registered_at IS NULL
+                "#,
+                invite,
+                user.id
+            )
+            .execute(&mut transaction)
+            .await
+            .map_err(sqlx_error_to_register_user_error)?;
+
+            if claimed.rows_affected() == 0 {
+                return Err(RegisterUserError::InviteAlreadyUsed);
+            }
+        }
+
+        if let Some(email) = email {
+            email_outbox::enqueue(&mut transaction, &email)
+                .await
+                .map_err(sqlx_error_to_register_user_error)?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(sqlx_error_to_register_user_error)?;
+
         Ok(Into::into(user))
     }
 
@@ -71,11 +135,18 @@ impl UserRepo for Database {
                    password_hash,
                    first_name,
                    last_name,
-                   canonical_email
+                   canonical_email,
+                   locale,
+                   email_status,
+                   email_verified
             FROM users
-            WHERE canonical_email = $1
+            WHERE canonical_email IN ($1, $2)
+            -- Account keeping the old canonical email after a collision is found by exact email
+            ORDER BY canonical_email = $2 DESC
+            LIMIT 1
             "#,
-            creds.email.to_lowercase()
+            canonical_email(&creds.email),
+            legacy_canonical_email(&creds.email)
         )
         .fetch_optional(&self.pool)
         .await?
@@ -135,7 +206,16 @@ impl UserRepo for Database {
             // language=PostgreSQL
             r#"
             UPDATE users
-            SET first_name = $2, last_name = $3, email = $4, canonical_email = $5
+            SET first_name = $2, last_name = $3, email = $4, canonical_email = $5, locale = $6,
+                -- Status belongs to the address, a new one is deliverable unless suppressed
+                email_status = CASE
+                                   WHEN canonical_email = $5::varchar THEN email_status
+                                   ELSE coalesce((SELECT status
+                                                  FROM email_suppressions
+                                                  WHERE email_suppressions.canonical_email = $5::varchar),
+                                                 'deliverable') END,
+                -- New email is not confirmed by anyone
+                email_verified = canonical_email = $5::varchar AND email_verified
             WHERE id = $1
             RETURNING users.*
             "#,
@@ -144,8 +224,9 @@ impl UserRepo for Database {
             form.last_name.unwrap_or(user.last_name),
             form.email.clone().unwrap_or(user.email),
             form.email
-                .map(|email| email.to_lowercase())
+                .map(|email| canonical_email(&email))
                 .unwrap_or(user.canonical_email),
+            form.locale.unwrap_or(user.locale),
         )
         .fetch_optional(&self.pool)
         .await
@@ -164,7 +245,10 @@ impl UserRepo for Database {
                    password_hash,
                    first_name,
                    last_name,
-                   canonical_email
+                   canonical_email,
+                   locale,
+                   email_status,
+                   email_verified
                 FROM users
                 "#,
         )
@@ -187,7 +271,10 @@ impl UserRepo for Database {
                password_hash,
                first_name,
                last_name,
-               canonical_email
+               canonical_email,
+               locale,
+               email_status,
+               email_verified
             FROM users
             WHERE email ILIKE $1
                 OR first_name ILIKE $1
@@ -218,10 +305,37 @@ impl UserRepo for Database {
             RETURNING users.*
             "#,
             user_id,
-            password_hash.trim_end_matches('\u{0}').to_owned(),
+            password_hash,
         )
         .fetch_optional(&self.pool)
         .await?
         .map(Into::into))
     }
+
+    async fn user_set_canonical_email(
+        &self,
+        user_id: uuid::Uuid,
+        canonical_email: String,
+    ) -> Result<(), UnexpectedDatabaseError> {
+        sqlx::query!(
+            // language=PostgreSQL
+            r#"
+            UPDATE users
+            SET canonical_email = $2
+            WHERE id = $1
+            "#,
+            user_id,
+            canonical_email,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Canonical email before provider rules. Users sharing a mailbox with another account
+/// and users not recalculated yet by `accesso-maintenance canonicalize-emails` still have it
+fn legacy_canonical_email(email: &str) -> String {
+    email.trim().to_lowercase()
 }
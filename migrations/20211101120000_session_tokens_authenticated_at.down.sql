@@ -0,0 +1,2 @@
+ALTER TABLE "session_tokens"
+    DROP COLUMN "authenticated_at";
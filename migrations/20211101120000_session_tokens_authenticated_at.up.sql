@@ -0,0 +1,2 @@
+ALTER TABLE "session_tokens"
+    ADD COLUMN "authenticated_at" timestamptz NOT NULL DEFAULT now();
@@ -0,0 +1,5 @@
+DROP TABLE "logout_notifications";
+
+ALTER TABLE "clients"
+    DROP COLUMN "post_logout_redirect_uri",
+    DROP COLUMN "backchannel_logout_uri";
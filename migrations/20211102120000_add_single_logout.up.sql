@@ -0,0 +1,18 @@
+ALTER TABLE "clients"
+    ADD COLUMN "post_logout_redirect_uri" text[] NOT NULL DEFAULT '{}',
+    ADD COLUMN "backchannel_logout_uri"   varchar NULL;
+
+CREATE TABLE "logout_notifications"
+(
+    "id"              uuid        NOT NULL DEFAULT uuid_generate_v4(),
+    "client_id"       uuid        NOT NULL REFERENCES clients (id) ON DELETE CASCADE,
+    "user_id"         uuid        NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "created_at"      timestamptz NOT NULL DEFAULT now(),
+    "attempts"        integer     NOT NULL DEFAULT 0,
+    "next_attempt_at" timestamptz NOT NULL DEFAULT now(),
+    "delivered_at"    timestamptz NULL,
+    PRIMARY KEY ("id")
+);
+
+CREATE INDEX "logout_notifications_pending" ON "logout_notifications" USING btree ("next_attempt_at")
+    WHERE "delivered_at" IS NULL;
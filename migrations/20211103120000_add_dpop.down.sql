@@ -0,0 +1,5 @@
+ALTER TABLE "access_tokens"
+    DROP COLUMN "dpop_jkt";
+
+ALTER TABLE "clients"
+    DROP COLUMN "require_dpop";
@@ -0,0 +1,6 @@
+ALTER TABLE "clients"
+    ADD COLUMN "require_dpop" boolean NOT NULL DEFAULT false;
+
+-- JWK SHA-256 thumbprint of the key access token is bound to
+ALTER TABLE "access_tokens"
+    ADD COLUMN "dpop_jkt" varchar NULL;
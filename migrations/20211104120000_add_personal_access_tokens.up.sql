@@ -0,0 +1,15 @@
+CREATE TABLE "personal_access_tokens"
+(
+    "id"           uuid        NOT NULL DEFAULT uuid_generate_v4(),
+    "user_id"      uuid        NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "name"         varchar     NOT NULL,
+    "token"        varchar     NOT NULL,
+    "scopes"       text[]      NOT NULL DEFAULT '{}',
+    "created_at"   timestamptz NOT NULL DEFAULT now(),
+    "expires_at"   timestamptz NOT NULL,
+    "last_used_at" timestamptz NULL,
+    PRIMARY KEY ("id"),
+    UNIQUE ("token")
+);
+
+CREATE INDEX "personal_access_tokens_user_id" ON "personal_access_tokens" USING btree ("user_id");
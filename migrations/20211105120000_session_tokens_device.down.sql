@@ -0,0 +1,6 @@
+ALTER TABLE "session_tokens"
+    DROP COLUMN "id",
+    DROP COLUMN "user_agent",
+    DROP COLUMN "ip",
+    DROP COLUMN "created_at",
+    DROP COLUMN "last_seen_at";
@@ -0,0 +1,9 @@
+ALTER TABLE "session_tokens"
+    ADD COLUMN "id"           uuid        NOT NULL DEFAULT uuid_generate_v4(),
+    ADD COLUMN "user_agent"   varchar     NULL,
+    ADD COLUMN "ip"           varchar     NULL,
+    ADD COLUMN "created_at"   timestamptz NOT NULL DEFAULT now(),
+    ADD COLUMN "last_seen_at" timestamptz NOT NULL DEFAULT now();
+
+ALTER TABLE "session_tokens"
+    ADD CONSTRAINT "session_tokens_id_key" UNIQUE ("id");
@@ -0,0 +1,8 @@
+ALTER TABLE "session_tokens"
+    ADD COLUMN "absolute_expires_at" timestamptz NULL;
+
+UPDATE "session_tokens"
+SET "absolute_expires_at" = "expires_at";
+
+ALTER TABLE "session_tokens"
+    ALTER COLUMN "absolute_expires_at" SET NOT NULL;
@@ -0,0 +1,4 @@
+DROP INDEX "session_tokens_expires_at";
+DROP INDEX "access_tokens_expires_at";
+DROP INDEX "authorization_codes_created_at";
+DROP INDEX "registration_requests_expires_at";
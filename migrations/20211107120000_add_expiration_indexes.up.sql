@@ -0,0 +1,5 @@
+-- Used by maintenance worker to find expired rows
+CREATE INDEX "session_tokens_expires_at" ON "session_tokens" USING btree ("expires_at");
+CREATE INDEX "access_tokens_expires_at" ON "access_tokens" USING btree ("expires_at");
+CREATE INDEX "authorization_codes_created_at" ON "authorization_codes" USING btree ("created_at");
+CREATE INDEX "registration_requests_expires_at" ON "registration_requests" USING btree ("expires_at");
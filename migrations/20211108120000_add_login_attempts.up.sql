@@ -0,0 +1,14 @@
+CREATE TABLE "login_attempts"
+(
+    "kind"            varchar     NOT NULL,
+    "key"             varchar     NOT NULL,
+    "failures"        integer     NOT NULL DEFAULT 0,
+    "last_failure_at" timestamptz NOT NULL DEFAULT now(),
+    "locked_until"    timestamptz NULL,
+    "unlock_code"     varchar     NULL,
+    PRIMARY KEY ("kind", "key"),
+    UNIQUE ("unlock_code")
+);
+
+-- Used by maintenance worker to find stale counters
+CREATE INDEX "login_attempts_last_failure_at" ON "login_attempts" USING btree ("last_failure_at");
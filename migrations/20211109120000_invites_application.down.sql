@@ -0,0 +1,2 @@
+ALTER TABLE "application_invites"
+    DROP COLUMN "application_id";
@@ -0,0 +1,4 @@
+ALTER TABLE "application_invites"
+    ADD COLUMN "application_id" uuid NULL REFERENCES clients (id) ON DELETE CASCADE;
+
+CREATE INDEX "application_invites_application_id" ON "application_invites" USING btree ("application_id");
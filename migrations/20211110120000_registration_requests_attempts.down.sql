@@ -0,0 +1,6 @@
+DROP INDEX "registration_requests_email";
+
+ALTER TABLE "registration_requests"
+    DROP COLUMN "created_at",
+    DROP COLUMN "last_sent_at",
+    DROP COLUMN "failed_attempts";
@@ -0,0 +1,6 @@
+ALTER TABLE "registration_requests"
+    ADD COLUMN "created_at"      timestamptz NOT NULL DEFAULT now(),
+    ADD COLUMN "last_sent_at"    timestamptz NOT NULL DEFAULT now(),
+    ADD COLUMN "failed_attempts" integer     NOT NULL DEFAULT 0;
+
+CREATE INDEX "registration_requests_email" ON "registration_requests" USING btree (lower("email"));
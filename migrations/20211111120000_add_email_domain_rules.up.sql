@@ -0,0 +1,15 @@
+CREATE TABLE "email_domain_rules"
+(
+    "id"             uuid        NOT NULL,
+    "domain"         varchar     NOT NULL,
+    "kind"           varchar     NOT NULL,
+    "application_id" uuid        NULL REFERENCES clients (id) ON DELETE CASCADE,
+    "created_at"     timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY ("id")
+);
+
+-- Global rules have no application, NULLs are distinct in unique constraints
+CREATE UNIQUE INDEX "email_domain_rules_unique" ON "email_domain_rules" USING btree (
+    "domain", "kind", coalesce("application_id", '00000000-0000-0000-0000-000000000000'));
+
+CREATE INDEX "email_domain_rules_application_id" ON "email_domain_rules" USING btree ("application_id");
@@ -0,0 +1,2 @@
+-- Canonical emails are recalculated with provider rules by `accesso-maintenance canonicalize-emails`
+CREATE INDEX "users_canonical_email" ON "users" USING btree ("canonical_email");
@@ -0,0 +1,19 @@
+CREATE TABLE "email_outbox"
+(
+    "id"              uuid        NOT NULL DEFAULT uuid_generate_v4(),
+    "recipient"       varchar     NOT NULL,
+    "message"         jsonb       NOT NULL,
+    "idempotency_key" varchar     NOT NULL,
+    "created_at"      timestamptz NOT NULL DEFAULT now(),
+    "attempts"        integer     NOT NULL DEFAULT 0,
+    "next_attempt_at" timestamptz NOT NULL DEFAULT now(),
+    "sent_at"         timestamptz NULL,
+    "failed_at"       timestamptz NULL,
+    "last_error"      varchar     NULL,
+    PRIMARY KEY ("id")
+);
+
+CREATE UNIQUE INDEX "email_outbox_idempotency_key" ON "email_outbox" USING btree ("idempotency_key");
+
+CREATE INDEX "email_outbox_pending" ON "email_outbox" USING btree ("next_attempt_at")
+    WHERE "sent_at" IS NULL AND "failed_at" IS NULL;
@@ -0,0 +1,8 @@
+ALTER TABLE "email_outbox"
+    DROP COLUMN "locale";
+
+ALTER TABLE "registration_requests"
+    DROP COLUMN "locale";
+
+ALTER TABLE "users"
+    DROP COLUMN "locale";
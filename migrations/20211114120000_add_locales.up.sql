@@ -0,0 +1,8 @@
+ALTER TABLE "users"
+    ADD COLUMN "locale" varchar NOT NULL DEFAULT 'en';
+
+ALTER TABLE "registration_requests"
+    ADD COLUMN "locale" varchar NOT NULL DEFAULT 'en';
+
+ALTER TABLE "email_outbox"
+    ADD COLUMN "locale" varchar NOT NULL DEFAULT 'en';
@@ -0,0 +1,7 @@
+DROP TABLE "email_events";
+
+ALTER TABLE "registration_requests"
+    DROP COLUMN "email_status";
+
+ALTER TABLE "users"
+    DROP COLUMN "email_status";
@@ -0,0 +1,24 @@
+ALTER TABLE "users"
+    ADD COLUMN "email_status" varchar NOT NULL DEFAULT 'deliverable';
+
+ALTER TABLE "registration_requests"
+    ADD COLUMN "email_status" varchar NOT NULL DEFAULT 'deliverable';
+
+CREATE TABLE "email_events"
+(
+    "id"                uuid        NOT NULL DEFAULT uuid_generate_v4(),
+    "provider"          varchar     NOT NULL,
+    "provider_event_id" varchar     NOT NULL,
+    "email"             varchar     NOT NULL,
+    "canonical_email"   varchar     NOT NULL,
+    "status"            varchar     NOT NULL,
+    "reason"            varchar     NULL,
+    "occurred_at"       timestamptz NOT NULL,
+    "created_at"        timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY ("id")
+);
+
+-- Providers retry webhooks, the same event is recorded once
+CREATE UNIQUE INDEX "email_events_provider_event" ON "email_events" USING btree ("provider", "provider_event_id");
+
+CREATE INDEX "email_events_canonical_email" ON "email_events" USING btree ("canonical_email");
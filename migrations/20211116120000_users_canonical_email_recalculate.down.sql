@@ -0,0 +1,3 @@
+-- Recalculated canonical emails are kept, the old ones are not stored
+DROP INDEX "users_canonical_email";
+CREATE INDEX "users_canonical_email" ON "users" USING btree ("canonical_email");
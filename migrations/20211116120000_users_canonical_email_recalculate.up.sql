@@ -0,0 +1,73 @@
+-- Provider rules of `canonical_email` as of this migration, for ASCII domains only.
+-- Other domains need punycode and keep `lower(email)`, login falls back to it
+-- until `accesso-maintenance canonicalize-emails` recalculates them
+CREATE FUNCTION pg_temp.canonical_email(address varchar) RETURNS varchar AS
+$$
+DECLARE
+    local    varchar;
+    domain   varchar;
+    provider varchar;
+BEGIN
+    address := lower(trim(address));
+
+    IF position('@' IN address) = 0 THEN
+        RETURN address;
+    END IF;
+
+    local := substring(address FROM '^(.*)@[^@]*$');
+    domain := rtrim(substring(address FROM '@([^@]*)$'), '.');
+
+    IF octet_length(domain) <> char_length(domain) THEN
+        RETURN address;
+    END IF;
+
+    provider := CASE
+        WHEN domain IN ('gmail.com', 'googlemail.com') THEN 'gmail.com'
+        WHEN domain IN ('icloud.com', 'me.com', 'mac.com') THEN 'icloud.com'
+        WHEN domain IN ('proton.me', 'protonmail.com', 'protonmail.ch', 'pm.me') THEN 'proton.me'
+        WHEN domain IN ('yandex.ru', 'yandex.com', 'ya.ru', 'yandex.by', 'yandex.kz', 'yandex.ua')
+            THEN 'yandex.ru'
+        WHEN domain IN ('outlook.com', 'hotmail.com', 'live.com', 'fastmail.com') THEN domain
+        END;
+
+    IF provider IS NULL THEN
+        RETURN local || '@' || domain;
+    END IF;
+
+    -- Address consisting of a tag only is left as is
+    IF position('+' IN local) > 1 THEN
+        local := split_part(local, '+', 1);
+    END IF;
+
+    IF provider = 'gmail.com' THEN
+        local := replace(local, '.', '');
+    END IF;
+
+    RETURN local || '@' || provider;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+-- One account of each mailbox gets the canonical email: the one already having it,
+-- then the one registered with the canonical spelling, then the first by email.
+-- Others keep the old one and sign in with their exact email
+WITH recalculated AS (
+    SELECT id,
+           pg_temp.canonical_email(email) AS canonical,
+           row_number() OVER (
+               PARTITION BY pg_temp.canonical_email(email)
+               ORDER BY canonical_email = pg_temp.canonical_email(email) DESC,
+                        lower(email) = pg_temp.canonical_email(email) DESC,
+                        email,
+                        id
+               ) AS rank
+    FROM users
+)
+UPDATE users
+SET canonical_email = recalculated.canonical
+FROM recalculated
+WHERE users.id = recalculated.id
+  AND recalculated.rank = 1
+  AND users.canonical_email <> recalculated.canonical;
+
+DROP INDEX "users_canonical_email";
+CREATE UNIQUE INDEX "users_canonical_email" ON "users" USING btree ("canonical_email");
@@ -0,0 +1,3 @@
+-- Invite is claimed on confirmation, requests of a deleted invite can't be confirmed
+ALTER TABLE "registration_requests"
+    ADD COLUMN "invite" varchar REFERENCES application_invites (invite) ON DELETE CASCADE;
@@ -0,0 +1,8 @@
+DROP INDEX "registration_requests_canonical_email";
+CREATE INDEX "registration_requests_email" ON "registration_requests" USING btree (lower("email"));
+
+ALTER TABLE "registration_requests"
+    DROP COLUMN "canonical_email",
+    ADD COLUMN "failed_attempts" integer NOT NULL DEFAULT 0;
+
+DROP TABLE "registration_failures";
@@ -0,0 +1,22 @@
+-- Wrong confirmation codes are counted per mailbox, new requests don't reset them
+CREATE TABLE "registration_failures"
+(
+    "canonical_email"   varchar     NOT NULL,
+    "failed_attempts"   integer     NOT NULL,
+    "window_started_at" timestamptz NOT NULL,
+    PRIMARY KEY ("canonical_email")
+);
+
+-- Requests live for a day, provider rules are applied to new ones only
+ALTER TABLE "registration_requests"
+    ADD COLUMN "canonical_email" varchar;
+
+UPDATE "registration_requests"
+SET "canonical_email" = lower(trim("email"));
+
+ALTER TABLE "registration_requests"
+    ALTER COLUMN "canonical_email" SET NOT NULL,
+    DROP COLUMN "failed_attempts";
+
+DROP INDEX "registration_requests_email";
+CREATE INDEX "registration_requests_canonical_email" ON "registration_requests" USING btree ("canonical_email");
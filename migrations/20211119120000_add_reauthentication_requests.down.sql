@@ -0,0 +1 @@
+DROP TABLE "reauthentication_requests";
@@ -0,0 +1,8 @@
+-- Application asked the user to enter credentials again with `prompt=login`
+CREATE TABLE "reauthentication_requests"
+(
+    "user_id"      uuid        NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "client_id"    uuid        NOT NULL REFERENCES clients (id) ON DELETE CASCADE,
+    "requested_at" timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY ("user_id", "client_id")
+);
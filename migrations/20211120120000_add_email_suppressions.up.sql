@@ -0,0 +1,18 @@
+-- Bounced or complained addresses, kept after their users and register requests are gone
+CREATE TABLE "email_suppressions"
+(
+    "canonical_email" varchar     NOT NULL,
+    "status"          varchar     NOT NULL,
+    "updated_at"      timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY ("canonical_email")
+);
+
+INSERT INTO email_suppressions (canonical_email, status)
+SELECT canonical_email, email_status
+FROM users
+WHERE email_status <> 'deliverable'
+UNION ALL
+SELECT canonical_email, email_status
+FROM registration_requests
+WHERE email_status <> 'deliverable'
+ON CONFLICT (canonical_email) DO NOTHING;
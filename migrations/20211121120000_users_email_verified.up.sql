@@ -0,0 +1,6 @@
+-- Accounts so far were created only after the confirmation code was received
+ALTER TABLE "users"
+    ADD COLUMN "email_verified" BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE "users"
+    ALTER COLUMN "email_verified" DROP DEFAULT;
@@ -0,0 +1 @@
+DROP TABLE "dpop_proofs";
@@ -0,0 +1,10 @@
+-- Proofs accepted recently, each `jti` of the key is accepted once
+CREATE TABLE "dpop_proofs"
+(
+    "jkt"        varchar     NOT NULL,
+    "jti"        varchar     NOT NULL,
+    "expires_at" timestamptz NOT NULL,
+    PRIMARY KEY ("jkt", "jti")
+);
+
+CREATE INDEX "dpop_proofs_expires_at" ON "dpop_proofs" USING btree ("expires_at");
@@ -0,0 +1,4 @@
+ALTER TABLE "logout_notifications"
+    DROP COLUMN "session_id";
+
+DROP TABLE "session_clients";
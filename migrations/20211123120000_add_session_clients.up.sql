@@ -0,0 +1,10 @@
+-- Applications authorized within the session, notified with `sid` when it ends
+CREATE TABLE "session_clients"
+(
+    "session_id" uuid NOT NULL REFERENCES session_tokens (id) ON DELETE CASCADE,
+    "client_id"  uuid NOT NULL REFERENCES clients (id) ON DELETE CASCADE,
+    PRIMARY KEY ("session_id", "client_id")
+);
+
+ALTER TABLE "logout_notifications"
+    ADD COLUMN "session_id" uuid NULL;
@@ -0,0 +1 @@
+DROP INDEX "registration_failures_window_started_at";
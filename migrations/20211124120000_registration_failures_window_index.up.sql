@@ -0,0 +1,2 @@
+-- Stale failure counters are purged by maintenance
+CREATE INDEX "registration_failures_window_started_at" ON "registration_failures" USING btree ("window_started_at");
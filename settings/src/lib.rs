@@ -12,7 +12,16 @@ pub struct Settings {
     pub database: Database,
     pub cookies: Cookies,
     pub server: Server,
+    pub email: Email,
     pub sendgrid: SendGrid,
+    pub smtp: Smtp,
+    pub logout: Logout,
+    pub session: SessionLifetime,
+    pub maintenance: Maintenance,
+    pub login_protection: LoginProtection,
+    pub password_hashing: PasswordHashing,
+    pub registration: Registration,
+    pub email_domains: EmailDomains,
     pub use_opentelemetry: bool,
 }
 
@@ -34,23 +43,390 @@ pub struct Database {
     pub pool_size: u32,
 }
 
-fn default_sendgrid_enabled() -> bool {
-    true
+fn default_email_sender_name() -> String {
+    "Accesso".to_owned()
+}
+
+fn default_email_outbox_interval() -> u64 {
+    5
+}
+
+fn default_email_transport() -> EmailTransport {
+    EmailTransport::SendGrid
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailTransport {
+    /// `[sendgrid]` api
+    SendGrid,
+    /// `[smtp]` server
+    Smtp,
+    /// Nothing is sent, messages are kept for inspection at `/dev/emails`.
+    /// Only for development and automated tests
+    Capture,
 }
 
+/// Content of the emails, independent of the transport
 #[derive(Debug, Deserialize, Clone)]
-pub struct SendGrid {
-    pub api_key: String,
+pub struct Email {
+    /// `no-reply@accesso.sova.dev`
+    pub sender_email: String,
+    #[serde(default = "default_email_sender_name")]
+    pub sender_name: String,
     /// with port
     pub application_host: String,
     /// `"/register/confirm-"`
     pub email_confirm_url_prefix: String,
-    /// Template ID
-    pub email_confirm_template: String,
-    /// `no-reply@accesso.sova.dev`
-    pub sender_email: String,
+    /// `"/login/unlock-"`
+    pub account_unlock_url_prefix: String,
+    /// Directory with templates replacing the embedded ones,
+    /// with the same layout as `resources/emails`
+    pub templates_dir: Option<String>,
+    #[serde(default = "default_email_transport")]
+    pub transport: EmailTransport,
+    /// How often queued emails are checked by the dispatcher, in seconds
+    #[serde(default = "default_email_outbox_interval")]
+    pub outbox_interval: u64,
+    /// Captured messages are written here, required by the capture transport
+    pub capture_dir: Option<String>,
+}
+
+fn default_sendgrid_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SendGrid {
+    pub api_key: String,
     #[serde(default = "default_sendgrid_enabled")]
     pub enabled: bool,
+    /// Verification key of the Event Webhook, `/webhooks/sendgrid` is enabled when set
+    pub webhook_public_key: Option<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_tls() -> SmtpTls {
+    SmtpTls::StartTls
+}
+
+fn default_smtp_timeout() -> u64 {
+    10
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Plain connection, only for local relays
+    None,
+    /// Plain connection upgraded with STARTTLS, usually port 587
+    StartTls,
+    /// TLS from the start of connection, usually port 465
+    Tls,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Smtp {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    #[serde(default = "default_smtp_tls")]
+    pub tls: SmtpTls,
+    /// Credentials are sent only if both are set
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Seconds to wait for the server response
+    #[serde(default = "default_smtp_timeout")]
+    pub timeout: u64,
+}
+
+fn default_logout_worker_interval() -> u64 {
+    30
+}
+
+fn default_logout_request_timeout() -> u64 {
+    10
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Logout {
+    /// Issuer of the logout tokens: `https://accesso.sova.dev`
+    pub issuer: String,
+    /// How often back-channel logout worker checks pending notifications, in seconds
+    #[serde(default = "default_logout_worker_interval")]
+    pub worker_interval: u64,
+    /// Timeout of the request to the application, in seconds
+    #[serde(default = "default_logout_request_timeout")]
+    pub request_timeout: u64,
+}
+
+fn default_session_idle_lifetime() -> i64 {
+    24
+}
+
+fn default_session_absolute_lifetime() -> i64 {
+    24 * 14
+}
+
+fn default_session_remember_me_lifetime() -> i64 {
+    24 * 90
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SessionLifetime {
+    /// Session expires if it is not used for this long, in hours.
+    /// Each use of the session close to expiration extends it,
+    /// so it must be shorter than `absolute_lifetime`
+    #[serde(default = "default_session_idle_lifetime")]
+    pub idle_lifetime: i64,
+    /// Session cannot be extended after this time since login, in hours.
+    /// Not less than the fixed 14 days lifetime sessions had before
+    #[serde(default = "default_session_absolute_lifetime")]
+    pub absolute_lifetime: i64,
+    /// Absolute lifetime when user asked to remember them, in hours
+    #[serde(default = "default_session_remember_me_lifetime")]
+    pub remember_me_lifetime: i64,
+}
+
+impl Default for SessionLifetime {
+    fn default() -> Self {
+        Self {
+            idle_lifetime: default_session_idle_lifetime(),
+            absolute_lifetime: default_session_absolute_lifetime(),
+            remember_me_lifetime: default_session_remember_me_lifetime(),
+        }
+    }
+}
+
+fn default_maintenance_run_in_server() -> bool {
+    true
+}
+
+fn default_maintenance_interval() -> u64 {
+    60 * 60
+}
+
+fn default_maintenance_batch_size() -> i64 {
+    1000
+}
+
+fn default_maintenance_retention() -> i64 {
+    24 * 7
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Maintenance {
+    /// Run maintenance worker inside api servers.
+    /// Disable to run it only as separate `accesso-maintenance` binary
+    #[serde(default = "default_maintenance_run_in_server")]
+    pub run_in_server: bool,
+    /// How often expired rows are purged, in seconds
+    #[serde(default = "default_maintenance_interval")]
+    pub interval: u64,
+    /// Count of rows deleted by one query
+    #[serde(default = "default_maintenance_batch_size")]
+    pub batch_size: i64,
+    /// How long expired rows are kept before deletion, in hours
+    #[serde(default = "default_maintenance_retention")]
+    pub session_tokens_retention: i64,
+    #[serde(default = "default_maintenance_retention")]
+    pub access_tokens_retention: i64,
+    #[serde(default = "default_maintenance_retention")]
+    pub authorization_codes_retention: i64,
+    #[serde(default = "default_maintenance_retention")]
+    pub registration_requests_retention: i64,
+    /// Since sending, emails failed all attempts are kept until deleted manually
+    #[serde(default = "default_maintenance_retention")]
+    pub sent_emails_retention: i64,
+}
+
+fn default_login_protection_account_delay_after() -> i32 {
+    3
+}
+
+fn default_login_protection_account_lockout_after() -> i32 {
+    10
+}
+
+fn default_login_protection_ip_delay_after() -> i32 {
+    10
+}
+
+fn default_login_protection_ip_lockout_after() -> i32 {
+    100
+}
+
+fn default_login_protection_base_delay() -> i64 {
+    1
+}
+
+fn default_login_protection_max_delay() -> i64 {
+    60
+}
+
+fn default_login_protection_lockout_duration() -> i64 {
+    15
+}
+
+fn default_login_protection_failure_window() -> i64 {
+    60
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LoginProtection {
+    /// Failed attempts for one email before each next attempt is delayed
+    #[serde(default = "default_login_protection_account_delay_after")]
+    pub account_delay_after: i32,
+    /// Failed attempts for one email before it is locked out.
+    /// Owner of the account receives an email to unlock it
+    #[serde(default = "default_login_protection_account_lockout_after")]
+    pub account_lockout_after: i32,
+    /// Failed attempts from one IP before each next attempt is delayed
+    #[serde(default = "default_login_protection_ip_delay_after")]
+    pub ip_delay_after: i32,
+    /// Failed attempts from one IP before it is locked out
+    #[serde(default = "default_login_protection_ip_lockout_after")]
+    pub ip_lockout_after: i32,
+    /// First delay, doubled with each next failure, in seconds
+    #[serde(default = "default_login_protection_base_delay")]
+    pub base_delay: i64,
+    /// Delay stops growing at this value, in seconds
+    #[serde(default = "default_login_protection_max_delay")]
+    pub max_delay: i64,
+    /// How long login is not allowed after too many failures, in minutes
+    #[serde(default = "default_login_protection_lockout_duration")]
+    pub lockout_duration: i64,
+    /// Failures are forgotten after this quiet period, in minutes
+    #[serde(default = "default_login_protection_failure_window")]
+    pub failure_window: i64,
+}
+
+fn default_password_hashing_max_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(4)
+}
+
+fn default_password_hashing_algorithm() -> PasswordHashAlgorithm {
+    PasswordHashAlgorithm::Argon2id
+}
+
+fn default_password_hashing_argon2_memory_cost() -> u32 {
+    64 * 1024
+}
+
+fn default_password_hashing_argon2_time_cost() -> u32 {
+    2
+}
+
+fn default_password_hashing_argon2_parallelism() -> u32 {
+    1
+}
+
+fn default_password_hashing_scrypt_log_n() -> u8 {
+    15
+}
+
+fn default_password_hashing_scrypt_r() -> u32 {
+    8
+}
+
+fn default_password_hashing_scrypt_p() -> u32 {
+    1
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordHashAlgorithm {
+    Argon2id,
+    Scrypt,
+}
+
+/// Hashes are stored as PHC strings with algorithm and cost inside.
+/// Hash made with other algorithm or cost is upgraded on successful login
+#[derive(Debug, Deserialize, Clone)]
+pub struct PasswordHashing {
+    /// Count of passwords hashed at the same time on blocking threads by the whole process,
+    /// other hashings wait in the queue. Defaults to count of CPUs
+    #[serde(default = "default_password_hashing_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Algorithm of new hashes
+    #[serde(default = "default_password_hashing_algorithm")]
+    pub algorithm: PasswordHashAlgorithm,
+    /// Argon2 memory size, in KiB
+    #[serde(default = "default_password_hashing_argon2_memory_cost")]
+    pub argon2_memory_cost: u32,
+    /// Argon2 count of iterations
+    #[serde(default = "default_password_hashing_argon2_time_cost")]
+    pub argon2_time_cost: u32,
+    /// Argon2 count of lanes
+    #[serde(default = "default_password_hashing_argon2_parallelism")]
+    pub argon2_parallelism: u32,
+    /// scrypt CPU/memory cost, as log2(N)
+    #[serde(default = "default_password_hashing_scrypt_log_n")]
+    pub scrypt_log_n: u8,
+    /// scrypt block size
+    #[serde(default = "default_password_hashing_scrypt_r")]
+    pub scrypt_r: u32,
+    /// scrypt parallelism
+    #[serde(default = "default_password_hashing_scrypt_p")]
+    pub scrypt_p: u32,
+}
+
+fn default_registration_mode() -> RegistrationMode {
+    RegistrationMode::Open
+}
+
+fn default_registration_max_confirm_attempts() -> i32 {
+    5
+}
+
+fn default_registration_resend_cooldown() -> i64 {
+    60
+}
+
+fn default_registration_max_requests_per_email() -> i64 {
+    3
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistrationMode {
+    /// Anyone can register with an email
+    Open,
+    /// Registration request and confirmation require a valid unused invite
+    Invite,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Registration {
+    #[serde(default = "default_registration_mode")]
+    pub mode: RegistrationMode,
+    /// Wrong codes entered for one mailbox within a day before its requests are deleted
+    #[serde(default = "default_registration_max_confirm_attempts")]
+    pub max_confirm_attempts: i32,
+    /// Code cannot be sent again, or by a new request, before, in seconds
+    #[serde(default = "default_registration_resend_cooldown")]
+    pub resend_cooldown: i64,
+    /// Requests kept for one email at the same time, older ones are deleted
+    #[serde(default = "default_registration_max_requests_per_email")]
+    pub max_requests_per_email: i64,
+}
+
+fn default_email_domains_block_disposable() -> bool {
+    true
+}
+
+/// Allow and deny rules are managed by admins, these settings apply to all of them
+#[derive(Debug, Deserialize, Clone)]
+pub struct EmailDomains {
+    /// Reject domains from the bundled list of throwaway email services,
+    /// unless an allow rule matches the domain
+    #[serde(default = "default_email_domains_block_disposable")]
+    pub block_disposable: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -61,6 +437,9 @@ pub struct Server {
     pub backlog: Option<u32>,
     pub keep_alive: Option<u16>,
     pub client_shutdown: Option<u64>,
+    /// Reverse proxies whose `X-Forwarded-For` is used to get the client address
+    #[serde(default)]
+    pub trusted_proxies: Vec<std::net::IpAddr>,
 }
 
 impl Settings {